@@ -0,0 +1,94 @@
+pub use crate::error::{Error, ErrorKind};
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// Single conversions on one of several input channels.
+pub trait Adc: ErrorType {
+    /// Channel identifier, typically an enum of the SoC's analog inputs.
+    type Channel: Copy;
+
+    /// Resolution of a conversion result in bits.
+    fn resolution_bits(&self) -> u8;
+
+    /// Full-scale reference voltage in millivolts.
+    fn reference_mv(&self) -> u32;
+
+    /// Starts a conversion on `channel` and blocks until it completes.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the raw, right-aligned conversion result.
+    fn read(&mut self, channel: Self::Channel) -> Result<u16, Self::Error>;
+
+    /// Converts `channel` and scales the result to millivolts.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the voltage, or [`ScaleError::InvalidResolution`]
+    /// without converting if [`Adc::resolution_bits`] is 0 or 64 and above.
+    fn read_mv(&mut self, channel: Self::Channel) -> Result<u32, ScaleError<Self::Error>> {
+        let full_scale = 1u64
+            .checked_shl(u32::from(self.resolution_bits()))
+            .map(|range| range - 1)
+            .filter(|&full_scale| full_scale != 0)
+            .ok_or(ScaleError::InvalidResolution)?;
+        let raw = u64::from(self.read(channel).map_err(ScaleError::Adc)?);
+        Ok((raw * u64::from(self.reference_mv()) / full_scale) as u32)
+    }
+}
+
+/// Failure of [`Adc::read_mv`].
+#[derive(Debug)]
+pub enum ScaleError<E> {
+    /// The reported resolution cannot be scaled.
+    InvalidResolution,
+    /// The conversion failed.
+    Adc(E),
+}
+
+impl<E: Error> Error for ScaleError<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            ScaleError::InvalidResolution => ErrorKind::InvalidParameter,
+            ScaleError::Adc(e) => e.kind(),
+        }
+    }
+}
+
+/// Conversion of several channels in one hardware sequence.
+pub trait AdcSequence: Adc {
+    /// Longest sequence the hardware can run.
+    const MAX_CHANNELS: usize;
+
+    /// Programs the channels converted by [`AdcSequence::read_sequence`], in order.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success, or an error of kind [`ErrorKind::InvalidParameter`]
+    /// if `channels` is longer than [`AdcSequence::MAX_CHANNELS`].
+    fn set_sequence(&mut self, channels: &[Self::Channel]) -> Result<(), Self::Error>;
+
+    /// Runs the programmed sequence once and writes one result per channel into `results`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of results written.
+    fn read_sequence(&mut self, results: &mut [u16]) -> Result<usize, Self::Error>;
+}
+
+/// Offset and gain calibration.
+pub trait AdcCalibration: Adc {
+    /// Runs the hardware self-calibration. Inputs should be idle meanwhile.
+    fn calibrate(&mut self) -> Result<(), Self::Error>;
+
+    /// Applies a trim from manufacturing data, e.g. read from OTP.
+    ///
+    /// # Parameters
+    ///
+    /// - `offset`: Signed correction added to raw results, in LSBs.
+    /// - `gain`: Gain correction as an unsigned Q1.15 factor, `0x8000` meaning 1.0.
+    fn set_trim(&mut self, offset: i16, gain: u16) -> Result<(), Self::Error>;
+}
@@ -0,0 +1,299 @@
+use core::fmt::Debug;
+
+use crate::common::Operation;
+
+/// Error kind.
+///
+/// This represents a common set of AEAD operation errors. Implementations are
+/// free to define more specific or additional error types. However, by providing
+/// a mapping to these common errors, generic code can still react to them.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The authentication tag did not match the ciphertext and associated data.
+    AuthenticationFailed,
+
+    /// The supplied key is not valid for the algorithm.
+    InvalidKey,
+
+    /// The supplied nonce is not valid for the algorithm.
+    InvalidNonce,
+
+    /// The input or output buffer length is not valid for the operation.
+    InvalidLength,
+
+    /// The specified algorithm is not supported by the hardware or software implementation.
+    UnsupportedAlgorithm,
+
+    /// The hardware accelerator is busy and cannot process the operation.
+    Busy,
+
+    /// General hardware failure during the operation.
+    HardwareFailure,
+
+    /// No further unique nonce can be generated for the key; it must be replaced.
+    NonceExhausted,
+}
+
+pub trait Error: core::fmt::Debug {
+    /// Convert error to a generic error kind
+    ///
+    /// By using this method, errors freely defined by Algo implementations
+    /// can be converted to a set of generic errors upon which generic
+    /// code can act.
+    fn kind(&self) -> ErrorKind;
+}
+
+impl Error for core::convert::Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// Key, nonce and tag types of an AEAD algorithm.
+///
+/// Shared by [`AeadEncrypt`] and [`AeadDecrypt`] so that both halves of an
+/// algorithm agree on the shape of their inputs.
+pub trait AeadTypes {
+    /// Key material or handle used by the algorithm.
+    type Key;
+
+    /// Nonce (IV) type. Typically a fixed-size byte array, e.g. `[u8; 12]` for AES-GCM.
+    type Nonce;
+
+    /// Authentication tag type. Typically a fixed-size byte array, e.g. `[u8; 16]`.
+    type Tag;
+}
+
+/// Authenticated encryption.
+pub trait AeadEncrypt: ErrorType + AeadTypes {
+    /// Encrypts `plaintext` into `ciphertext` and returns a detached tag.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The key to encrypt with.
+    /// - `nonce`: The nonce. It must never be reused with the same key.
+    /// - `associated_data`: Data that is authenticated but not encrypted.
+    /// - `plaintext`: The data to encrypt.
+    /// - `ciphertext`: Output buffer. Its length must equal the length of `plaintext`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the authentication tag on success, or an error of type `Self::Error`.
+    fn encrypt_detached(
+        &mut self,
+        key: &Self::Key,
+        nonce: &Self::Nonce,
+        associated_data: &[u8],
+        plaintext: &[u8],
+        ciphertext: &mut [u8],
+    ) -> Result<Self::Tag, Self::Error>;
+}
+
+/// Authenticated decryption.
+pub trait AeadDecrypt: ErrorType + AeadTypes {
+    /// Verifies `tag` and decrypts `ciphertext` into `plaintext`.
+    ///
+    /// Implementations must not release any plaintext when tag verification fails.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The key to decrypt with.
+    /// - `nonce`: The nonce used during encryption.
+    /// - `associated_data`: Data that was authenticated but not encrypted.
+    /// - `ciphertext`: The data to decrypt.
+    /// - `tag`: The detached authentication tag.
+    /// - `plaintext`: Output buffer. Its length must equal the length of `ciphertext`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or failure. On failure to authenticate, returns an
+    /// error whose kind is [`ErrorKind::AuthenticationFailed`].
+    fn decrypt_detached(
+        &mut self,
+        key: &Self::Key,
+        nonce: &Self::Nonce,
+        associated_data: &[u8],
+        ciphertext: &[u8],
+        tag: &Self::Tag,
+        plaintext: &mut [u8],
+    ) -> Result<(), Self::Error>;
+}
+
+/// Authenticated encryption on an interrupt-driven engine.
+pub trait AeadEncryptStart: ErrorType + AeadTypes {
+    type EncryptOperation<'a>: Operation<Output = Self::Tag, Error = Self::Error>
+    where
+        Self: 'a;
+
+    /// Starts encrypting `plaintext` into `ciphertext`. Completes like
+    /// [`AeadEncrypt::encrypt_detached`].
+    fn start_encrypt_detached<'a>(
+        &'a mut self,
+        key: &'a Self::Key,
+        nonce: &'a Self::Nonce,
+        associated_data: &'a [u8],
+        plaintext: &'a [u8],
+        ciphertext: &'a mut [u8],
+    ) -> Result<Self::EncryptOperation<'a>, Self::Error>;
+}
+
+/// Authenticated decryption on an interrupt-driven engine.
+pub trait AeadDecryptStart: ErrorType + AeadTypes {
+    type DecryptOperation<'a>: Operation<Output = (), Error = Self::Error>
+    where
+        Self: 'a;
+
+    /// Starts verifying and decrypting `ciphertext` into `plaintext`. Completes
+    /// like [`AeadDecrypt::decrypt_detached`].
+    ///
+    /// Until the operation completes successfully, `plaintext` must be treated
+    /// as unauthenticated.
+    fn start_decrypt_detached<'a>(
+        &'a mut self,
+        key: &'a Self::Key,
+        nonce: &'a Self::Nonce,
+        associated_data: &'a [u8],
+        ciphertext: &'a [u8],
+        tag: &'a Self::Tag,
+        plaintext: &'a mut [u8],
+    ) -> Result<Self::DecryptOperation<'a>, Self::Error>;
+}
+
+/// Marker for nonce-misuse-resistant AEADs, such as AES-GCM-SIV (RFC 8452).
+///
+/// Encrypting twice under the same key and nonce only reveals whether the
+/// associated data and plaintext were identical; confidentiality and
+/// integrity are otherwise kept. Generic code that cannot guarantee unique
+/// nonces, e.g. across resets without persistent state, can require this
+/// bound instead of [`AeadEncrypt`] alone.
+pub trait NonceMisuseResistant: AeadEncrypt {}
+
+/// Authenticated encryption where the implementation chooses the nonce.
+///
+/// The implementation guarantees that a nonce is never returned twice for
+/// the same key, e.g. from a counter persisted in a monotonic counter or
+/// from a DRBG with a sufficiently large nonce.
+pub trait AeadEncryptManagedNonce: ErrorType + AeadTypes {
+    /// Encrypts `plaintext` into `ciphertext` under a fresh nonce.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The key to encrypt with.
+    /// - `associated_data`: Data that is authenticated but not encrypted.
+    /// - `plaintext`: The data to encrypt.
+    /// - `ciphertext`: Output buffer. Its length must equal the length of `plaintext`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the nonce, to be sent along with the ciphertext,
+    /// and the authentication tag, or an error of kind [`ErrorKind::NonceExhausted`].
+    fn seal_detached(
+        &mut self,
+        key: &Self::Key,
+        associated_data: &[u8],
+        plaintext: &[u8],
+        ciphertext: &mut [u8],
+    ) -> Result<(Self::Nonce, Self::Tag), Self::Error>;
+}
+
+/// Lengths of an AEAD message, for algorithms that need them up front.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct MessageLengths {
+    pub associated_data: u64,
+    pub payload: u64,
+}
+
+/// Multi-part authenticated encryption with bounded memory.
+///
+/// A message is processed as `begin_encrypt`, any number of
+/// `update_encrypt_aad` calls, any number of `update_encrypt` calls and
+/// `finish_encrypt`. Chunks may have any length; implementations buffer at
+/// most one block internally, so payloads far larger than RAM, such as
+/// firmware images, can be processed in small pieces.
+///
+/// Algorithms that need the lengths before processing, such as CCM, fail
+/// `begin_encrypt` with [`ErrorKind::InvalidLength`] when `lengths` is
+/// `None`, and fail `finish_encrypt` the same way if the data fed in does
+/// not match them.
+pub trait AeadEncryptStream: ErrorType + AeadTypes {
+    /// Starts a message, replacing any message in progress.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The key to encrypt with. It is loaded during the call and need not outlive it.
+    /// - `nonce`: The nonce. It must never be reused with the same key.
+    /// - `lengths`: Total lengths, if known.
+    fn begin_encrypt(
+        &mut self,
+        key: &Self::Key,
+        nonce: &Self::Nonce,
+        lengths: Option<MessageLengths>,
+    ) -> Result<(), Self::Error>;
+
+    /// Authenticates the next chunk of associated data.
+    ///
+    /// All associated data must be supplied before the first payload chunk.
+    fn update_encrypt_aad(&mut self, associated_data: &[u8]) -> Result<(), Self::Error>;
+
+    /// Encrypts the next chunk of plaintext into `ciphertext`.
+    ///
+    /// # Parameters
+    ///
+    /// - `plaintext`: The next chunk. May have any length.
+    /// - `ciphertext`: Output buffer. Its length must equal the length of `plaintext`.
+    fn update_encrypt(&mut self, plaintext: &[u8], ciphertext: &mut [u8]) -> Result<(), Self::Error>;
+
+    /// Ends the message.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the authentication tag.
+    fn finish_encrypt(&mut self) -> Result<Self::Tag, Self::Error>;
+}
+
+/// Multi-part authenticated decryption with bounded memory.
+///
+/// Mirrors [`AeadEncryptStream`]. Unlike [`AeadDecrypt::decrypt_detached`],
+/// plaintext is released before the tag is checked: every chunk written by
+/// `update_decrypt` is unauthenticated until `finish_decrypt` succeeds.
+/// Callers must keep it out of use, e.g. in a staging partition, and
+/// discard it if `finish_decrypt` fails.
+pub trait AeadDecryptStream: ErrorType + AeadTypes {
+    /// Starts a message, replacing any message in progress.
+    ///
+    /// See [`AeadEncryptStream::begin_encrypt`] for the parameters.
+    fn begin_decrypt(
+        &mut self,
+        key: &Self::Key,
+        nonce: &Self::Nonce,
+        lengths: Option<MessageLengths>,
+    ) -> Result<(), Self::Error>;
+
+    /// Authenticates the next chunk of associated data.
+    ///
+    /// All associated data must be supplied before the first payload chunk.
+    fn update_decrypt_aad(&mut self, associated_data: &[u8]) -> Result<(), Self::Error>;
+
+    /// Decrypts the next chunk of ciphertext into `plaintext`.
+    ///
+    /// # Parameters
+    ///
+    /// - `ciphertext`: The next chunk. May have any length.
+    /// - `plaintext`: Output buffer. Its length must equal the length of `ciphertext`.
+    fn update_decrypt(&mut self, ciphertext: &[u8], plaintext: &mut [u8]) -> Result<(), Self::Error>;
+
+    /// Ends the message and verifies `tag` in constant time.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success, or an error of kind
+    /// [`ErrorKind::AuthenticationFailed`], in which case all plaintext
+    /// released for the message must be discarded.
+    fn finish_decrypt(&mut self, tag: &Self::Tag) -> Result<(), Self::Error>;
+}
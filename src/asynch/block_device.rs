@@ -0,0 +1,29 @@
+pub use crate::block_device::{Error, ErrorKind, ErrorType};
+
+/// Async block device.
+///
+/// Mirrors [`crate::block_device::BlockDevice`]; geometry queries stay
+/// synchronous as they never touch the hardware.
+#[allow(async_fn_in_trait)]
+pub trait BlockDevice: ErrorType {
+    /// Get size of a readable block
+    fn read_size(&self) -> usize;
+    async fn read(&mut self, block_addr: usize, data: &mut [u8]) -> Result<(), Self::Error>;
+
+    fn erase_size(&self) -> usize;
+    async fn erase(&mut self, block_addr: usize, size_in_bytes: usize) -> Result<(), Self::Error>;
+
+    fn program_size(&self) -> usize;
+    async fn program(&mut self, block_addr: usize, data: &[u8]) -> Result<(), Self::Error>;
+
+    /// Size of the underlying device in bytes
+    fn capacity(&self) -> usize;
+}
+
+/// Async discard (TRIM).
+///
+/// Mirrors [`crate::block_device::Discard`].
+#[allow(async_fn_in_trait)]
+pub trait Discard: BlockDevice {
+    async fn discard(&mut self, block_addr: usize, size_in_bytes: usize) -> Result<(), Self::Error>;
+}
@@ -0,0 +1,12 @@
+pub use crate::gpio::{Error, ErrorKind, ErrorType, InputPin, Trigger};
+
+/// Async wait for a pin event.
+///
+/// Mirrors [`crate::gpio::Wait`].
+#[allow(async_fn_in_trait)]
+pub trait Wait: InputPin {
+    /// Completes when `trigger` occurs on the pin.
+    ///
+    /// Level triggers complete immediately if the pin is already at that level.
+    async fn wait_for(&mut self, trigger: Trigger) -> Result<(), Self::Error>;
+}
@@ -0,0 +1,35 @@
+pub use crate::i2c_target::{BusFault, Error, ErrorKind, ErrorType, Event};
+
+/// Async I2C target.
+///
+/// Mirrors [`crate::i2c_target::I2cTarget`]; configuration and the receive
+/// and response buffers stay synchronous, only waiting for bus events is
+/// async.
+#[allow(async_fn_in_trait)]
+pub trait I2cTarget: ErrorType {
+    /// Sets the 7-bit address the target responds to.
+    fn set_address(&mut self, addr: u8) -> Result<(), Self::Error>;
+
+    /// Starts responding on the bus.
+    fn enable(&mut self) -> Result<(), Self::Error>;
+
+    /// Stops responding on the bus.
+    fn disable(&mut self) -> Result<(), Self::Error>;
+
+    /// Completes with the next bus event.
+    async fn wait_event(&mut self) -> Result<Event, Self::Error>;
+
+    /// Copies the data of the last completed write into `buf`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes copied.
+    fn read_received(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error>;
+
+    /// Stages data for the next controller read.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes accepted.
+    fn set_response(&mut self, data: &[u8]) -> Result<usize, Self::Error>;
+}
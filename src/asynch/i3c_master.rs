@@ -0,0 +1,42 @@
+pub use crate::i3c_master::{Error, ErrorKind, ErrorType, IbiDescriptor};
+
+/// Async I3C controller private transfers.
+///
+/// Mirrors [`crate::i3c_master::I3cMaster`].
+#[allow(async_fn_in_trait)]
+pub trait I3cMaster: ErrorType {
+    /// Private write to `addr`.
+    async fn write(&mut self, addr: u8, data: &[u8]) -> Result<(), Self::Error>;
+
+    /// Private read from `addr`. The target may end the read early.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes read.
+    async fn read(&mut self, addr: u8, buf: &mut [u8]) -> Result<usize, Self::Error>;
+
+    /// Private write followed by a read, separated by a repeated start.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes read.
+    async fn write_read(&mut self, addr: u8, data: &[u8], buf: &mut [u8]) -> Result<usize, Self::Error>;
+}
+
+/// Async in-band interrupt handling.
+///
+/// Mirrors [`crate::i3c_master::I3cIbi`].
+#[allow(async_fn_in_trait)]
+pub trait I3cIbi: ErrorType {
+    /// Descriptor describing a received interrupt.
+    type Ibi: IbiDescriptor;
+
+    /// Accepts IBIs from `addr`, sending ENEC to the target if required.
+    async fn enable_ibi(&mut self, addr: u8) -> Result<(), Self::Error>;
+
+    /// NACKs further IBIs from `addr`, sending DISEC to the target if required.
+    async fn disable_ibi(&mut self, addr: u8) -> Result<(), Self::Error>;
+
+    /// Completes with the oldest pending IBI.
+    async fn wait_ibi(&mut self) -> Result<Self::Ibi, Self::Error>;
+}
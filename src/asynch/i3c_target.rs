@@ -0,0 +1,59 @@
+pub use crate::i3c_target::{
+    DeviceCharacteristics, Error, ErrorKind, ErrorType, Event, TransactionEvent, TransactionState,
+};
+
+/// Async I3C target.
+///
+/// Mirrors [`crate::i3c_target::I3cTarget`]; only waiting for bus events is
+/// async.
+#[allow(async_fn_in_trait)]
+pub trait I3cTarget: ErrorType {
+    /// Returns the identity reported during dynamic address assignment.
+    fn characteristics(&self) -> DeviceCharacteristics;
+
+    /// Returns the current dynamic address, or `None` before one is assigned.
+    fn dynamic_address(&self) -> Option<u8>;
+
+    /// Completes with the next bus event.
+    async fn wait_event(&mut self) -> Result<Event, Self::Error>;
+
+    /// Copies the data of the last completed private write into `buf`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes copied.
+    fn read_received(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error>;
+
+    /// Stages data for the next private read.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes accepted.
+    fn set_response(&mut self, data: &[u8]) -> Result<usize, Self::Error>;
+}
+
+/// Async transaction-level target interface.
+///
+/// Mirrors [`crate::i3c_target::Transactions`], including its contract that
+/// one completed write is reported at a time.
+#[allow(async_fn_in_trait)]
+pub trait Transactions: I3cTarget {
+    /// Returns the current [`TransactionState`].
+    fn state(&self) -> TransactionState;
+
+    /// Loads `data` into the TX FIFO for the next private read.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes accepted.
+    fn preload_tx(&mut self, data: &[u8]) -> Result<usize, Self::Error>;
+
+    /// Returns the free space in the TX FIFO, in bytes.
+    fn tx_free(&self) -> usize;
+
+    /// Discards preloaded data that has not been read.
+    fn flush_tx(&mut self) -> Result<(), Self::Error>;
+
+    /// Completes with the oldest completed transfer.
+    async fn wait_transaction(&mut self) -> Result<TransactionEvent, Self::Error>;
+}
@@ -0,0 +1,13 @@
+//! Async variants of the peripheral traits.
+//!
+//! Each submodule mirrors its blocking counterpart and reuses the same
+//! `ErrorType`, so a single error definition serves both flavours of the
+//! trait and executors such as Embassy can drive the peripheral without
+//! wrapping it in blocking adapters.
+
+pub mod block_device;
+pub mod gpio;
+pub mod i2c_target;
+pub mod i3c_master;
+pub mod i3c_target;
+pub mod time;
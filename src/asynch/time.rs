@@ -0,0 +1,24 @@
+pub use crate::time::{Deadline, Instant, Monotonic};
+
+/// Async delay.
+///
+/// Mirrors [`crate::time::DelayNs`].
+#[allow(async_fn_in_trait)]
+pub trait DelayNs {
+    /// Completes after at least `ns` nanoseconds.
+    async fn delay_ns(&mut self, ns: u32);
+
+    /// Completes after at least `us` microseconds.
+    async fn delay_us(&mut self, us: u32) {
+        for _ in 0..us {
+            self.delay_ns(1_000).await;
+        }
+    }
+
+    /// Completes after at least `ms` milliseconds.
+    async fn delay_ms(&mut self, ms: u32) {
+        for _ in 0..ms {
+            self.delay_ns(1_000_000).await;
+        }
+    }
+}
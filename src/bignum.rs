@@ -0,0 +1,134 @@
+//! Big-number modular arithmetic for offloading RSA and ECC math.
+//!
+//! Operands are unsigned big-endian byte strings. Results are written
+//! zero-padded to the length of the output buffer, which must be at least the
+//! length of the modulus.
+
+use core::fmt::Debug;
+
+/// Error kind.
+///
+/// This represents a common set of big-number operation errors. Implementations are
+/// free to define more specific or additional error types. However, by providing
+/// a mapping to these common errors, generic code can still react to them.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// An operand is longer than the accelerator supports, or the output buffer is too short.
+    InvalidLength,
+
+    /// The modulus is zero, or even where the operation requires an odd modulus.
+    InvalidModulus,
+
+    /// An operand is not reduced modulo the modulus.
+    OperandOutOfRange,
+
+    /// The hardware accelerator is busy and cannot process the operation.
+    Busy,
+
+    /// General hardware failure during the operation.
+    HardwareFailure,
+
+    /// A different error occurred.
+    Other,
+}
+
+pub trait Error: core::fmt::Debug {
+    /// Convert error to a generic error kind
+    ///
+    /// By using this method, errors freely defined by Algo implementations
+    /// can be converted to a set of generic errors upon which generic
+    /// code can act.
+    fn kind(&self) -> ErrorKind;
+}
+
+impl Error for core::convert::Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// Modular exponentiation, the primitive most RSA accelerators expose.
+pub trait ModExp: ErrorType {
+    /// Largest operand size supported, in bytes.
+    const MAX_OPERAND_BYTES: usize;
+
+    /// Computes `base ^ exponent mod modulus`.
+    ///
+    /// # Parameters
+    ///
+    /// - `base`: The base, reduced modulo `modulus`.
+    /// - `exponent`: The exponent.
+    /// - `modulus`: The modulus.
+    /// - `result`: Output buffer, at least as long as `modulus`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or failure.
+    fn mod_exp(
+        &mut self,
+        base: &[u8],
+        exponent: &[u8],
+        modulus: &[u8],
+        result: &mut [u8],
+    ) -> Result<(), Self::Error>;
+}
+
+/// Modular multiplication.
+pub trait ModMul: ErrorType {
+    /// Computes `a * b mod modulus`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or failure.
+    fn mod_mul(
+        &mut self,
+        a: &[u8],
+        b: &[u8],
+        modulus: &[u8],
+        result: &mut [u8],
+    ) -> Result<(), Self::Error>;
+}
+
+/// Montgomery arithmetic for repeated operations under one odd modulus.
+///
+/// Loading the modulus precomputes its constants once; values are then
+/// converted into Montgomery form, multiplied any number of times and
+/// converted back.
+pub trait Montgomery: ErrorType {
+    /// Precomputed constants of a loaded modulus.
+    type Context;
+
+    /// Loads an odd `modulus` and precomputes its Montgomery constants.
+    fn load_modulus(&mut self, modulus: &[u8]) -> Result<Self::Context, Self::Error>;
+
+    /// Converts `value` into Montgomery form (`value * R mod n`).
+    fn load_montgomery(
+        &mut self,
+        context: &Self::Context,
+        value: &[u8],
+        result: &mut [u8],
+    ) -> Result<(), Self::Error>;
+
+    /// Converts `value` out of Montgomery form (`value * R^-1 mod n`).
+    fn store_montgomery(
+        &mut self,
+        context: &Self::Context,
+        value: &[u8],
+        result: &mut [u8],
+    ) -> Result<(), Self::Error>;
+
+    /// Multiplies two values in Montgomery form (`a * b * R^-1 mod n`).
+    fn montgomery_mul(
+        &mut self,
+        context: &Self::Context,
+        a: &[u8],
+        b: &[u8],
+        result: &mut [u8],
+    ) -> Result<(), Self::Error>;
+}
@@ -1,49 +1,694 @@
+use core::ops::Range;
 
+use crate::dma::{ReadBuffer, StartError, Transfer, WriteBuffer};
 
-pub enum ErrorKind {
-    ReadError,
-    ProgramError,
-    EraseError,
-    OutOfBounds,
+pub use crate::error::{Error, ErrorKind};
+
+/// A trait that BlockDevice implementations can use to share an error type.
+pub trait ErrorType {
+    /// Errors returned by this NOR flash.
+    type Error: Error;
+}
+
+/// Block devices are byte addressable but operate in units of "blocks".
+///
+/// Reading comes from the [`ReadBlockDevice`] supertrait.
+///
+/// Drivers should also implement [`crate::system_control::power::PowerManaged`].
+pub trait BlockDevice: ReadBlockDevice {
+    fn erase_size(&self) -> usize;
+    fn erase(&mut self, block_addr: usize, size_in_bytes: usize) -> Result<(), Self::Error>;
+
+    fn program_size(&self) -> usize;
+    fn program(&mut self, block_addr: usize, data: &[u8]) -> Result<(), Self::Error>;
+}
+
+/// Fixed parameters of a block device whose geometry is known at compile time.
+///
+/// Must agree with the values returned by the [`BlockDevice`] methods.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Capabilities {
+    pub read_size: usize,
+    pub erase_size: usize,
+    pub program_size: usize,
+    /// Number of erase blocks; the device capacity is `block_count * erase_size`.
+    pub block_count: usize,
+    /// Longest read or program the device handles in one call, in bytes.
+    pub max_transfer_len: usize,
 }
 
-pub trait Error: core::fmt::Debug {
-	/// Convert a specific NOR flash error into a generic error kind.
-	fn kind(&self) -> ErrorKind;
+impl Capabilities {
+    pub const fn capacity(&self) -> usize {
+        self.block_count * self.erase_size
+    }
+}
+
+/// Capability discovery at compile time, e.g. to size buffers statically.
+pub trait BlockDeviceCapabilities: BlockDevice {
+    const CAPABILITIES: Capabilities;
+}
+
+/// Scatter-gather transfers.
+///
+/// The buffers cover consecutive device addresses starting at `block_addr`,
+/// so a DMA engine with SG list support can move them in one operation.
+pub trait BlockDeviceVectored: BlockDevice {
+    fn read_vectored(&mut self, block_addr: usize, bufs: &mut [&mut [u8]]) -> Result<(), Self::Error> {
+        let mut addr = block_addr;
+        for buf in bufs.iter_mut() {
+            self.read(addr, buf)?;
+            addr += buf.len();
+        }
+        Ok(())
+    }
+
+    fn program_vectored(&mut self, block_addr: usize, bufs: &[&[u8]]) -> Result<(), Self::Error> {
+        let mut addr = block_addr;
+        for buf in bufs {
+            self.program(addr, buf)?;
+            addr += buf.len();
+        }
+        Ok(())
+    }
+}
+
+/// Programming a block across several calls.
+///
+/// Data passed to `write` is staged by the implementation and only reaches
+/// the device on `commit`, so callers don't need a block-sized buffer.
+pub trait StreamingProgram: BlockDevice {
+    /// Starts staging data for the block at `block_addr`.
+    fn begin(&mut self, block_addr: usize) -> Result<(), Self::Error>;
+
+    /// Appends `data` to the staged block.
+    fn write(&mut self, data: &[u8]) -> Result<(), Self::Error>;
+
+    /// Programs the staged data.
+    fn commit(&mut self) -> Result<(), Self::Error>;
+
+    /// Discards the staged data without programming it.
+    fn abort(&mut self);
 }
 
-impl Error for core::convert::Infallible {
-    /// Convert error to a generic Mac error kind.
+/// State found by [`Transactional::recover`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Recovery {
+    /// No transaction was interrupted.
+    Clean,
+    /// An uncommitted transaction was discarded.
+    RolledBack,
+    /// A commit that was interrupted after its commit point was completed.
+    RolledForward,
+}
+
+/// Power-fail atomic updates spanning several blocks.
+///
+/// # Atomicity contract
+///
+/// - Programs and erases issued inside a transaction do not change what
+///   [`BlockDevice::read`] returns until [`Transactional::commit_transaction`]
+///   succeeds.
+/// - If power fails before `commit_transaction` returns, the device shows
+///   either the state from before [`Transactional::begin_transaction`] or
+///   the fully committed state once [`Transactional::recover`] has run;
+///   never a mix of the two. Once `commit_transaction` has returned `Ok`,
+///   the committed state survives power loss.
+/// - Programs and erases outside a transaction are not covered.
+/// - At most one transaction is open at a time.
+pub trait Transactional: BlockDevice {
+    /// Largest number of bytes one transaction can program or erase, bounded by
+    /// the space the implementation reserves for its journal or shadow copy.
+    fn max_transaction_size(&self) -> usize;
+
+    /// Opens a transaction.
+    ///
+    /// Fails with [`ErrorKind::InUse`] if one is already open.
+    fn begin_transaction(&mut self) -> Result<(), Self::Error>;
+
+    /// Programs `data` at `block_addr` within the open transaction.
+    ///
+    /// Fails with [`ErrorKind::Full`] once the transaction exceeds
+    /// [`Transactional::max_transaction_size`]; the transaction stays open
+    /// and can still be aborted.
+    fn transaction_program(&mut self, block_addr: usize, data: &[u8]) -> Result<(), Self::Error>;
+
+    /// Erases `size_in_bytes` bytes at `block_addr` within the open transaction.
+    fn transaction_erase(&mut self, block_addr: usize, size_in_bytes: usize) -> Result<(), Self::Error>;
+
+    /// Makes every change of the open transaction visible at once and closes it.
+    fn commit_transaction(&mut self) -> Result<(), Self::Error>;
+
+    /// Discards the open transaction, if any.
+    fn abort_transaction(&mut self);
+
+    /// Completes or rolls back a transaction interrupted by power loss.
+    ///
+    /// Must be called once at start-up before any other access.
+    fn recover(&mut self) -> Result<Recovery, Self::Error>;
+}
+
+/// Discard (TRIM) of data that is no longer needed.
+///
+/// Lets a flash translation layer or the device itself reclaim the blocks
+/// without copying their contents during garbage collection.
+pub trait Discard: BlockDevice {
+    /// Marks `size_in_bytes` bytes at `block_addr` as unused.
     ///
-    /// By using this method, Mac errors freely defined by Algo implementations
-    /// can be converted to a set of generic I2C errors upon which generic
-    /// code can act.    
+    /// The range must be aligned to [`BlockDevice::erase_size`]. Reading a
+    /// discarded range returns unspecified data until it is programmed again.
+    fn discard(&mut self, block_addr: usize, size_in_bytes: usize) -> Result<(), Self::Error>;
+}
+
+/// Physical layout of a block device.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Geometry {
+    /// Size of a logical block as seen through [`BlockDevice`], in bytes.
+    pub logical_block_size: usize,
+    /// Size of a physical erase block, in bytes.
+    pub erase_block_size: usize,
+    /// Number of physical erase blocks, including spares not visible in [`BlockDevice::capacity`].
+    pub erase_block_count: usize,
+    /// Size of a physical program page, in bytes.
+    pub page_size: usize,
+}
+
+/// Query of the physical geometry behind the logical block size.
+pub trait BlockGeometry: BlockDevice {
+    fn geometry(&self) -> Geometry;
+}
+
+/// Wear and health summary of a block device.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Health {
+    /// Estimated remaining endurance, from 100 for a new part down to 0.
+    pub remaining_life_percent: u8,
+    /// Number of blocks retired as bad, including factory bad blocks.
+    pub bad_blocks: u32,
+    /// Number of spare blocks still available for replacing bad blocks.
+    pub spare_blocks: u32,
+}
+
+/// Wear and health reporting.
+pub trait WearHealth: BlockDevice {
+    /// Returns the current health summary.
+    fn health(&mut self) -> Result<Health, Self::Error>;
+
+    /// Returns the erase count of the erase block containing `block_addr`,
+    /// or `None` if the device does not track per-block wear.
+    fn erase_count(&mut self, block_addr: usize) -> Result<Option<u32>, Self::Error>;
+}
+
+/// DMA transfers to and from a block device.
+pub trait BlockDeviceDma: BlockDevice {
+    type ReadTransfer<'a, B>: Transfer<Buffer = B, Error = Self::Error>
+    where
+        Self: 'a,
+        B: WriteBuffer + 'a;
+
+    type ProgramTransfer<'a, B>: Transfer<Buffer = B, Error = Self::Error>
+    where
+        Self: 'a,
+        B: ReadBuffer + 'a;
+
+    /// Starts reading into `buf` from `block_addr`.
+    fn read_dma<B: WriteBuffer>(
+        &mut self,
+        block_addr: usize,
+        buf: B,
+    ) -> Result<Self::ReadTransfer<'_, B>, StartError<Self::Error, B>>;
+
+    /// Starts programming `buf` at `block_addr`.
+    fn program_dma<B: ReadBuffer>(
+        &mut self,
+        block_addr: usize,
+        buf: B,
+    ) -> Result<Self::ProgramTransfer<'_, B>, StartError<Self::Error, B>>;
+}
+
+/// Caller-chosen identifier of a queued command, echoed in its [`Completion`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct CommandTag(pub u16);
+
+/// A command for a [`CommandQueue`].
+///
+/// Program commands take a buffer of type `B` that is only read, such as
+/// `&'static [u8]`; read commands take a buffer of type `R` that is
+/// written.
+#[derive(Debug)]
+pub enum Command<B, R = B> {
+    /// Reads into `buf` from `block_addr`.
+    Read { block_addr: usize, buf: R },
+    /// Programs `buf` at `block_addr`.
+    Program { block_addr: usize, buf: B },
+    Erase { block_addr: usize, size_in_bytes: usize },
+    /// Completes once every earlier command is durable. Later commands are not held back.
+    Flush,
+}
+
+/// A finished command returned by [`CommandQueue::poll_completion`].
+#[derive(Debug)]
+pub struct Completion<B, R, E> {
+    pub tag: CommandTag,
+    /// The command, handing back its buffer.
+    pub command: Command<B, R>,
+    pub result: Result<(), E>,
+}
+
+/// Completion returned by the command queue `Q`.
+pub type CompletionOf<Q, B, R> = Completion<B, R, <Q as ErrorType>::Error>;
+
+/// Several outstanding commands, as on eMMC command queueing or NVMe-like controllers.
+///
+/// Commands may execute and complete in any order; callers that need
+/// ordering between two commands wait for the first one's completion
+/// before submitting the second.
+pub trait CommandQueue<B: ReadBuffer, R: WriteBuffer = B>: BlockDevice {
+    /// Maximum number of commands in flight.
+    fn queue_depth(&self) -> usize;
+
+    /// Number of commands submitted and not yet returned by `poll_completion`.
+    fn in_flight(&self) -> usize;
+
+    /// Queues `command` under `tag` without blocking.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success, or the command handed back with an error
+    /// of kind [`ErrorKind::Full`] if `queue_depth` commands are in flight, or of
+    /// kind [`ErrorKind::InUse`] if `tag` is already in flight.
+    fn submit(
+        &mut self,
+        tag: CommandTag,
+        command: Command<B, R>,
+    ) -> Result<(), StartError<Self::Error, Command<B, R>>>;
+
+    /// Takes any finished command without blocking.
+    fn poll_completion(&mut self) -> Result<Option<CompletionOf<Self, B, R>>, Self::Error>;
+
+    /// Blocks until any command finishes.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the completion, or `None` if nothing is in flight.
+    fn wait_completion(&mut self) -> Result<Option<CompletionOf<Self, B, R>>, Self::Error> {
+        while self.in_flight() > 0 {
+            if let Some(completion) = self.poll_completion()? {
+                return Ok(Some(completion));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Read access to a block device.
+///
+/// Supertrait of [`BlockDevice`], and implemented alone by [`ReadOnly`], so
+/// code that only needs to read can be handed a view that cannot program or
+/// erase.
+pub trait ReadBlockDevice: ErrorType {
+    /// Get size of a reaadable block
+    fn read_size(&self) -> usize;
+    fn read(&mut self, block_addr: usize, data: &mut [u8]) -> Result<(), Self::Error>;
+
+    /// Size of the underlying device in bytes
+    fn capacity(&self) -> usize;
+
+    /// Reads `buf.len()` bytes starting at any byte `offset`.
+    ///
+    /// The aligned middle of the range is read in a single call; unaligned
+    /// edges are read through `scratch`, which must then be at least
+    /// `read_size` bytes long.
+    fn read_exact_at(
+        &mut self,
+        offset: usize,
+        buf: &mut [u8],
+        scratch: &mut [u8],
+    ) -> Result<(), ReadExactError<Self::Error>> {
+        let read_size = self.read_size().max(1);
+        let end = offset.checked_add(buf.len()).ok_or(ReadExactError::OutOfBounds)?;
+        if end > self.capacity() {
+            return Err(ReadExactError::OutOfBounds);
+        }
+        let mut addr = offset;
+        let mut pos = 0;
+        let head = offset % read_size;
+        if head != 0 && !buf.is_empty() {
+            if offset - head + read_size > self.capacity() {
+                return Err(ReadExactError::OutOfBounds);
+            }
+            let block = scratch.get_mut(..read_size).ok_or(ReadExactError::ScratchTooSmall)?;
+            self.read(offset - head, block).map_err(ReadExactError::Device)?;
+            let len = (read_size - head).min(buf.len());
+            buf[..len].copy_from_slice(&block[head..head + len]);
+            pos += len;
+            addr += len;
+        }
+        let middle = (buf.len() - pos) / read_size * read_size;
+        if middle > 0 {
+            self.read(addr, &mut buf[pos..pos + middle])
+                .map_err(ReadExactError::Device)?;
+            pos += middle;
+            addr += middle;
+        }
+        if pos < buf.len() {
+            if addr + read_size > self.capacity() {
+                return Err(ReadExactError::OutOfBounds);
+            }
+            let block = scratch.get_mut(..read_size).ok_or(ReadExactError::ScratchTooSmall)?;
+            self.read(addr, block).map_err(ReadExactError::Device)?;
+            let len = buf.len() - pos;
+            buf[pos..].copy_from_slice(&block[..len]);
+        }
+        Ok(())
+    }
+}
+
+/// Failure of [`ReadBlockDevice::read_exact_at`].
+#[derive(Debug)]
+pub enum ReadExactError<E> {
+    /// The range extends past the end of the device.
+    OutOfBounds,
+    /// The range is unaligned and the scratch buffer is shorter than the read size.
+    ScratchTooSmall,
+    /// The device failed.
+    Device(E),
+}
+
+impl<E: Error> Error for ReadExactError<E> {
     fn kind(&self) -> ErrorKind {
-        match *self {}
+        match self {
+            ReadExactError::OutOfBounds => ErrorKind::OutOfBounds,
+            ReadExactError::ScratchTooSmall => ErrorKind::InvalidParameter,
+            ReadExactError::Device(e) => e.kind(),
+        }
     }
 }
 
-/// A trait that BlockDevice implementations can use to share an error type.
-pub trait ErrorType {
-	/// Errors returned by this NOR flash.
-	type Error: Error;
+/// Expected order of upcoming reads.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum AccessPattern {
+    /// Increasing addresses, e.g. hashing an image.
+    Sequential,
+    /// No particular order; prefetching would waste bandwidth.
+    Random,
 }
 
-/// Block devices are byte addressable but operate in units of "blocks".
-pub trait BlockDevice: ErrorType {
+/// Read performance hints.
+///
+/// Hints never change what is read, only how fast.
+pub trait ReadAhead: ReadBlockDevice {
+    /// Read length the device handles most efficiently, a multiple of
+    /// `read_size`, e.g. a page or the controller's burst size.
+    fn preferred_read_size(&self) -> usize;
 
-    /// Get size of a reaadable block 
-    fn  read_size(&self) -> usize;
-    fn read(&mut self, block_addr: usize, data : &mut[u8]) -> Result<(), Self::Error>; 
+    /// Announces that `range` will be read with `pattern`, so the
+    /// implementation can prefetch or enable a read cache.
+    fn advise(&mut self, range: Range<usize>, pattern: AccessPattern) -> Result<(), Self::Error>;
+}
 
+impl<T: ErrorType + ?Sized> ErrorType for &mut T {
+    type Error = T::Error;
+}
 
-    fn  erase_size(&self) -> usize; 
-    fn erase(&mut self, block_addr: usize, size_in_bytes: usize) -> Result<(), Self::Error>; 
+impl<T: ReadBlockDevice + ?Sized> ReadBlockDevice for &mut T {
+    fn read_size(&self) -> usize {
+        T::read_size(self)
+    }
 
+    fn read(&mut self, block_addr: usize, data: &mut [u8]) -> Result<(), Self::Error> {
+        T::read(self, block_addr, data)
+    }
 
-    fn  program_size(&self) -> usize; 
-    fn program(&mut self, block_addr: usize, data : &[u8]) -> Result<(), Self::Error>; 
+    fn capacity(&self) -> usize {
+        T::capacity(self)
+    }
+}
 
-    /// Size of the underlying device in bytes
-    fn  capacity(&self) -> usize;
-}
\ No newline at end of file
+impl<T: BlockDevice + ?Sized> BlockDevice for &mut T {
+    fn erase_size(&self) -> usize {
+        T::erase_size(self)
+    }
+
+    fn erase(&mut self, block_addr: usize, size_in_bytes: usize) -> Result<(), Self::Error> {
+        T::erase(self, block_addr, size_in_bytes)
+    }
+
+    fn program_size(&self) -> usize {
+        T::program_size(self)
+    }
+
+    fn program(&mut self, block_addr: usize, data: &[u8]) -> Result<(), Self::Error> {
+        T::program(self, block_addr, data)
+    }
+}
+
+/// Read-only view of a block device.
+///
+/// Only implements [`ReadBlockDevice`], so holders of the view cannot
+/// program or erase. Wrap a `&mut D` to lend a view while keeping the device.
+pub struct ReadOnly<D>(D);
+
+impl<D: BlockDevice> ReadOnly<D> {
+    pub fn new(device: D) -> Self {
+        Self(device)
+    }
+
+    /// Returns the wrapped device, restoring write access.
+    pub fn into_inner(self) -> D {
+        self.0
+    }
+}
+
+impl<D: ErrorType> ErrorType for ReadOnly<D> {
+    type Error = D::Error;
+}
+
+impl<D: BlockDevice> ReadBlockDevice for ReadOnly<D> {
+    fn read_size(&self) -> usize {
+        self.0.read_size()
+    }
+
+    fn read(&mut self, block_addr: usize, data: &mut [u8]) -> Result<(), Self::Error> {
+        self.0.read(block_addr, data)
+    }
+
+    fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+}
+
+/// Error returned by [`Region`].
+#[derive(Debug)]
+pub enum RegionError<E> {
+    /// The access extends past the end of the region.
+    OutOfBounds,
+    /// The underlying device failed.
+    Device(E),
+}
+
+impl<E: Error> Error for RegionError<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            RegionError::OutOfBounds => ErrorKind::OutOfBounds,
+            RegionError::Device(e) => e.kind(),
+        }
+    }
+}
+
+/// A range of a block device, exposed as a device of its own.
+///
+/// Addresses are relative to the start of the region and accesses outside it
+/// fail before reaching the device, so e.g. an update agent handed the
+/// staging partition cannot touch the rest of the flash. Combine with
+/// [`ReadOnly`] as `ReadOnly<Region<D>>` for a read-only range.
+pub struct Region<D> {
+    device: D,
+    offset: usize,
+    size: usize,
+}
+
+impl<D: BlockDevice> Region<D> {
+    /// Restricts `device` to `size` bytes starting at `offset`.
+    ///
+    /// Returns `None` if the range does not fit the device or is not aligned
+    /// to its erase size.
+    pub fn new(device: D, offset: usize, size: usize) -> Option<Self> {
+        let erase_size = device.erase_size();
+        let fits = offset
+            .checked_add(size)
+            .is_some_and(|end| end <= device.capacity());
+        let aligned =
+            erase_size != 0 && offset.is_multiple_of(erase_size) && size.is_multiple_of(erase_size);
+        (fits && aligned).then_some(Self { device, offset, size })
+    }
+
+    /// Offset of the region on the underlying device, in bytes.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the wrapped device.
+    pub fn into_inner(self) -> D {
+        self.device
+    }
+
+    fn translate(&self, block_addr: usize, len: usize) -> Result<usize, RegionError<D::Error>> {
+        block_addr
+            .checked_add(len)
+            .filter(|&end| end <= self.size)
+            .map(|_| self.offset + block_addr)
+            .ok_or(RegionError::OutOfBounds)
+    }
+}
+
+impl<D: BlockDevice> ErrorType for Region<D> {
+    type Error = RegionError<D::Error>;
+}
+
+impl<D: BlockDevice> ReadBlockDevice for Region<D> {
+    fn read_size(&self) -> usize {
+        self.device.read_size()
+    }
+
+    fn read(&mut self, block_addr: usize, data: &mut [u8]) -> Result<(), Self::Error> {
+        let addr = self.translate(block_addr, data.len())?;
+        self.device.read(addr, data).map_err(RegionError::Device)
+    }
+
+    fn capacity(&self) -> usize {
+        self.size
+    }
+}
+
+impl<D: BlockDevice> BlockDevice for Region<D> {
+    fn erase_size(&self) -> usize {
+        self.device.erase_size()
+    }
+
+    fn erase(&mut self, block_addr: usize, size_in_bytes: usize) -> Result<(), Self::Error> {
+        let addr = self.translate(block_addr, size_in_bytes)?;
+        self.device.erase(addr, size_in_bytes).map_err(RegionError::Device)
+    }
+
+    fn program_size(&self) -> usize {
+        self.device.program_size()
+    }
+
+    fn program(&mut self, block_addr: usize, data: &[u8]) -> Result<(), Self::Error> {
+        let addr = self.translate(block_addr, data.len())?;
+        self.device.program(addr, data).map_err(RegionError::Device)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `capacity` bytes holding `0..capacity`, readable in aligned 4-byte units only.
+    struct AlignedRom {
+        reads: usize,
+        capacity: usize,
+    }
+
+    impl ErrorType for AlignedRom {
+        type Error = ErrorKind;
+    }
+
+    impl ReadBlockDevice for AlignedRom {
+        fn read_size(&self) -> usize {
+            4
+        }
+
+        fn read(&mut self, block_addr: usize, data: &mut [u8]) -> Result<(), Self::Error> {
+            if !block_addr.is_multiple_of(4) || !data.len().is_multiple_of(4) {
+                return Err(ErrorKind::InvalidParameter);
+            }
+            if block_addr + data.len() > self.capacity {
+                return Err(ErrorKind::OutOfBounds);
+            }
+            for (i, byte) in data.iter_mut().enumerate() {
+                *byte = (block_addr + i) as u8;
+            }
+            self.reads += 1;
+            Ok(())
+        }
+
+        fn capacity(&self) -> usize {
+            self.capacity
+        }
+    }
+
+    fn read_at<const N: usize>(offset: usize) -> Result<([u8; N], usize), ReadExactError<ErrorKind>> {
+        let mut rom = AlignedRom { reads: 0, capacity: 16 };
+        let mut buf = [0u8; N];
+        let mut scratch = [0u8; 4];
+        rom.read_exact_at(offset, &mut buf, &mut scratch)?;
+        Ok((buf, rom.reads))
+    }
+
+    #[test]
+    fn aligned_range_is_read_in_one_call() {
+        let (buf, reads) = read_at::<8>(4).unwrap();
+        assert_eq!(buf, [4, 5, 6, 7, 8, 9, 10, 11]);
+        assert_eq!(reads, 1);
+    }
+
+    #[test]
+    fn unaligned_edges_go_through_scratch() {
+        let (buf, reads) = read_at::<9>(3).unwrap();
+        assert_eq!(buf, [3, 4, 5, 6, 7, 8, 9, 10, 11]);
+        assert_eq!(reads, 2);
+
+        let (buf, reads) = read_at::<6>(5).unwrap();
+        assert_eq!(buf, [5, 6, 7, 8, 9, 10]);
+        assert_eq!(reads, 2);
+
+        let (buf, reads) = read_at::<10>(3).unwrap();
+        assert_eq!(buf, [3, 4, 5, 6, 7, 8, 9, 10, 11, 12]);
+        assert_eq!(reads, 3);
+    }
+
+    #[test]
+    fn short_read_within_one_block() {
+        let (buf, reads) = read_at::<2>(13).unwrap();
+        assert_eq!(buf, [13, 14]);
+        assert_eq!(reads, 1);
+
+        let (buf, reads) = read_at::<3>(0).unwrap();
+        assert_eq!(buf, [0, 1, 2]);
+        assert_eq!(reads, 1);
+    }
+
+    #[test]
+    fn empty_read_touches_nothing() {
+        assert_eq!(read_at::<0>(7).unwrap().1, 0);
+    }
+
+    #[test]
+    fn range_past_the_end_is_rejected() {
+        assert!(matches!(read_at::<2>(15), Err(ReadExactError::OutOfBounds)));
+        assert!(matches!(read_at::<1>(usize::MAX), Err(ReadExactError::OutOfBounds)));
+    }
+
+    #[test]
+    fn partial_last_block_is_out_of_bounds() {
+        // The last 4-byte block would extend past the end of a 14-byte device.
+        let mut rom = AlignedRom { reads: 0, capacity: 14 };
+        let mut scratch = [0u8; 4];
+        let head = rom.read_exact_at(13, &mut [0u8; 1], &mut scratch);
+        assert!(matches!(head, Err(ReadExactError::OutOfBounds)));
+        assert_eq!(rom.reads, 0);
+        let tail = rom.read_exact_at(8, &mut [0u8; 5], &mut scratch);
+        assert!(matches!(tail, Err(ReadExactError::OutOfBounds)));
+    }
+
+    #[test]
+    fn unaligned_read_needs_scratch() {
+        let mut rom = AlignedRom { reads: 0, capacity: 16 };
+        let mut buf = [0u8; 4];
+        let result = rom.read_exact_at(1, &mut buf, &mut [0u8; 3]);
+        assert!(matches!(result, Err(ReadExactError::ScratchTooSmall)));
+        assert_eq!(rom.read_exact_at(4, &mut buf, &mut []).map_err(|e| e.kind()), Ok(()));
+        assert_eq!(buf, [4, 5, 6, 7]);
+    }
+}
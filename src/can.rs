@@ -0,0 +1,326 @@
+//! CAN and CAN FD controllers.
+
+pub use crate::error::{Error, ErrorKind};
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// Largest payload of a CAN FD frame, in bytes.
+pub const MAX_FD_PAYLOAD: usize = 64;
+
+/// Largest payload of a classic CAN frame, in bytes.
+pub const MAX_CLASSIC_PAYLOAD: usize = 8;
+
+/// Frame identifier, either an 11-bit standard or a 29-bit extended one.
+///
+/// Identifiers are ordered by bus arbitration: the identifier that wins
+/// arbitration is the smaller one, so the order doubles as bus priority.
+/// Arbitration compares the 11 base bits first, which for an extended
+/// identifier are its top 11 bits; on equal base bits a standard identifier
+/// wins over an extended one, and two extended identifiers are then decided
+/// by their remaining 18 bits.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Id {
+    raw: u32,
+    extended: bool,
+}
+
+impl Id {
+    /// Returns `None` if `id` does not fit in 11 bits.
+    pub const fn standard(id: u16) -> Option<Self> {
+        if id <= 0x7FF {
+            Some(Self {
+                raw: id as u32,
+                extended: false,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns `None` if `id` does not fit in 29 bits.
+    pub const fn extended(id: u32) -> Option<Self> {
+        if id <= 0x1FFF_FFFF {
+            Some(Self { raw: id, extended: true })
+        } else {
+            None
+        }
+    }
+
+    /// The identifier bits, 11 for a standard and 29 for an extended identifier.
+    pub const fn raw(self) -> u32 {
+        self.raw
+    }
+
+    pub const fn is_extended(self) -> bool {
+        self.extended
+    }
+
+    /// Base bits, IDE bit and extension bits, in the order they are arbitrated.
+    fn arbitration_key(self) -> (u32, bool, u32) {
+        if self.extended {
+            (self.raw >> 18, true, self.raw & 0x3_FFFF)
+        } else {
+            (self.raw, false, 0)
+        }
+    }
+}
+
+impl Ord for Id {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.arbitration_key().cmp(&other.arbitration_key())
+    }
+}
+
+impl PartialOrd for Id {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Payload lengths a CAN FD frame can carry.
+const FD_LENGTHS: [usize; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 12, 16, 20, 24, 32, 48, 64];
+
+/// Frame format.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Format {
+    /// Classic CAN, up to 8 bytes.
+    Classic,
+    /// CAN FD, up to 64 bytes, optionally with the data phase at the higher bit rate.
+    Fd { bit_rate_switch: bool },
+}
+
+/// A data or remote frame.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Frame {
+    id: Id,
+    format: Format,
+    remote: bool,
+    len: u8,
+    data: [u8; MAX_FD_PAYLOAD],
+}
+
+impl Frame {
+    /// Creates a data frame.
+    ///
+    /// Returns `None` if `data` is longer than `format` allows or, for CAN FD,
+    /// is not one of the lengths the DLC can encode.
+    pub fn new(id: Id, format: Format, data: &[u8]) -> Option<Self> {
+        let valid = match format {
+            Format::Classic => data.len() <= MAX_CLASSIC_PAYLOAD,
+            Format::Fd { .. } => FD_LENGTHS.contains(&data.len()),
+        };
+        if !valid {
+            return None;
+        }
+        let mut frame = Self {
+            id,
+            format,
+            remote: false,
+            len: data.len() as u8,
+            data: [0; MAX_FD_PAYLOAD],
+        };
+        frame.data[..data.len()].copy_from_slice(data);
+        Some(frame)
+    }
+
+    /// Creates a classic remote frame requesting `len` bytes.
+    ///
+    /// Returns `None` if `len` exceeds 8. CAN FD has no remote frames.
+    pub fn new_remote(id: Id, len: u8) -> Option<Self> {
+        (usize::from(len) <= MAX_CLASSIC_PAYLOAD).then_some(Self {
+            id,
+            format: Format::Classic,
+            remote: true,
+            len,
+            data: [0; MAX_FD_PAYLOAD],
+        })
+    }
+
+    pub fn id(&self) -> Id {
+        self.id
+    }
+
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    pub fn is_remote(&self) -> bool {
+        self.remote
+    }
+
+    /// Payload of a data frame; empty for a remote frame.
+    pub fn data(&self) -> &[u8] {
+        if self.remote {
+            &[]
+        } else {
+            &self.data[..usize::from(self.len)]
+        }
+    }
+
+    /// Data length code of the frame.
+    pub fn dlc(&self) -> u8 {
+        let len = usize::from(self.len);
+        if len <= MAX_CLASSIC_PAYLOAD {
+            self.len
+        } else {
+            FD_LENGTHS.iter().position(|&l| l >= len).unwrap_or(15) as u8
+        }
+    }
+}
+
+/// Fault confinement state of the controller (ISO 11898-1).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ErrorState {
+    /// Both error counters are below 128.
+    Active,
+    /// An error counter reached 128; the node only sends passive error flags.
+    Passive,
+    /// The transmit error counter exceeded 255; the node is off the bus.
+    BusOff,
+}
+
+/// Error counters and state.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct BusStatus {
+    pub state: ErrorState,
+    pub transmit_errors: u8,
+    pub receive_errors: u8,
+}
+
+/// Frame transmit and receive.
+pub trait Can: ErrorType {
+    /// Queues `frame` for transmission without blocking.
+    ///
+    /// Pending frames are sent in identifier order, so a lower identifier
+    /// overtakes queued frames with higher ones.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success, or an error of kind [`ErrorKind::Full`] if
+    /// every transmit mailbox is occupied.
+    fn transmit(&mut self, frame: &Frame) -> Result<(), Self::Error>;
+
+    /// Returns the oldest received frame that passed the acceptance filters, without blocking.
+    fn receive(&mut self) -> Result<Option<Frame>, Self::Error>;
+
+    /// Returns the error counters and fault confinement state.
+    fn bus_status(&mut self) -> Result<BusStatus, Self::Error>;
+
+    /// Starts bus-off recovery: the controller rejoins the bus after 128
+    /// occurrences of 11 recessive bits.
+    fn recover_from_bus_off(&mut self) -> Result<(), Self::Error>;
+}
+
+/// An acceptance filter: a frame passes if `frame_id & mask == id & mask`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Filter {
+    /// Identifier to match, also selecting standard or extended frames.
+    pub id: Id,
+    pub mask: u32,
+}
+
+impl Filter {
+    /// Filter accepting exactly `id`.
+    pub fn exact(id: Id) -> Self {
+        Self { id, mask: u32::MAX }
+    }
+
+    /// Returns whether a frame with `id` passes.
+    pub fn matches(&self, id: Id) -> bool {
+        self.id.extended == id.extended && (self.id.raw ^ id.raw) & self.mask == 0
+    }
+}
+
+/// Hardware acceptance filtering.
+pub trait CanFilters: Can {
+    /// Number of filter banks.
+    const FILTER_COUNT: usize;
+
+    /// Programs filter bank `index`. Frames passing any enabled bank are received.
+    fn set_filter(&mut self, index: usize, filter: Filter) -> Result<(), Self::Error>;
+
+    /// Disables filter bank `index`.
+    fn clear_filter(&mut self, index: usize) -> Result<(), Self::Error>;
+
+    /// Accepts every frame, e.g. for bus monitoring.
+    fn accept_all(&mut self) -> Result<(), Self::Error>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID: Id = Id::standard(0x123).unwrap();
+    const FD: Format = Format::Fd { bit_rate_switch: true };
+
+    #[test]
+    fn dlc_of_every_fd_length() {
+        let data = [0xA5u8; MAX_FD_PAYLOAD];
+        for (dlc, &len) in FD_LENGTHS.iter().enumerate() {
+            let frame = Frame::new(ID, FD, &data[..len]).unwrap();
+            assert_eq!(frame.dlc(), dlc as u8, "length {len}");
+            assert_eq!(frame.data().len(), len);
+        }
+    }
+
+    #[test]
+    fn fd_rejects_lengths_between_dlc_steps() {
+        let data = [0u8; MAX_FD_PAYLOAD + 1];
+        for len in [9, 11, 13, 33, 47, 63, 65] {
+            assert_eq!(Frame::new(ID, FD, &data[..len]), None, "length {len}");
+        }
+    }
+
+    #[test]
+    fn classic_frames_use_the_length_as_dlc() {
+        let data = [1u8, 2, 3, 4, 5, 6, 7, 8, 9];
+        for len in 0..=MAX_CLASSIC_PAYLOAD {
+            let frame = Frame::new(ID, Format::Classic, &data[..len]).unwrap();
+            assert_eq!((frame.dlc(), frame.data()), (len as u8, &data[..len]));
+        }
+        assert_eq!(Frame::new(ID, Format::Classic, &data), None);
+    }
+
+    #[test]
+    fn remote_frames_carry_a_dlc_but_no_data() {
+        let frame = Frame::new_remote(ID, 6).unwrap();
+        assert!(frame.is_remote());
+        assert_eq!((frame.dlc(), frame.data()), (6, &[][..]));
+        assert_eq!(Frame::new_remote(ID, 9), None);
+    }
+
+    #[test]
+    fn identifier_ranges() {
+        assert_eq!(Id::standard(0x7FF).map(Id::raw), Some(0x7FF));
+        assert_eq!(Id::standard(0x800), None);
+        let extended = Id::extended(0x1FFF_FFFF).unwrap();
+        assert_eq!((extended.raw(), extended.is_extended()), (0x1FFF_FFFF, true));
+        assert_eq!(Id::extended(0x2000_0000), None);
+    }
+
+    #[test]
+    fn identifiers_order_by_arbitration() {
+        let standard = |id| Id::standard(id).unwrap();
+        let extended = |id| Id::extended(id).unwrap();
+        assert!(extended(0) < standard(0x7FF));
+        assert!(standard(0x100) < extended(0x100 << 18));
+        assert!(extended(0x100 << 18) < extended((0x100 << 18) | 1));
+        assert!(extended((0x100 << 18) | 0x3_FFFF) < standard(0x101));
+        assert!(standard(0x100) < standard(0x101));
+    }
+
+    #[test]
+    fn filters_match_the_identifier_kind() {
+        let filter = Filter {
+            id: Id::standard(0x120).unwrap(),
+            mask: 0x7F0,
+        };
+        assert!(filter.matches(ID));
+        assert!(!filter.matches(Id::standard(0x133).unwrap()));
+        assert!(!filter.matches(Id::extended(0x123).unwrap()));
+        assert!(Filter::exact(ID).matches(ID));
+    }
+}
@@ -0,0 +1,230 @@
+//! X.509 certificate handling.
+//!
+//! Parsing is zero-copy: a parsed certificate borrows the DER it was parsed
+//! from. Signature checks are delegated to a [`SignatureVerifier`], which
+//! implementations typically build from the `ecdsa`, `rsa`, `eddsa` or `pqc`
+//! verify traits together with a `digest` implementation.
+
+use core::fmt::Debug;
+
+use crate::time::UnixTime;
+
+/// Error kind.
+///
+/// This represents a common set of certificate handling errors. Implementations are
+/// free to define more specific or additional error types. However, by providing
+/// a mapping to these common errors, generic code can still react to them.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The DER encoding is invalid.
+    Malformed,
+
+    /// The signature or public key algorithm is not supported.
+    UnsupportedAlgorithm,
+
+    /// A certificate signature did not verify.
+    InvalidSignature,
+
+    /// The issuer of a certificate does not match the subject of the next one in the chain.
+    IssuerMismatch,
+
+    /// The chain does not end at the trust anchor.
+    UntrustedRoot,
+
+    /// The chain is longer than the implementation supports.
+    ChainTooLong,
+
+    /// A certificate is outside its validity period.
+    Expired,
+
+    /// An unknown extension is marked critical.
+    UnsupportedCriticalExtension,
+
+    /// The output buffer is too small.
+    BufferTooSmall,
+
+    /// A different error occurred.
+    Other,
+}
+
+pub trait Error: core::fmt::Debug {
+    /// Convert error to a generic error kind
+    ///
+    /// By using this method, errors freely defined by HAL implementations
+    /// can be converted to a set of generic errors upon which generic
+    /// code can act.
+    fn kind(&self) -> ErrorKind;
+}
+
+impl Error for core::convert::Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// Certificate signature algorithm.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum SignatureAlgorithm {
+    EcdsaWithSha256,
+    EcdsaWithSha384,
+    EcdsaWithSha512,
+    RsaPkcs1v15WithSha256,
+    RsaPkcs1v15WithSha384,
+    RsaPssWithSha256,
+    RsaPssWithSha384,
+    Ed25519,
+    MlDsa44,
+    MlDsa65,
+    MlDsa87,
+}
+
+/// Validity period of a certificate.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Validity {
+    pub not_before: UnixTime,
+    pub not_after: UnixTime,
+}
+
+impl Validity {
+    /// Returns whether `now` lies within the period, both ends included.
+    pub fn contains(&self, now: UnixTime) -> bool {
+        self.not_before <= now && now <= self.not_after
+    }
+}
+
+/// A parsed certificate borrowing its DER encoding.
+pub trait Certificate<'a>: Sized {
+    type Error: Error;
+
+    /// Parses a DER-encoded certificate.
+    fn parse(der: &'a [u8]) -> Result<Self, Self::Error>;
+
+    /// DER of the `TBSCertificate`, the signed portion.
+    fn tbs(&self) -> &'a [u8];
+
+    fn signature_algorithm(&self) -> SignatureAlgorithm;
+
+    /// Raw signature value.
+    fn signature(&self) -> &'a [u8];
+
+    /// DER of the issuer `Name`.
+    fn issuer(&self) -> &'a [u8];
+
+    /// DER of the subject `Name`.
+    fn subject(&self) -> &'a [u8];
+
+    /// DER of the `SubjectPublicKeyInfo`.
+    fn subject_public_key_info(&self) -> &'a [u8];
+
+    fn validity(&self) -> Validity;
+
+    /// Returns the value of the extension identified by the DER-encoded `oid`, if present.
+    fn extension(&self, oid: &[u8]) -> Option<&'a [u8]>;
+}
+
+/// Extraction of a `SubjectPublicKeyInfo` into a crate key type, e.g.
+/// `EcdsaVerify::PublicKey`.
+pub trait PublicKeyFromSpki: ErrorType {
+    type PublicKey;
+
+    fn public_key_from_spki(&self, spki: &[u8]) -> Result<Self::PublicKey, Self::Error>;
+}
+
+/// Checks certificate signatures.
+pub trait SignatureVerifier: ErrorType {
+    /// Verifies `signature` over `tbs` with the key in `issuer_spki`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success, or an error of kind [`ErrorKind::InvalidSignature`]
+    /// or [`ErrorKind::UnsupportedAlgorithm`].
+    fn verify_signature(
+        &mut self,
+        algorithm: SignatureAlgorithm,
+        issuer_spki: &[u8],
+        tbs: &[u8],
+        signature: &[u8],
+    ) -> Result<(), Self::Error>;
+}
+
+/// Verification of a certificate chain up to a trust anchor.
+pub trait ChainVerifier: SignatureVerifier {
+    /// Verifies `chain`, ordered from the leaf to the certificate issued by `trust_anchor`.
+    ///
+    /// # Parameters
+    ///
+    /// - `chain`: DER-encoded certificates, leaf first.
+    /// - `trust_anchor`: DER-encoded trusted root certificate.
+    /// - `now`: Current time, e.g. from an [`crate::rtc::Rtc`], or `None` to skip validity checks.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or failure.
+    fn verify_chain(
+        &mut self,
+        chain: &[&[u8]],
+        trust_anchor: &[u8],
+        now: Option<UnixTime>,
+    ) -> Result<(), Self::Error>;
+}
+
+/// Produces the signature of a certificate being built.
+pub trait TbsSigner: ErrorType {
+    /// Algorithm recorded in the certificate.
+    fn algorithm(&self) -> SignatureAlgorithm;
+
+    /// Signs `tbs` and writes the encoded signature value into `out`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes written.
+    fn sign_tbs(&mut self, tbs: &[u8], out: &mut [u8]) -> Result<usize, Self::Error>;
+}
+
+/// Construction of certificates, e.g. DICE alias or attestation certificates.
+pub trait CertificateBuilder: ErrorType {
+    fn set_serial_number(&mut self, serial: &[u8]) -> Result<(), Self::Error>;
+
+    /// Sets the issuer from a DER-encoded `Name`.
+    fn set_issuer(&mut self, name: &[u8]) -> Result<(), Self::Error>;
+
+    /// Sets the subject from a DER-encoded `Name`.
+    fn set_subject(&mut self, name: &[u8]) -> Result<(), Self::Error>;
+
+    fn set_validity(&mut self, validity: Validity) -> Result<(), Self::Error>;
+
+    /// Sets the subject key from a DER-encoded `SubjectPublicKeyInfo`.
+    fn set_subject_public_key_info(&mut self, spki: &[u8]) -> Result<(), Self::Error>;
+
+    /// Adds an extension, e.g. a DICE `TcbInfo`.
+    ///
+    /// # Parameters
+    ///
+    /// - `oid`: DER-encoded object identifier.
+    /// - `critical`: Whether the extension is marked critical.
+    /// - `value`: DER-encoded extension value.
+    fn add_extension(&mut self, oid: &[u8], critical: bool, value: &[u8]) -> Result<(), Self::Error>;
+
+    /// Encodes the `TBSCertificate` for `algorithm` into `out`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes written.
+    fn build_tbs(&mut self, algorithm: SignatureAlgorithm, out: &mut [u8]) -> Result<usize, Self::Error>;
+
+    /// Encodes the `TBSCertificate`, signs it with `signer` and writes the complete certificate into `out`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes written.
+    fn sign<S>(&mut self, signer: &mut S, out: &mut [u8]) -> Result<usize, Self::Error>
+    where
+        S: TbsSigner<Error = Self::Error>;
+}
@@ -0,0 +1,276 @@
+//! Traits shared by the crypto modules for handling secrets.
+//!
+//! Secret-bearing associated types, such as private keys and cipher keys,
+//! are bounded by [`Zeroize`], and tag comparisons go through
+//! [`ConstantTimeEq`], so the trait layer does not invite timing or residue
+//! leaks.
+//!
+//! Crypto engines that complete by interrupt expose their long-running
+//! operations through the `*Start` traits of each module, which return an
+//! [`Operation`] instead of blocking.
+//!
+//! An [`AlgorithmPolicy`] restricts at compile time which algorithms and key
+//! sizes a firmware image can instantiate.
+
+use core::future::Future;
+use core::hint::black_box;
+use core::pin::Pin;
+use core::sync::atomic::{compiler_fence, Ordering};
+use core::task::{Context, Poll, Waker};
+
+use crate::digest::{self, DigestOf};
+use crate::rsa::{Rsa2048, Rsa3072, Rsa4096};
+
+/// Clearing of secret material.
+///
+/// Implementations overwrite every byte of the secret before returning, in
+/// a way the compiler does not elide as a dead store.
+pub trait Zeroize {
+    fn zeroize(&mut self);
+}
+
+macro_rules! zeroize_int {
+    ($($t:ty),*) => {$(
+        impl Zeroize for $t {
+            #[allow(unsafe_code)]
+            fn zeroize(&mut self) {
+                // SAFETY: `self` is a valid, aligned, exclusive reference.
+                unsafe { core::ptr::write_volatile(self, 0) };
+                compiler_fence(Ordering::SeqCst);
+            }
+        }
+    )*};
+}
+
+zeroize_int!(u8, u16, u32, u64, u128, usize);
+
+impl<T: Zeroize> Zeroize for [T] {
+    fn zeroize(&mut self) {
+        for item in self.iter_mut() {
+            item.zeroize();
+        }
+    }
+}
+
+impl<T: Zeroize, const N: usize> Zeroize for [T; N] {
+    fn zeroize(&mut self) {
+        self[..].zeroize();
+    }
+}
+
+impl Zeroize for () {
+    fn zeroize(&mut self) {}
+}
+
+/// Equality whose running time does not depend on the contents compared.
+///
+/// The slice implementation accumulates differences without branching and
+/// passes them through [`black_box`] to discourage the optimizer from
+/// short-circuiting. `black_box` is only a best-effort hint, so this is not
+/// a guarantee against every compiler; hardware comparators or audited
+/// assembly should back it where timing is critical.
+pub trait ConstantTimeEq {
+    /// Returns whether `self` equals `other`.
+    ///
+    /// Only the lengths of variable-length values may influence timing.
+    fn ct_eq(&self, other: &Self) -> bool;
+}
+
+impl ConstantTimeEq for [u8] {
+    fn ct_eq(&self, other: &Self) -> bool {
+        if self.len() != other.len() {
+            return false;
+        }
+        let diff = self
+            .iter()
+            .zip(other)
+            .fold(0u8, |acc, (a, b)| black_box(acc | (a ^ b)));
+        black_box(diff) == 0
+    }
+}
+
+impl<const N: usize> ConstantTimeEq for [u8; N] {
+    fn ct_eq(&self, other: &Self) -> bool {
+        self[..].ct_eq(&other[..])
+    }
+}
+
+/// A crypto operation started on a hardware engine.
+///
+/// The operation borrows its engine and its inputs until it completes, so
+/// safe code cannot touch them while it is pending, and dropping an
+/// unfinished operation aborts it.
+///
+/// Neither holds if the operation is leaked with [`core::mem::forget`]: the
+/// borrows end without the abort running. Implementations must therefore
+/// not let the hardware access borrowed buffers in place; they copy inputs
+/// into engine-owned memory (and outputs back in `poll`), or only accept
+/// `'static` buffers where the engine works on caller memory directly.
+pub trait Operation {
+    type Output;
+    type Error;
+
+    /// Checks for completion without blocking.
+    ///
+    /// # Returns
+    ///
+    /// `Err(nb::Error::WouldBlock)` while the engine is busy, otherwise the result.
+    /// Polling again after the result was returned is an implementation-defined error.
+    fn poll(&mut self) -> ::nb::Result<Self::Output, Self::Error>;
+
+    /// Registers `waker` to be woken from the completion interrupt.
+    fn register_waker(&mut self, waker: &Waker);
+
+    /// Blocks until the operation completes.
+    fn wait(mut self) -> Result<Self::Output, Self::Error>
+    where
+        Self: Sized,
+    {
+        ::nb::block!(self.poll())
+    }
+
+    /// Wraps the operation into a future for async executors.
+    fn into_future(self) -> OperationFuture<Self>
+    where
+        Self: Sized,
+    {
+        OperationFuture(self)
+    }
+}
+
+/// Future returned by [`Operation::into_future`].
+pub struct OperationFuture<O>(O);
+
+impl<O: Operation + Unpin> Future for OperationFuture<O> {
+    type Output = Result<O::Output, O::Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let op = &mut self.0;
+        match op.poll() {
+            Err(::nb::Error::WouldBlock) => {}
+            Err(::nb::Error::Other(e)) => return Poll::Ready(Err(e)),
+            Ok(output) => return Poll::Ready(Ok(output)),
+        }
+        op.register_waker(cx.waker());
+        // Poll again in case the interrupt fired before the waker was registered.
+        match op.poll() {
+            Err(::nb::Error::WouldBlock) => Poll::Pending,
+            Err(::nb::Error::Other(e)) => Poll::Ready(Err(e)),
+            Ok(output) => Poll::Ready(Ok(output)),
+        }
+    }
+}
+
+/// Compile-time restriction of the algorithms a firmware image may use.
+///
+/// A policy is a marker type that implements [`Permits`] for each approved
+/// algorithm marker, such as the digest markers in [`crate::digest`] or the
+/// RSA moduli in [`crate::rsa`]. An image fixes its policy once, e.g.
+/// `type ImagePolicy = Cnsa;`, and bounds its generic crypto code on it:
+///
+/// ```
+/// use peripheral_traits::common::{Cnsa, PolicyDigest};
+/// use peripheral_traits::rsa::{Rsa3072, RsaKeyGen};
+///
+/// type ImagePolicy = Cnsa;
+///
+/// fn measure<D: PolicyDigest<ImagePolicy>>(digest: &mut D, image: &mut [u8]) -> Result<(), D::Error> {
+///     digest.update(image)
+/// }
+///
+/// fn provision<K: RsaKeyGen<Policy = ImagePolicy>>() -> Result<(), K::Error> {
+///     K::generate_keys::<Rsa3072>().map(|_| ())
+/// }
+/// ```
+///
+/// Passing a digest the policy does not permit is then a type error rather
+/// than something for code review to catch. Under [`Cnsa`], SHA-1 is
+/// rejected:
+///
+/// ```compile_fail,E0277
+/// use peripheral_traits::common::{Cnsa, PolicyDigest};
+/// use peripheral_traits::digest::{DigestOf, Sha1};
+///
+/// fn measure<D: PolicyDigest<Cnsa>>(_digest: &mut D) {}
+///
+/// fn legacy<D: DigestOf<Algorithm = Sha1>>(digest: &mut D) {
+///     measure(digest)
+/// }
+/// ```
+///
+/// RSA key generation is always gated by the implementation's policy, so a
+/// generator configured for [`Cnsa`] cannot produce 2048-bit keys:
+///
+/// ```compile_fail,E0277
+/// use peripheral_traits::common::Cnsa;
+/// use peripheral_traits::rsa::{Rsa2048, RsaKeyGen};
+///
+/// fn provision<K: RsaKeyGen<Policy = Cnsa>>() -> Result<(), K::Error> {
+///     K::generate_keys::<Rsa2048>().map(|_| ())
+/// }
+/// ```
+///
+/// Algorithm markers defined elsewhere, such as an implementation's curve
+/// types, are admitted with `impl Permits<MyCurve> for Fips140 {}`; custom
+/// policies are plain types implementing [`AlgorithmPolicy`].
+pub trait AlgorithmPolicy {}
+
+/// Declares that a policy approves the algorithm marker `A`.
+pub trait Permits<A: ?Sized>: AlgorithmPolicy {}
+
+/// Permits every algorithm.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub struct Unrestricted;
+
+impl AlgorithmPolicy for Unrestricted {}
+
+impl<A: ?Sized> Permits<A> for Unrestricted {}
+
+/// FIPS 140-3 approved algorithms: SHA-2 and SHA-3 digests and RSA moduli
+/// of at least 2048 bits.
+///
+/// Stricter than FIPS for SHA-1, which it forbids for every use.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub struct Fips140;
+
+impl AlgorithmPolicy for Fips140 {}
+
+/// CNSA 1.0 algorithms: SHA-384 or SHA-512 and RSA moduli of at least 3072 bits.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub struct Cnsa;
+
+impl AlgorithmPolicy for Cnsa {}
+
+macro_rules! permits {
+    ($policy:ty => $($algorithm:ty),* $(,)?) => {
+        $(impl Permits<$algorithm> for $policy {})*
+    };
+}
+
+permits!(Fips140 =>
+    digest::Sha224,
+    digest::Sha256,
+    digest::Sha384,
+    digest::Sha512,
+    digest::Sha3_224,
+    digest::Sha3_256,
+    digest::Sha3_384,
+    digest::Sha3_512,
+    Rsa2048,
+    Rsa3072,
+    Rsa4096,
+);
+
+permits!(Cnsa => digest::Sha384, digest::Sha512, Rsa3072, Rsa4096);
+
+/// A [`DigestOf`] whose algorithm the policy `P` permits.
+///
+/// Implemented for every such digest.
+pub trait PolicyDigest<P: AlgorithmPolicy>: DigestOf {}
+
+impl<P, D> PolicyDigest<P> for D
+where
+    P: Permits<D::Algorithm>,
+    D: DigestOf + ?Sized,
+{
+}
@@ -0,0 +1,59 @@
+//! PKCS#10 certificate signing requests for device identity provisioning.
+//!
+//! Shares its error type and signer with [`crate::cert`].
+
+pub use crate::cert::{Error, ErrorKind, ErrorType, SignatureAlgorithm, TbsSigner};
+
+/// Distinguished name of the requesting device.
+///
+/// Attributes are encoded in the order of the fields; `None` attributes are omitted.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Subject<'a> {
+    pub common_name: &'a str,
+    pub serial_number: Option<&'a str>,
+    pub organization: Option<&'a str>,
+    pub organizational_unit: Option<&'a str>,
+    pub country: Option<&'a str>,
+}
+
+/// An extension requested through the `extensionRequest` attribute.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Extension<'a> {
+    /// DER-encoded object identifier.
+    pub oid: &'a [u8],
+    pub critical: bool,
+    /// DER-encoded extension value.
+    pub value: &'a [u8],
+}
+
+/// Contents of a certificate signing request.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct CsrInfo<'a> {
+    pub subject: Subject<'a>,
+    /// DER-encoded `SubjectPublicKeyInfo` of the key being certified.
+    pub subject_public_key_info: &'a [u8],
+    pub extensions: &'a [Extension<'a>],
+}
+
+/// Encoder of PKCS#10 certification requests.
+pub trait CsrBuilder: ErrorType {
+    /// Encodes `info`, signs it with `signer` and writes the DER-encoded CSR into `out`.
+    ///
+    /// # Parameters
+    ///
+    /// - `info`: The subject, key and requested extensions.
+    /// - `signer`: Signs with the private key matching `info.subject_public_key_info`,
+    ///   e.g. an IDevID key held in a key vault.
+    /// - `out`: Output buffer.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes written, or an error of kind
+    /// [`ErrorKind::BufferTooSmall`].
+    fn build_csr(
+        &mut self,
+        info: &CsrInfo<'_>,
+        signer: &mut dyn TbsSigner<Error = Self::Error>,
+        out: &mut [u8],
+    ) -> Result<usize, Self::Error>;
+}
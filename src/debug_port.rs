@@ -0,0 +1,175 @@
+//! Host side of JTAG and SWD debug interfaces.
+//!
+//! BMC and root-of-trust firmware uses these to program and recover
+//! companion chips: [`Jtag`] drives an IEEE 1149.1 TAP, [`Swd`] performs
+//! Arm Serial Wire Debug transactions, and [`Swd::read_ap`] and
+//! [`Swd::write_ap`] build ADIv5 access-port accesses on top of them.
+//!
+//! Scan data is LSB first: bit 0 of byte 0 is shifted first.
+
+pub use crate::error::{Error, ErrorKind};
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// State of the TAP controller (IEEE 1149.1 figure 6-1).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TapState {
+    TestLogicReset,
+    RunTestIdle,
+    SelectDrScan,
+    CaptureDr,
+    ShiftDr,
+    Exit1Dr,
+    PauseDr,
+    Exit2Dr,
+    UpdateDr,
+    SelectIrScan,
+    CaptureIr,
+    ShiftIr,
+    Exit1Ir,
+    PauseIr,
+    Exit2Ir,
+    UpdateIr,
+}
+
+impl TapState {
+    /// Returns whether the TAP can stay in the state while TCK runs,
+    /// making it valid as the end state of a scan or move.
+    pub const fn is_stable(self) -> bool {
+        matches!(
+            self,
+            Self::TestLogicReset | Self::RunTestIdle | Self::PauseDr | Self::PauseIr
+        )
+    }
+}
+
+/// JTAG test access port controller.
+pub trait Jtag: ErrorType {
+    /// Resets the TAP: pulses TRST if wired, otherwise clocks five TMS-high
+    /// cycles. Leaves the TAP in [`TapState::TestLogicReset`].
+    fn reset(&mut self) -> Result<(), Self::Error>;
+
+    /// Returns the state the controller believes the TAP is in.
+    fn state(&self) -> TapState;
+
+    /// Moves the TAP to the stable state `state` along the shortest TMS path.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success, or an error of kind
+    /// [`ErrorKind::InvalidParameter`] if `state` is not stable.
+    fn move_to(&mut self, state: TapState) -> Result<(), Self::Error>;
+
+    /// Clocks `cycles` TCK cycles in [`TapState::RunTestIdle`], moving there first.
+    fn run_test_idle(&mut self, cycles: u32) -> Result<(), Self::Error>;
+
+    /// Shifts `bits` bits through the instruction register.
+    ///
+    /// # Parameters
+    ///
+    /// - `bits`: Number of bits to shift.
+    /// - `tdi`: Bits shifted in, at least `bits` long.
+    /// - `tdo`: Buffer for the bits shifted out, at least `bits` long, or
+    ///   empty to discard them.
+    /// - `end`: Stable state to move to after the scan.
+    fn scan_ir(&mut self, bits: usize, tdi: &[u8], tdo: &mut [u8], end: TapState) -> Result<(), Self::Error>;
+
+    /// Shifts `bits` bits through the selected data register.
+    ///
+    /// See [`Jtag::scan_ir`] for the parameters.
+    fn scan_dr(&mut self, bits: usize, tdi: &[u8], tdo: &mut [u8], end: TapState) -> Result<(), Self::Error>;
+
+    /// Sets the TCK frequency.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the frequency actually used, at most `hz`.
+    fn set_tck_frequency(&mut self, hz: u32) -> Result<u32, Self::Error>;
+
+    /// Reads the 32-bit IDCODE of a single TAP on the chain, selected by the reset.
+    fn read_idcode(&mut self) -> Result<u32, Self::Error> {
+        self.reset()?;
+        let mut idcode = [0u8; 4];
+        self.scan_dr(32, &[0; 4], &mut idcode, TapState::RunTestIdle)?;
+        Ok(u32::from_le_bytes(idcode))
+    }
+}
+
+/// SWD port addressed by a transaction.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum SwdPort {
+    /// Debug port.
+    Dp,
+    /// Access port selected by the DP `SELECT` register.
+    Ap,
+}
+
+/// ADIv5 DP identification register (read).
+pub const DP_DPIDR: u8 = 0x0;
+/// ADIv5 DP sticky error clearing register (write).
+pub const DP_ABORT: u8 = 0x0;
+/// ADIv5 DP control and status register.
+pub const DP_CTRL_STAT: u8 = 0x4;
+/// ADIv5 DP access port and bank select register.
+pub const DP_SELECT: u8 = 0x8;
+/// ADIv5 DP read buffer, holding the result of the last posted AP read.
+pub const DP_RDBUFF: u8 = 0xC;
+
+/// Serial Wire Debug host.
+///
+/// An `OK` acknowledge completes the transaction. Implementations retry
+/// `WAIT` for a bounded time and then fail with [`ErrorKind::Busy`]; a
+/// `FAULT` acknowledge fails with [`ErrorKind::Bus`], a data parity error
+/// with [`ErrorKind::Parity`] and no acknowledge with [`ErrorKind::Nack`].
+pub trait Swd: ErrorType {
+    /// Performs a line reset: at least 50 SWDIO-high cycles followed by two idle cycles.
+    ///
+    /// A `DPIDR` read must follow before other transactions are accepted.
+    fn line_reset(&mut self) -> Result<(), Self::Error>;
+
+    /// Sends the JTAG-to-SWD switch sequence, with line resets around it.
+    fn switch_from_jtag(&mut self) -> Result<(), Self::Error>;
+
+    /// Reads register `address` (bits 3:2 significant) of `port`.
+    ///
+    /// AP reads are posted: the value returned is that of the previous AP
+    /// read, and the final one is fetched from [`DP_RDBUFF`].
+    fn read(&mut self, port: SwdPort, address: u8) -> Result<u32, Self::Error>;
+
+    /// Writes `value` to register `address` (bits 3:2 significant) of `port`.
+    fn write(&mut self, port: SwdPort, address: u8, value: u32) -> Result<(), Self::Error>;
+
+    /// Sets the SWCLK frequency.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the frequency actually used, at most `hz`.
+    fn set_swclk_frequency(&mut self, hz: u32) -> Result<u32, Self::Error>;
+
+    /// Resets the line and reads `DPIDR`, bringing the DP online.
+    fn connect(&mut self) -> Result<u32, Self::Error> {
+        self.line_reset()?;
+        self.read(SwdPort::Dp, DP_DPIDR)
+    }
+
+    /// Reads register `register` of access port `ap`, selecting its bank first.
+    fn read_ap(&mut self, ap: u8, register: u8) -> Result<u32, Self::Error> {
+        self.write(SwdPort::Dp, DP_SELECT, ap_select(ap, register))?;
+        self.read(SwdPort::Ap, register & 0x0C)?;
+        self.read(SwdPort::Dp, DP_RDBUFF)
+    }
+
+    /// Writes `value` to register `register` of access port `ap`, selecting its bank first.
+    fn write_ap(&mut self, ap: u8, register: u8, value: u32) -> Result<(), Self::Error> {
+        self.write(SwdPort::Dp, DP_SELECT, ap_select(ap, register))?;
+        self.write(SwdPort::Ap, register & 0x0C, value)
+    }
+}
+
+/// `SELECT` value addressing the bank of `register` in access port `ap`.
+fn ap_select(ap: u8, register: u8) -> u32 {
+    u32::from(ap) << 24 | u32::from(register & 0xF0)
+}
@@ -1,5 +1,9 @@
 use core::fmt::Debug;
 
+use crate::common::Operation;
+
+pub mod scheduler;
+
 /// Error kind.
 ///
 /// This represents a common set of digest operation errors. Implementations are
@@ -57,6 +61,12 @@ impl Error for core::convert::Infallible {
     }
 }
 
+impl Error for ErrorKind {
+    fn kind(&self) -> ErrorKind {
+        *self
+    }
+}
+
 pub trait ErrorType {
     /// Error type.
     type Error: Error;
@@ -102,6 +112,238 @@ pub trait Digest: ErrorType {
     ///
     /// # Returns
     ///
-    /// A `Result` indicating success or failure. On success, returns `Ok(())`. On failure, returns a `CryptoError`.    
-    fn finalize(&mut self, out: &mut [u8]) -> Result<(), Self::Error>;
+    /// A `Result` indicating success or failure. On success, returns the number of bytes written to `out`.
+    /// On failure, returns a `CryptoError`.
+    fn finalize(&mut self, out: &mut [u8]) -> Result<usize, Self::Error>;
+}
+
+/// Export and import of intermediate digest state.
+///
+/// Allows a long-running hash computation to be suspended, e.g. across a
+/// power event, and resumed later from the saved state.
+pub trait DigestContext: Digest {
+    /// Maximum size in bytes of a serialized intermediate state.
+    const MAX_STATE_SIZE: usize;
+
+    /// Save the intermediate state of the computation.
+    ///
+    /// # Parameters
+    ///
+    /// - `state`: A mutable slice to store the state. The length of the slice must be at least `MAX_STATE_SIZE`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or failure. On success, returns the number of bytes written to `state`.
+    fn export_state(&self, state: &mut [u8]) -> Result<usize, Self::Error>;
+
+    /// Restore a previously exported intermediate state.
+    ///
+    /// # Parameters
+    ///
+    /// - `state`: State produced by `export_state`, possibly by another instance of the same implementation.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or failure. On success, subsequent `update` calls continue the saved computation.
+    fn import_state(&mut self, state: &[u8]) -> Result<(), Self::Error>;
+}
+
+/// Digest updates and finalization running on an interrupt-driven engine.
+pub trait DigestStart: Digest {
+    type UpdateOperation<'a>: Operation<Output = (), Error = Self::Error>
+    where
+        Self: 'a;
+    type FinalizeOperation<'a>: Operation<Output = usize, Error = Self::Error>
+    where
+        Self: 'a;
+
+    /// Starts absorbing `input`. Completes like [`Digest::update`].
+    fn start_update<'a>(&'a mut self, input: &'a [u8]) -> Result<Self::UpdateOperation<'a>, Self::Error>;
+
+    /// Starts finalization into `out`. Completes like [`Digest::finalize`].
+    fn start_finalize<'a>(&'a mut self, out: &'a mut [u8]) -> Result<Self::FinalizeOperation<'a>, Self::Error>;
+}
+
+/// Fixed-output digest algorithms.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum DigestAlgorithm {
+    Sha1,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+    Sha3_224,
+    Sha3_256,
+    Sha3_384,
+    Sha3_512,
+}
+
+impl DigestAlgorithm {
+    /// Output size in bytes.
+    pub const fn output_size(self) -> usize {
+        match self {
+            Self::Sha1 => 20,
+            Self::Sha224 | Self::Sha3_224 => 28,
+            Self::Sha256 | Self::Sha3_256 => 32,
+            Self::Sha384 | Self::Sha3_384 => 48,
+            Self::Sha512 | Self::Sha3_512 => 64,
+        }
+    }
+
+    /// Input block size in bytes; the sponge rate for SHA-3.
+    pub const fn block_size(self) -> usize {
+        match self {
+            Self::Sha1 | Self::Sha224 | Self::Sha256 => 64,
+            Self::Sha384 | Self::Sha512 => 128,
+            Self::Sha3_224 => 144,
+            Self::Sha3_256 => 136,
+            Self::Sha3_384 => 104,
+            Self::Sha3_512 => 72,
+        }
+    }
+
+    /// DER content octets of the algorithm's object identifier
+    /// (2.16.840.1.101.3.4.2.*, or 1.3.14.3.2.26 for SHA-1), as used in an
+    /// X.509 `AlgorithmIdentifier`.
+    pub const fn oid(self) -> &'static [u8] {
+        match self {
+            Self::Sha1 => &[0x2B, 0x0E, 0x03, 0x02, 0x1A],
+            Self::Sha256 => &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01],
+            Self::Sha384 => &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x02],
+            Self::Sha512 => &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x03],
+            Self::Sha224 => &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x04],
+            Self::Sha3_224 => &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x07],
+            Self::Sha3_256 => &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x08],
+            Self::Sha3_384 => &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x09],
+            Self::Sha3_512 => &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x0A],
+        }
+    }
+
+    /// Returns the algorithm with the given DER-encoded object identifier.
+    pub fn from_oid(oid: &[u8]) -> Option<Self> {
+        [
+            Self::Sha1,
+            Self::Sha224,
+            Self::Sha256,
+            Self::Sha384,
+            Self::Sha512,
+            Self::Sha3_224,
+            Self::Sha3_256,
+            Self::Sha3_384,
+            Self::Sha3_512,
+        ]
+        .into_iter()
+        .find(|algorithm| algorithm.oid() == oid)
+    }
+}
+
+/// Type-level identity of a digest algorithm.
+///
+/// Lets generic code such as HMAC, HKDF or certificate parsing size buffers
+/// and pick identifiers at compile time.
+pub trait DigestMarker {
+    const ALGORITHM: DigestAlgorithm;
+    const OUTPUT_SIZE: usize = Self::ALGORITHM.output_size();
+    const BLOCK_SIZE: usize = Self::ALGORITHM.block_size();
+}
+
+macro_rules! digest_markers {
+    ($($(#[$doc:meta])* $name:ident => $algorithm:ident,)*) => {
+        $(
+            $(#[$doc])*
+            #[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+            pub struct $name;
+
+            impl DigestMarker for $name {
+                const ALGORITHM: DigestAlgorithm = DigestAlgorithm::$algorithm;
+            }
+
+            impl crate::ecdsa::HashMarker for $name {
+                fn size() -> usize {
+                    Self::OUTPUT_SIZE
+                }
+            }
+        )*
+    };
+}
+
+digest_markers! {
+    /// SHA-1 (FIPS 180-4). No longer collision resistant; defined so that
+    /// legacy verification can name it and algorithm policies can forbid it.
+    Sha1 => Sha1,
+    /// SHA-224 (FIPS 180-4).
+    Sha224 => Sha224,
+    /// SHA-256 (FIPS 180-4).
+    Sha256 => Sha256,
+    /// SHA-384 (FIPS 180-4).
+    Sha384 => Sha384,
+    /// SHA-512 (FIPS 180-4).
+    Sha512 => Sha512,
+    /// SHA3-224 (FIPS 202).
+    Sha3_224 => Sha3_224,
+    /// SHA3-256 (FIPS 202).
+    Sha3_256 => Sha3_256,
+    /// SHA3-384 (FIPS 202).
+    Sha3_384 => Sha3_384,
+    /// SHA3-512 (FIPS 202).
+    Sha3_512 => Sha3_512,
+}
+
+/// A [`Digest`] bound to a single algorithm.
+pub trait DigestOf: Digest {
+    type Algorithm: DigestMarker;
+}
+
+/// Keccak-based extendable-output functions.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum XofAlgorithm {
+    Shake128,
+    Shake256,
+    CShake128,
+    CShake256,
+}
+
+/// Extendable-output function, such as SHAKE128 or SHAKE256.
+///
+/// Input is absorbed first; output of any length is then squeezed out in
+/// one or more calls. Unlike [`Digest`], there is no fixed output size.
+pub trait Xof: ErrorType {
+    /// The function computed by this instance.
+    fn algorithm(&self) -> XofAlgorithm;
+
+    /// Absorbs `input`. Must not be called after the first `squeeze`.
+    fn absorb(&mut self, input: &[u8]) -> Result<(), Self::Error>;
+
+    /// Fills `out` with the next bytes of output.
+    ///
+    /// Successive calls continue the output stream, so squeezing 32 bytes
+    /// twice yields the same bytes as squeezing 64 once.
+    fn squeeze(&mut self, out: &mut [u8]) -> Result<(), Self::Error>;
+
+    /// Resets the function to absorb a new input.
+    fn reset(&mut self) -> Result<(), Self::Error>;
+
+    /// Absorbs `input` and fills `out` in one call, starting from a reset state.
+    fn xof(&mut self, input: &[u8], out: &mut [u8]) -> Result<(), Self::Error> {
+        self.reset()?;
+        self.absorb(input)?;
+        self.squeeze(out)
+    }
+
+    /// Squeezes the next `N` bytes of output into an array.
+    fn squeeze_array<const N: usize>(&mut self) -> Result<[u8; N], Self::Error> {
+        let mut out = [0u8; N];
+        self.squeeze(&mut out)?;
+        Ok(out)
+    }
+}
+
+/// cSHAKE (NIST SP 800-185), SHAKE with domain separation.
+pub trait CShake: Xof {
+    /// Sets the function name `N` and customization string `S` and resets the function.
+    ///
+    /// With both empty, cSHAKE equals plain SHAKE.
+    fn set_customization(&mut self, function_name: &[u8], customization: &[u8]) -> Result<(), Self::Error>;
 }
@@ -0,0 +1,46 @@
+//! Sharing one hardware digest engine between several logical sessions.
+//!
+//! Only one session is loaded in the engine at a time. When another session
+//! needs the engine, the loaded one is exported (see [`super::DigestContext`])
+//! and restored when it is resumed, so concurrent flows such as measurement
+//! and firmware verification can interleave their updates.
+
+use super::ErrorType;
+
+pub trait DigestScheduler: ErrorType {
+    type InitParams;
+
+    /// Logical session. Owned by the caller and consumed on finalization.
+    type Session;
+
+    /// Acquires a new session.
+    ///
+    /// # Parameters
+    ///
+    /// - `init_params`: Algorithm and configuration of the session.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the session. Fails with `ErrorKind::Busy` when no
+    /// more sessions can be saved.
+    fn acquire(&mut self, init_params: Self::InitParams) -> Result<Self::Session, Self::Error>;
+
+    /// Loads `session` into the engine, yielding the currently loaded session if any.
+    fn resume(&mut self, session: &mut Self::Session) -> Result<(), Self::Error>;
+
+    /// Exports the loaded session's state and leaves the engine idle.
+    fn yield_engine(&mut self) -> Result<(), Self::Error>;
+
+    /// Feeds `input` to `session`, resuming it first if it is not loaded.
+    fn update(&mut self, session: &mut Self::Session, input: &[u8]) -> Result<(), Self::Error>;
+
+    /// Finalizes `session` and releases its resources.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes written to `out`.
+    fn finalize(&mut self, session: Self::Session, out: &mut [u8]) -> Result<usize, Self::Error>;
+
+    /// Releases `session` without producing a digest.
+    fn release(&mut self, session: Self::Session);
+}
@@ -0,0 +1,118 @@
+//! DMA transfers that take ownership of their buffers.
+//!
+//! Starting a transfer moves the buffer into it, so the CPU cannot touch the
+//! memory while the DMA engine does, and the buffer is handed back when the
+//! transfer completes or is aborted.
+
+pub use crate::error::{Error, ErrorKind};
+
+/// Memory a DMA engine reads from, i.e. the source of a transmit.
+///
+/// This crate provides implementations for `'static` references only.
+///
+/// # Safety
+///
+/// Implementations must return the same memory on every call for as long as
+/// the value lives, even when the value itself is moved, and the memory must
+/// stay valid until the value is dropped. HAL code hands the address to the
+/// DMA engine and relies on this, so a by-value array, which moves with its
+/// owner, must not implement the trait.
+#[allow(unsafe_code)]
+pub unsafe trait ReadBuffer {
+    fn dma_read_buffer(&self) -> &[u8];
+}
+
+/// Memory a DMA engine writes to, i.e. the destination of a receive.
+///
+/// # Safety
+///
+/// The same stability requirement as for [`ReadBuffer`] applies.
+#[allow(unsafe_code)]
+pub unsafe trait WriteBuffer {
+    fn dma_write_buffer(&mut self) -> &mut [u8];
+}
+
+#[allow(unsafe_code)]
+unsafe impl ReadBuffer for &'static [u8] {
+    fn dma_read_buffer(&self) -> &[u8] {
+        self
+    }
+}
+
+#[allow(unsafe_code)]
+unsafe impl ReadBuffer for &'static mut [u8] {
+    fn dma_read_buffer(&self) -> &[u8] {
+        self
+    }
+}
+
+#[allow(unsafe_code)]
+unsafe impl<const N: usize> ReadBuffer for &'static mut [u8; N] {
+    fn dma_read_buffer(&self) -> &[u8] {
+        &self[..]
+    }
+}
+
+#[allow(unsafe_code)]
+unsafe impl WriteBuffer for &'static mut [u8] {
+    fn dma_write_buffer(&mut self) -> &mut [u8] {
+        self
+    }
+}
+
+#[allow(unsafe_code)]
+unsafe impl<const N: usize> WriteBuffer for &'static mut [u8; N] {
+    fn dma_write_buffer(&mut self) -> &mut [u8] {
+        &mut self[..]
+    }
+}
+
+/// A one-shot transfer in progress.
+pub trait Transfer {
+    /// The buffer or buffers owned by the transfer.
+    type Buffer;
+
+    type Error: Error;
+
+    /// Returns whether the transfer has finished, successfully or not.
+    fn is_complete(&self) -> bool;
+
+    /// Blocks until the transfer finishes and returns the buffer with the outcome.
+    fn wait(self) -> (Self::Buffer, Result<(), Self::Error>);
+
+    /// Stops the transfer and returns the buffer. Its contents are unspecified.
+    fn abort(self) -> Self::Buffer;
+}
+
+/// Half of a circular buffer.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Half {
+    First,
+    Second,
+}
+
+/// A circular receive, where the DMA engine refills the two halves of the buffer in turn.
+pub trait CircularTransfer {
+    type Buffer;
+
+    type Error: Error;
+
+    /// Returns the half the engine finished since the last call, if any.
+    ///
+    /// Fails with [`ErrorKind::Overrun`] if the engine refilled a half before
+    /// it was read.
+    fn poll_half(&mut self) -> Result<Option<Half>, Self::Error>;
+
+    /// Returns the contents of a finished half.
+    fn half(&self, half: Half) -> &[u8];
+
+    /// Stops the transfer and returns the buffer.
+    fn stop(self) -> Self::Buffer;
+}
+
+/// Failure to start a transfer, handing the buffer back.
+#[derive(Debug)]
+pub struct StartError<E, B> {
+    pub error: E,
+    pub buffer: B,
+}
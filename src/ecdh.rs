@@ -0,0 +1,132 @@
+use core::fmt::Debug;
+
+use crate::common::Operation;
+use crate::ecdsa::EcdsaCurve;
+use crate::key_vault::{KeyLocator, KeyUsage, KeyVault};
+
+pub trait Error: core::fmt::Debug {
+    /// Convert error to a generic error kind
+    ///
+    /// By using this method, errors freely defined by HAL implementations
+    /// can be converted to a set of generic errors upon which generic
+    /// code can act.
+    fn kind(&self) -> ErrorKind;
+}
+
+impl Error for core::convert::Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// Error kind.
+///
+/// This represents a common set of key agreement errors. Implementations are
+/// free to define more specific or additional error types. However, by providing
+/// a mapping to these common errors, generic code can still react to them.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    Busy,
+    /// The peer public key is not a valid point on the curve.
+    InvalidPublicKey,
+    KeyGenError,
+    /// The computed shared secret is the point at infinity.
+    InvalidSharedSecret,
+    /// Storing the shared secret in the key vault failed.
+    KeyStorageFailure,
+    Other,
+}
+
+/// Key types of an ECDH implementation.
+pub trait EcdhTypes {
+    type PrivateKey;
+    type PublicKey;
+    /// Raw shared secret, e.g. the x-coordinate as `[u8; 32]` for P-256.
+    type SharedSecret;
+    type Curve: EcdsaCurve;
+}
+
+/// Trait for ephemeral ECDH.
+///
+/// The ephemeral private key is generated and consumed inside the
+/// implementation, so it never needs to be handled by the caller.
+pub trait EcdhEphemeral: ErrorType + EcdhTypes {
+    /// Opaque handle to a generated ephemeral key pair, consumed by [`EcdhEphemeral::agree`].
+    type EphemeralKey;
+
+    /// Generates an ephemeral key pair.
+    ///
+    /// # Parameters
+    /// - `curve`: The elliptic curve to use.
+    ///
+    /// # Returns
+    /// A result containing the ephemeral key and the public key to send to the peer, or an error.
+    fn generate(
+        &mut self,
+        curve: &Self::Curve,
+    ) -> Result<(Self::EphemeralKey, Self::PublicKey), Self::Error>;
+
+    /// Computes the shared secret and destroys the ephemeral key.
+    ///
+    /// # Returns
+    /// A result containing the shared secret, or an error.
+    fn agree(
+        &mut self,
+        ephemeral_key: Self::EphemeralKey,
+        peer_public_key: &Self::PublicKey,
+    ) -> Result<Self::SharedSecret, Self::Error>;
+}
+
+/// Trait for static ECDH with a long-term private key.
+pub trait EcdhStatic: ErrorType + EcdhTypes {
+    /// Computes the shared secret between `private_key` and `peer_public_key`.
+    ///
+    /// # Returns
+    /// A result containing the shared secret, or an error.
+    fn agree(
+        &mut self,
+        curve: &Self::Curve,
+        private_key: &Self::PrivateKey,
+        peer_public_key: &Self::PublicKey,
+    ) -> Result<Self::SharedSecret, Self::Error>;
+}
+
+/// Trait for static ECDH on an interrupt-driven engine.
+pub trait EcdhStaticStart: ErrorType + EcdhTypes {
+    type AgreeOperation<'a>: Operation<Output = Self::SharedSecret, Error = Self::Error>
+    where
+        Self: 'a;
+
+    /// Starts computing a shared secret. Completes like [`EcdhStatic::agree`].
+    fn start_agree<'a>(
+        &'a mut self,
+        curve: &'a Self::Curve,
+        private_key: &'a Self::PrivateKey,
+        peer_public_key: &'a Self::PublicKey,
+    ) -> Result<Self::AgreeOperation<'a>, Self::Error>;
+}
+
+/// Trait for static ECDH whose shared secret is written directly into a key vault.
+pub trait EcdhToVault: ErrorType + EcdhTypes {
+    type Vault: KeyVault;
+
+    /// Computes the shared secret and stores it at `locator`.
+    ///
+    /// # Returns
+    /// A result containing the handle of the shared secret, or an error.
+    fn agree_to_vault(
+        &mut self,
+        vault: &mut Self::Vault,
+        curve: &Self::Curve,
+        private_key: &Self::PrivateKey,
+        peer_public_key: &Self::PublicKey,
+        locator: KeyLocator<'_>,
+        usage: KeyUsage,
+    ) -> Result<<Self::Vault as KeyVault>::KeyHandle, Self::Error>;
+}
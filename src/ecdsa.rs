@@ -1,5 +1,9 @@
 use core::fmt::Debug;
 
+use crate::common::{Operation, Zeroize};
+use crate::key_vault::{GeneratedKeyPair, KeyDestination};
+use crate::rand::{Drbg, Error as _, ErrorKind as RandErrorKind};
+
 pub trait Error: core::fmt::Debug {
     /// Convert error to a generic error kind
     ///
@@ -26,6 +30,10 @@ pub enum ErrorKind {
     InvalidSignature,
     KeyGenError,
     SigningError,
+    /// The output buffer is too small for the encoding.
+    BufferTooSmall,
+    /// The [`NonceSource`] failed to deliver nonce material.
+    NonceUnavailable,
     Other,
 }
 
@@ -38,7 +46,7 @@ pub trait EcdsaCurve {
 }
 
 pub trait EcdsaTypes {
-    type PrivateKey;
+    type PrivateKey: Zeroize;
     type PublicKey;
     type Signature;
     type Curve: EcdsaCurve;
@@ -66,7 +74,7 @@ pub trait EcdsaKeyGen: ErrorType + EcdsaTypes {
 ///
 /// This trait defines the methods required for signing messages using ECDSA.
 pub trait EcdsaSign: ErrorType {
-    type PrivateKey;
+    type PrivateKey: Zeroize;
     type Curve: EcdsaCurve;
     type Signature;
 
@@ -86,6 +94,122 @@ pub trait EcdsaSign: ErrorType {
     ) -> Result<Self::Signature, Self::Error>;
 }
 
+/// How the per-signature nonce `k` is chosen.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum NonceGeneration {
+    /// `k` is drawn from an approved random bit generator (FIPS 186-5).
+    Random,
+    /// `k` is derived from the private key and message hash (RFC 6979).
+    Deterministic,
+    /// `k` is requested from a caller-supplied [`NonceSource`] at signing time.
+    External,
+}
+
+/// Declares how [`EcdsaSign::sign`] chooses its nonce, so callers with FIPS
+/// or reproducibility requirements can check at compile time.
+pub trait EcdsaNonceGeneration: EcdsaSign {
+    const NONCE_GENERATION: NonceGeneration;
+}
+
+/// Trait for deterministic ECDSA signing as specified in RFC 6979.
+///
+/// Available regardless of what [`EcdsaNonceGeneration::NONCE_GENERATION`] reports for `sign`.
+pub trait EcdsaSignDeterministic: EcdsaSign {
+    /// Signs a message hash with a nonce derived per RFC 6979 using the hash `H`.
+    ///
+    /// # Parameters
+    /// - `curve`: The elliptic curve to use for signing.
+    /// - `private_key`: The private key to use for signing.
+    /// - `message_hash`: The hash of the message to sign.
+    ///
+    /// # Returns
+    /// A result containing the generated signature, or an error.
+    fn sign_deterministic<H: HashMarker>(
+        curve: &Self::Curve,
+        private_key: &Self::PrivateKey,
+        message_hash: impl AsRef<[u8]>,
+    ) -> Result<Self::Signature, Self::Error>;
+}
+
+/// Supplier of nonce material for signing engines that take `k` as an input.
+///
+/// Implemented for every [`Drbg`], so an instantiated HMAC-DRBG or CTR-DRBG
+/// can be passed as `&mut dyn NonceSource` regardless of its error type.
+pub trait NonceSource {
+    /// Fills `dest` with fresh random bytes.
+    ///
+    /// # Parameters
+    /// - `additional_input`: Additional input for the generate call, see [`EcdsaSignWithNonceSource`].
+    /// - `dest`: Output buffer.
+    fn fill_nonce(&mut self, additional_input: &[u8], dest: &mut [u8]) -> Result<(), RandErrorKind>;
+}
+
+impl<T: Drbg + ?Sized> NonceSource for T {
+    fn fill_nonce(&mut self, additional_input: &[u8], dest: &mut [u8]) -> Result<(), RandErrorKind> {
+        self.generate(additional_input, dest).map_err(|e| e.kind())
+    }
+}
+
+/// Trait for ECDSA signing on engines that need an externally supplied `k`.
+///
+/// Rather than leaving the nonce source implementation-defined, the caller
+/// passes the generator at signing time, so production builds bind it to
+/// an approved DRBG and tests can substitute a known-answer source.
+///
+/// # Nonce contract
+///
+/// For every call, the implementation:
+/// - requests fresh bytes from `nonces` and never caches, reuses or returns them;
+/// - passes `message_hash` as the additional input of every request, so even
+///   a generator stuck in one state yields different `k` for different messages;
+/// - requests the byte length of the curve order and discards candidates
+///   outside `[1, n - 1]`, requesting again (rejection sampling, FIPS 186-5 A.3.2);
+/// - zeroizes the candidate buffers before returning;
+/// - fails with [`ErrorKind::NonceUnavailable`] if `nonces` fails, without
+///   falling back to another source.
+pub trait EcdsaSignWithNonceSource: EcdsaSign {
+    /// Signs a message hash using `k` drawn from `nonces`.
+    ///
+    /// # Parameters
+    /// - `curve`: The elliptic curve to use for signing.
+    /// - `private_key`: The private key to use for signing.
+    /// - `message_hash`: The hash of the message to sign.
+    /// - `nonces`: Generator the nonce is requested from.
+    ///
+    /// # Returns
+    /// A result containing the generated signature, or an error.
+    fn sign_with_nonce_source<H: HashMarker>(
+        curve: &Self::Curve,
+        private_key: &Self::PrivateKey,
+        message_hash: impl AsRef<[u8]>,
+        nonces: &mut dyn NonceSource,
+    ) -> Result<Self::Signature, Self::Error>;
+}
+
+/// Trait for ECDSA signing with a caller-supplied nonce.
+///
+/// Only meant for running known-answer tests such as CAVP vectors; reusing
+/// or leaking a nonce reveals the private key.
+#[cfg(feature = "test-hooks")]
+pub trait EcdsaSignWithNonce: EcdsaSign {
+    /// Signs a message hash using `nonce` as `k`.
+    ///
+    /// # Parameters
+    /// - `curve`: The elliptic curve to use for signing.
+    /// - `private_key`: The private key to use for signing.
+    /// - `message_hash`: The hash of the message to sign.
+    /// - `nonce`: Big-endian nonce, in `[1, n - 1]`.
+    ///
+    /// # Returns
+    /// A result containing the generated signature, or an error.
+    fn sign_with_nonce<H: HashMarker>(
+        curve: &Self::Curve,
+        private_key: &Self::PrivateKey,
+        message_hash: impl AsRef<[u8]>,
+        nonce: &[u8],
+    ) -> Result<Self::Signature, Self::Error>;
+}
+
 /// Trait for ECDSA verification.
 ///
 /// This trait defines the methods required for verifying ECDSA signatures.
@@ -112,3 +236,140 @@ pub trait EcdsaVerify: ErrorType {
     ) -> Result<(), Self::Error>;
 }
 
+/// Trait for ECDSA signing on an interrupt-driven engine.
+pub trait EcdsaSignStart: EcdsaSign {
+    type SignOperation<'a>: Operation<Output = Self::Signature, Error = Self::Error>
+    where
+        Self: 'a;
+
+    /// Starts signing a message hash. Completes like [`EcdsaSign::sign`].
+    fn start_sign<'a, H: HashMarker>(
+        &'a mut self,
+        curve: &'a Self::Curve,
+        private_key: &'a Self::PrivateKey,
+        message_hash: &'a [u8],
+    ) -> Result<Self::SignOperation<'a>, Self::Error>;
+}
+
+/// Trait for ECDSA verification on an interrupt-driven engine.
+pub trait EcdsaVerifyStart: EcdsaVerify {
+    type VerifyOperation<'a>: Operation<Output = (), Error = Self::Error>
+    where
+        Self: 'a;
+
+    /// Starts verifying a signature. Completes like [`EcdsaVerify::verify`].
+    fn start_verify<'a, H: HashMarker>(
+        &'a mut self,
+        curve: &'a Self::Curve,
+        public_key: &'a Self::PublicKey,
+        message_hash: &'a [u8],
+        signature: &'a Self::Signature,
+    ) -> Result<Self::VerifyOperation<'a>, Self::Error>;
+}
+
+/// Key pair returned by [`EcdsaGenerateKeyPair::generate_key_pair`].
+pub type GeneratedKeyPairOf<G> = GeneratedKeyPair<
+    <G as EcdsaTypes>::PrivateKey,
+    <G as EcdsaGenerateKeyPair>::KeyHandle,
+    <G as EcdsaTypes>::PublicKey,
+>;
+
+/// Trait for ECDSA key generation into raw material or a hardware key slot.
+pub trait EcdsaGenerateKeyPair: ErrorType + EcdsaTypes {
+    /// Handle to a private key kept in hardware.
+    type KeyHandle;
+
+    /// Generates an ECDSA key pair.
+    ///
+    /// # Parameters
+    /// - `curve`: The elliptic curve to use for key generation.
+    /// - `destination`: Whether to return the private key or keep it in hardware.
+    ///
+    /// # Returns
+    /// A result containing the key pair, or an error.
+    fn generate_key_pair(
+        &mut self,
+        curve: &Self::Curve,
+        destination: KeyDestination<'_>,
+    ) -> Result<GeneratedKeyPairOf<Self>, Self::Error>;
+}
+
+/// SEC1 encoding of an elliptic-curve point.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum PointFormat {
+    /// `0x04 || x || y`.
+    Uncompressed,
+    /// `0x02` or `0x03`, depending on the parity of y, followed by `x`.
+    Compressed,
+}
+
+/// Trait for exporting ECDSA public keys.
+pub trait EcdsaPublicKeyExport: ErrorType + EcdsaTypes {
+    /// Encodes `public_key` as a SEC1 point.
+    ///
+    /// # Parameters
+    /// - `public_key`: The public key to export.
+    /// - `format`: The point encoding.
+    /// - `out`: Output buffer.
+    ///
+    /// # Returns
+    /// A result containing the number of bytes written, or an error of kind
+    /// [`ErrorKind::BufferTooSmall`].
+    fn export_public_key(
+        &self,
+        public_key: &Self::PublicKey,
+        format: PointFormat,
+        out: &mut [u8],
+    ) -> Result<usize, Self::Error>;
+}
+
+/// Reason an elliptic-curve public key was rejected (NIST SP 800-56A 5.6.2.3).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum PublicKeyDefect {
+    /// The point is the point at infinity.
+    Identity,
+    /// A coordinate is not in the range `[0, p)`.
+    CoordinateOutOfRange,
+    /// The point does not satisfy the curve equation.
+    NotOnCurve,
+    /// `n * Q` is not the point at infinity, so the point lies outside the prime-order subgroup.
+    NotInSubgroup,
+}
+
+/// How thoroughly a public key is checked.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ValidationLevel {
+    /// Range and on-curve checks. Sufficient on prime-order curves such as P-256 and P-384.
+    Partial,
+    /// Partial checks plus the subgroup check.
+    Full,
+}
+
+/// Failure of [`EcdsaPublicKeyValidate::validate_public_key`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum KeyValidationError<E> {
+    /// The key is malformed.
+    Rejected(PublicKeyDefect),
+    /// The check itself could not be run.
+    Other(E),
+}
+
+/// Trait for validating externally supplied ECDSA public keys before use.
+pub trait EcdsaPublicKeyValidate: ErrorType + EcdsaTypes {
+    /// Checks that `public_key` is a valid point of `curve`.
+    ///
+    /// # Parameters
+    /// - `curve`: The curve the key is claimed to belong to.
+    /// - `public_key`: The key to check.
+    /// - `level`: Which checks to run.
+    ///
+    /// # Returns
+    /// A result indicating success, or the first defect found.
+    fn validate_public_key(
+        &self,
+        curve: &Self::Curve,
+        public_key: &Self::PublicKey,
+        level: ValidationLevel,
+    ) -> Result<(), KeyValidationError<Self::Error>>;
+}
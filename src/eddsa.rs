@@ -0,0 +1,195 @@
+use core::fmt::Debug;
+
+use crate::common::Operation;
+
+pub trait Error: core::fmt::Debug {
+    /// Convert error to a generic error kind
+    ///
+    /// By using this method, errors freely defined by HAL implementations
+    /// can be converted to a set of generic errors upon which generic
+    /// code can act.
+    fn kind(&self) -> ErrorKind;
+}
+
+impl Error for core::convert::Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// Error kind.
+///
+/// This represents a common set of EdDSA operation errors. Implementations are
+/// free to define more specific or additional error types. However, by providing
+/// a mapping to these common errors, generic code can still react to them.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    Busy,
+    InvalidSignature,
+    InvalidPublicKey,
+    /// The context string is longer than the 255 bytes allowed by RFC 8032.
+    InvalidContext,
+    SigningError,
+    Other,
+}
+
+/// Key and signature types of an EdDSA instance.
+///
+/// Implementations are expected to use fixed-size types, e.g. `[u8; 32]`
+/// keys and `[u8; 64]` signatures for Ed25519, so no allocation is needed.
+pub trait EddsaTypes {
+    type PrivateKey;
+    type PublicKey;
+    type Signature;
+}
+
+/// Trait for Ed25519 signing (RFC 8032, section 5.1).
+pub trait Ed25519Sign: ErrorType + EddsaTypes {
+    /// Signs a message with pure Ed25519.
+    ///
+    /// # Parameters
+    /// - `private_key`: The private key to use for signing.
+    /// - `message`: The complete message to sign. EdDSA hashes the message itself.
+    ///
+    /// # Returns
+    /// A result containing the generated signature, or an error.
+    fn sign(
+        &mut self,
+        private_key: &Self::PrivateKey,
+        message: &[u8],
+    ) -> Result<Self::Signature, Self::Error>;
+
+    /// Signs a message with Ed25519ctx.
+    ///
+    /// # Parameters
+    /// - `private_key`: The private key to use for signing.
+    /// - `context`: Domain-separation context string, at most 255 bytes and not empty.
+    /// - `message`: The complete message to sign.
+    ///
+    /// # Returns
+    /// A result containing the generated signature, or an error.
+    fn sign_with_context(
+        &mut self,
+        private_key: &Self::PrivateKey,
+        context: &[u8],
+        message: &[u8],
+    ) -> Result<Self::Signature, Self::Error>;
+}
+
+/// Trait for Ed25519 verification (RFC 8032, section 5.1).
+pub trait Ed25519Verify: ErrorType + EddsaTypes {
+    /// Verifies a pure Ed25519 signature.
+    ///
+    /// # Returns
+    /// A result indicating whether the signature is valid, or an error.
+    fn verify(
+        &mut self,
+        public_key: &Self::PublicKey,
+        message: &[u8],
+        signature: &Self::Signature,
+    ) -> Result<(), Self::Error>;
+
+    /// Verifies an Ed25519ctx signature.
+    ///
+    /// # Returns
+    /// A result indicating whether the signature is valid, or an error.
+    fn verify_with_context(
+        &mut self,
+        public_key: &Self::PublicKey,
+        context: &[u8],
+        message: &[u8],
+        signature: &Self::Signature,
+    ) -> Result<(), Self::Error>;
+}
+
+/// Trait for Ed25519 signing on an interrupt-driven engine.
+pub trait Ed25519SignStart: ErrorType + EddsaTypes {
+    type SignOperation<'a>: Operation<Output = Self::Signature, Error = Self::Error>
+    where
+        Self: 'a;
+
+    /// Starts signing a message. Completes like [`Ed25519Sign::sign`].
+    fn start_sign<'a>(
+        &'a mut self,
+        private_key: &'a Self::PrivateKey,
+        message: &'a [u8],
+    ) -> Result<Self::SignOperation<'a>, Self::Error>;
+}
+
+/// Trait for Ed25519 verification on an interrupt-driven engine.
+pub trait Ed25519VerifyStart: ErrorType + EddsaTypes {
+    type VerifyOperation<'a>: Operation<Output = (), Error = Self::Error>
+    where
+        Self: 'a;
+
+    /// Starts verifying a signature. Completes like [`Ed25519Verify::verify`].
+    fn start_verify<'a>(
+        &'a mut self,
+        public_key: &'a Self::PublicKey,
+        message: &'a [u8],
+        signature: &'a Self::Signature,
+    ) -> Result<Self::VerifyOperation<'a>, Self::Error>;
+}
+
+/// Trait for Ed25519ph signing, where the caller hashes the message with SHA-512.
+pub trait Ed25519phSign: ErrorType + EddsaTypes {
+    /// Signs a SHA-512 prehash of the message.
+    ///
+    /// # Parameters
+    /// - `private_key`: The private key to use for signing.
+    /// - `context`: Domain-separation context string, at most 255 bytes. May be empty.
+    /// - `prehash`: SHA-512 digest of the message.
+    ///
+    /// # Returns
+    /// A result containing the generated signature, or an error.
+    fn sign_prehashed(
+        &mut self,
+        private_key: &Self::PrivateKey,
+        context: &[u8],
+        prehash: &[u8; 64],
+    ) -> Result<Self::Signature, Self::Error>;
+}
+
+/// Trait for Ed25519ph verification.
+pub trait Ed25519phVerify: ErrorType + EddsaTypes {
+    /// Verifies a signature over a SHA-512 prehash of the message.
+    ///
+    /// # Returns
+    /// A result indicating whether the signature is valid, or an error.
+    fn verify_prehashed(
+        &mut self,
+        public_key: &Self::PublicKey,
+        context: &[u8],
+        prehash: &[u8; 64],
+        signature: &Self::Signature,
+    ) -> Result<(), Self::Error>;
+}
+
+/// Trait for Ed448 signing (RFC 8032, section 5.2).
+///
+/// Ed448 always takes a context string, which may be empty.
+pub trait Ed448Sign: ErrorType + EddsaTypes {
+    fn sign(
+        &mut self,
+        private_key: &Self::PrivateKey,
+        context: &[u8],
+        message: &[u8],
+    ) -> Result<Self::Signature, Self::Error>;
+}
+
+/// Trait for Ed448 verification (RFC 8032, section 5.2).
+pub trait Ed448Verify: ErrorType + EddsaTypes {
+    fn verify(
+        &mut self,
+        public_key: &Self::PublicKey,
+        context: &[u8],
+        message: &[u8],
+        signature: &Self::Signature,
+    ) -> Result<(), Self::Error>;
+}
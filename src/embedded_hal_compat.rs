@@ -0,0 +1,176 @@
+//! Adapters between these traits and embedded-hal 1.0.
+//!
+//! [`FromHal`] wraps an embedded-hal implementation so it can be used where
+//! the traits of this crate are expected; [`IntoHal`] and [`HalSpiDevice`] go
+//! the other way, so drivers written against embedded-hal run on top of
+//! implementations of this crate.
+
+use embedded_hal as hal;
+
+use crate::error::{Error, ErrorKind};
+use crate::{gpio, spi_master, time};
+
+/// Number of operations [`HalSpiDevice`] can forward in a single transaction.
+pub const MAX_SPI_OPERATIONS: usize = 16;
+
+/// An embedded-hal implementation used through the traits of this crate.
+pub struct FromHal<T>(pub T);
+
+/// Error of an embedded-hal digital pin wrapped in [`FromHal`].
+#[derive(Debug)]
+pub struct DigitalError<E>(pub E);
+
+impl<E: hal::digital::Error> Error for DigitalError<E> {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+impl<T: hal::digital::ErrorType> gpio::ErrorType for FromHal<T> {
+    type Error = DigitalError<T::Error>;
+}
+
+impl<T: hal::digital::InputPin> gpio::InputPin for FromHal<T> {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        self.0.is_high().map_err(DigitalError)
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        self.0.is_low().map_err(DigitalError)
+    }
+}
+
+impl<T: hal::digital::OutputPin> gpio::OutputPin for FromHal<T> {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.0.set_low().map_err(DigitalError)
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.0.set_high().map_err(DigitalError)
+    }
+}
+
+impl<T: hal::delay::DelayNs> time::DelayNs for FromHal<T> {
+    fn delay_ns(&mut self, ns: u32) {
+        self.0.delay_ns(ns)
+    }
+
+    fn delay_us(&mut self, us: u32) {
+        self.0.delay_us(us)
+    }
+
+    fn delay_ms(&mut self, ms: u32) {
+        self.0.delay_ms(ms)
+    }
+}
+
+/// An implementation of this crate used through embedded-hal traits.
+pub struct IntoHal<T>(pub T);
+
+/// Error of an implementation wrapped in [`IntoHal`] or [`HalSpiDevice`].
+#[derive(Debug)]
+pub enum HalError<E> {
+    /// The wrapped implementation failed.
+    Inner(E),
+    /// The transaction has more than [`MAX_SPI_OPERATIONS`] operations.
+    TooManyOperations,
+}
+
+impl<E: Error> hal::digital::Error for HalError<E> {
+    fn kind(&self) -> hal::digital::ErrorKind {
+        hal::digital::ErrorKind::Other
+    }
+}
+
+impl<E: Error> hal::spi::Error for HalError<E> {
+    fn kind(&self) -> hal::spi::ErrorKind {
+        let HalError::Inner(e) = self else {
+            return hal::spi::ErrorKind::Other;
+        };
+        match e.kind() {
+            ErrorKind::Overrun => hal::spi::ErrorKind::Overrun,
+            ErrorKind::ModeFault => hal::spi::ErrorKind::ModeFault,
+            ErrorKind::FrameFormat => hal::spi::ErrorKind::FrameFormat,
+            ErrorKind::ChipSelectFault => hal::spi::ErrorKind::ChipSelectFault,
+            _ => hal::spi::ErrorKind::Other,
+        }
+    }
+}
+
+impl<T: gpio::ErrorType> hal::digital::ErrorType for IntoHal<T> {
+    type Error = HalError<T::Error>;
+}
+
+impl<T: gpio::InputPin> hal::digital::InputPin for IntoHal<T> {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        self.0.is_high().map_err(HalError::Inner)
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        self.0.is_low().map_err(HalError::Inner)
+    }
+}
+
+impl<T: gpio::OutputPin> hal::digital::OutputPin for IntoHal<T> {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.0.set_low().map_err(HalError::Inner)
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.0.set_high().map_err(HalError::Inner)
+    }
+}
+
+impl<T: time::DelayNs> hal::delay::DelayNs for IntoHal<T> {
+    fn delay_ns(&mut self, ns: u32) {
+        self.0.delay_ns(ns)
+    }
+
+    fn delay_us(&mut self, us: u32) {
+        self.0.delay_us(us)
+    }
+
+    fn delay_ms(&mut self, ms: u32) {
+        self.0.delay_ms(ms)
+    }
+}
+
+/// One chip select of a [`spi_master::SpiMaster`], exposed as an embedded-hal `SpiDevice`.
+pub struct HalSpiDevice<S: spi_master::SpiMaster> {
+    pub spi: S,
+    pub cs: S::ChipSelect,
+}
+
+impl<S: spi_master::SpiMaster> hal::spi::ErrorType for HalSpiDevice<S> {
+    type Error = HalError<S::Error>;
+}
+
+impl<S: spi_master::SpiMaster> hal::spi::SpiDevice for HalSpiDevice<S> {
+    fn transaction(
+        &mut self,
+        operations: &mut [hal::spi::Operation<'_, u8>],
+    ) -> Result<(), Self::Error> {
+        let len = operations.len();
+        if len > MAX_SPI_OPERATIONS {
+            return Err(HalError::TooManyOperations);
+        }
+        let mut ops: [spi_master::Operation<'_>; MAX_SPI_OPERATIONS] =
+            core::array::from_fn(|_| spi_master::Operation::DelayNs(0));
+        for (op, hal_op) in ops.iter_mut().zip(operations.iter_mut()) {
+            *op = match hal_op {
+                hal::spi::Operation::Read(buf) => spi_master::Operation::Read(buf),
+                hal::spi::Operation::Write(buf) => spi_master::Operation::Write(buf),
+                hal::spi::Operation::Transfer(read, write) => {
+                    spi_master::Operation::Transfer(read, write)
+                }
+                hal::spi::Operation::TransferInPlace(buf) => {
+                    spi_master::Operation::TransferInPlace(buf)
+                }
+                hal::spi::Operation::DelayNs(ns) => spi_master::Operation::DelayNs(*ns),
+            };
+        }
+        self.spi
+            .transaction(self.cs, &mut ops[..len])
+            .map_err(HalError::Inner)
+    }
+}
@@ -0,0 +1,127 @@
+//! Error taxonomy shared by the peripheral traits.
+//!
+//! Every peripheral module bounds its `ErrorType::Error` by [`Error`], so
+//! generic drivers can tell, for example, a NACK from a bus timeout no matter
+//! which bus or implementation produced it.
+
+use core::fmt::Debug;
+
+/// Error kind.
+///
+/// This represents a common set of peripheral errors. Implementations are
+/// free to define more specific or additional error types. However, by providing
+/// a mapping to these common errors, generic code can still react to them.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The addressed device did not acknowledge.
+    Nack,
+
+    /// Bus arbitration was lost to another controller.
+    ArbitrationLoss,
+
+    /// A misplaced START or STOP condition or another bus protocol violation was detected.
+    Bus,
+
+    /// The operation did not complete in time.
+    Timeout,
+
+    /// A parity or CRC error was detected on the bus.
+    Parity,
+
+    /// Data was received while the receive buffer was full.
+    Overrun,
+
+    /// More data was requested than had been staged for transmission.
+    Underrun,
+
+    /// Multiple devices on the bus are trying to drive the same line.
+    ModeFault,
+
+    /// Received data does not conform to the peripheral configuration.
+    FrameFormat,
+
+    /// Noise was detected on a received frame.
+    Noise,
+
+    /// A chip-select line could not be asserted or released.
+    ChipSelectFault,
+
+    /// The address is not valid or not assigned to any device.
+    InvalidAddress,
+
+    /// Reading from the storage medium failed.
+    ReadError,
+
+    /// Programming the storage medium failed or the programmed data did not read back.
+    ProgramError,
+
+    /// Erasing the storage medium failed.
+    EraseError,
+
+    /// The address or range lies outside the device or region.
+    OutOfBounds,
+
+    /// The resource is locked against the operation.
+    Locked,
+
+    /// The data has an ECC error that could not be corrected.
+    UncorrectableEcc,
+
+    /// The operation is not permitted in the current device lifecycle state.
+    InvalidLifecycleState,
+
+    /// A parameter or configuration value is outside the range supported by the hardware.
+    InvalidParameter,
+
+    /// The hardware does not support the requested operation or mode.
+    Unsupported,
+
+    /// The operation is not permitted in the current state, e.g. disabling a locked watchdog.
+    NotPermitted,
+
+    /// The resource is reserved by another function or owner.
+    InUse,
+
+    /// The requested item does not exist, e.g. a missing key-value record.
+    NotFound,
+
+    /// There is no space left for the request.
+    Full,
+
+    /// A caller-supplied buffer is too small for the data.
+    BufferTooSmall,
+
+    /// The hardware is busy and cannot process the request.
+    Busy,
+
+    /// A monitored input stopped moving, e.g. a stalled fan.
+    Stalled,
+
+    /// General hardware failure.
+    HardwareFailure,
+
+    /// A different error occurred.
+    Other,
+}
+
+pub trait Error: core::fmt::Debug {
+    /// Convert error to a generic error kind
+    ///
+    /// By using this method, errors freely defined by HAL implementations
+    /// can be converted to a set of generic errors upon which generic
+    /// code can act.
+    fn kind(&self) -> ErrorKind;
+}
+
+impl Error for core::convert::Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+impl Error for ErrorKind {
+    fn kind(&self) -> ErrorKind {
+        *self
+    }
+}
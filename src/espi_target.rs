@@ -0,0 +1,159 @@
+//! eSPI target (slave) role, as found on BMCs attached to a host chipset.
+
+use crate::gpio::Level;
+
+pub use crate::error::{Error, ErrorKind};
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// eSPI channels.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Channel {
+    Peripheral,
+    VirtualWire,
+    OutOfBand,
+    FlashAccess,
+}
+
+/// A virtual wire.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum VirtualWire {
+    SlpS3,
+    SlpS4,
+    SlpS5,
+    SusStat,
+    PltRst,
+    OobRstWarn,
+    OobRstAck,
+    HostRstWarn,
+    HostRstAck,
+    SusWarn,
+    SusAck,
+    SlaveBootLoadDone,
+    SlaveBootLoadStatus,
+    /// Wire identified by its virtual wire index and bit position (0..=3).
+    Raw { index: u8, bit: u8 },
+}
+
+/// Events reported by an eSPI target.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum Event {
+    /// The host enabled a channel.
+    ChannelEnabled(Channel),
+    /// The host disabled a channel or reset the link.
+    ChannelDisabled(Channel),
+    /// A host-driven virtual wire changed level.
+    VirtualWireChanged { wire: VirtualWire, level: Level },
+    /// A peripheral channel request is pending.
+    PeripheralRequest,
+    /// An out-of-band message was received.
+    OobReceived,
+    /// A flash access request is pending.
+    FlashRequest,
+}
+
+/// eSPI target controller.
+pub trait EspiTarget: ErrorType {
+    /// Returns whether the host has enabled `channel` and it is ready.
+    fn is_channel_ready(&self, channel: Channel) -> bool;
+
+    /// Returns the next pending event, if any, without blocking.
+    fn poll_event(&mut self) -> Result<Option<Event>, Self::Error>;
+}
+
+/// Virtual wire channel.
+pub trait VirtualWires: ErrorType {
+    /// Returns the current level of `wire`.
+    fn get(&self, wire: VirtualWire) -> Result<Level, Self::Error>;
+
+    /// Drives a target-to-host `wire` to `level`.
+    ///
+    /// Fails with [`ErrorKind::NotPermitted`] for host-to-target wires.
+    fn set(&mut self, wire: VirtualWire, level: Level) -> Result<(), Self::Error>;
+}
+
+/// Kind of a peripheral channel cycle.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Cycle {
+    IoRead,
+    IoWrite,
+    MemoryRead,
+    MemoryWrite,
+}
+
+/// Peripheral channel request from the host.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct PeripheralRequest {
+    pub cycle: Cycle,
+    pub address: u64,
+    pub len: usize,
+}
+
+/// Peripheral channel, serving host I/O and memory cycles.
+///
+/// Each request returned by [`PeripheralChannel::poll_request`] must be
+/// finished with exactly one completion call before the next one is polled.
+pub trait PeripheralChannel: ErrorType {
+    /// Returns the pending request, if any, without blocking.
+    fn poll_request(&mut self) -> Result<Option<PeripheralRequest>, Self::Error>;
+
+    /// Copies the data of the pending write cycle into `buf`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes copied.
+    fn write_data(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error>;
+
+    /// Completes the pending read cycle with `data`.
+    fn complete_read(&mut self, data: &[u8]) -> Result<(), Self::Error>;
+
+    /// Completes the pending write cycle.
+    fn complete_write(&mut self) -> Result<(), Self::Error>;
+
+    /// Completes the pending request as unsuccessful.
+    fn complete_unsuccessful(&mut self) -> Result<(), Self::Error>;
+}
+
+/// Out-of-band channel, carrying SMBus-encapsulated packets such as MCTP.
+pub trait OobChannel: ErrorType {
+    /// Sends one packet to the host.
+    ///
+    /// Fails with [`ErrorKind::Busy`] while a previous packet is still queued.
+    fn send(&mut self, packet: &[u8]) -> Result<(), Self::Error>;
+
+    /// Copies the next received packet into `buf` without blocking.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the packet length, or `None` if no packet is pending.
+    fn receive(&mut self, buf: &mut [u8]) -> Result<Option<usize>, Self::Error>;
+}
+
+/// Serves flash access requests for [`FlashChannel::service_request`].
+///
+/// Returning an error completes the request as unsuccessful.
+pub trait FlashAccessHandler {
+    fn read(&mut self, address: u32, buf: &mut [u8]) -> Result<(), ErrorKind>;
+
+    fn write(&mut self, address: u32, data: &[u8]) -> Result<(), ErrorKind>;
+
+    fn erase(&mut self, address: u32, len: u32) -> Result<(), ErrorKind>;
+}
+
+/// Flash access channel, for target-attached flash shared with the host.
+pub trait FlashChannel: ErrorType {
+    /// Hands the pending flash request, if any, to `handler` and sends the completion.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing whether a request was served.
+    fn service_request(
+        &mut self,
+        handler: &mut impl FlashAccessHandler,
+    ) -> Result<bool, Self::Error>;
+}
@@ -0,0 +1,48 @@
+//! Bounded event queues between interrupt handlers and tasks.
+//!
+//! A driver's interrupt handler pushes events through an [`EventProducer`]
+//! and the task that owns the peripheral drains them through an
+//! [`EventConsumer`], instead of running application callbacks in interrupt
+//! context.
+
+use core::task::{Context, Poll};
+
+/// Interrupt-side half of an event queue.
+///
+/// Takes `&self` so the producer can be shared with an interrupt handler;
+/// implementations must be safe to call from interrupt context and never block.
+pub trait EventProducer<E> {
+    /// Enqueues `event`.
+    ///
+    /// # Returns
+    ///
+    /// `Err(event)` if the queue is full. A caller that then discards the
+    /// event reports it with [`EventProducer::record_dropped`].
+    fn try_send(&self, event: E) -> Result<(), E>;
+
+    /// Records an event that was discarded because the queue was full.
+    fn record_dropped(&self);
+}
+
+/// Task-side half of an event queue.
+pub trait EventConsumer<E> {
+    /// Dequeues the oldest event without blocking.
+    fn try_receive(&mut self) -> Option<E>;
+
+    /// Dequeues the oldest event, or registers the waker of `cx` to be woken
+    /// by the next [`EventProducer::try_send`].
+    fn poll_receive(&mut self, cx: &mut Context<'_>) -> Poll<E>;
+
+    /// Number of events currently queued.
+    fn len(&self) -> usize;
+
+    /// Maximum number of events the queue holds.
+    fn capacity(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns and resets the number of events dropped because the queue was full.
+    fn dropped(&mut self) -> usize;
+}
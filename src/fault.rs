@@ -0,0 +1,943 @@
+//! Fault injection for robustness testing.
+//!
+//! [`Faulty`] wraps a real implementation of a peripheral trait and makes
+//! selected operations fail or stall according to a list of [`Fault`]s, so
+//! driver error paths can be exercised without writing a mock per project.
+//! Failures surface as [`FaultError::Injected`] with the configured
+//! [`ErrorKind`], which generic code handles like any other error.
+
+use core::cell::{Cell, RefCell};
+
+use crate::error::{Error, ErrorKind};
+use crate::time::DelayNs;
+use crate::system_control::{self, clock, power, reset, watchdog};
+use crate::i3c_master::ccc;
+use crate::{
+    adc, block_device, can, debug_port, espi_target, flash_partition, gpio, hotplug, i2c_target, i3c_master,
+    i3c_target, kv_store, mailbox, monotonic_counter, otp, pwm, rtc, serial, smbus_target, spi_master, spi_target,
+    tach, thermal,
+};
+
+/// A failure to inject.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Fault {
+    /// Every `n`th operation fails with `kind`.
+    EveryNth { n: u32, kind: ErrorKind },
+    /// Operations touching an address in `start..end` fail with `kind`.
+    ///
+    /// Addresses are bytes for block devices, words for OTP, register indices
+    /// for mailboxes and bus addresses for I3C, including direct CCCs. Operations of other
+    /// peripherals have no address and never match.
+    AddressRange { start: usize, end: usize, kind: ErrorKind },
+    /// Every `n`th operation is delayed by `ns` nanoseconds before it runs.
+    Latency { n: u32, ns: u32 },
+}
+
+/// Delay used by a [`Faulty`] without a real delay source; latency faults have no effect.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct NoDelay;
+
+impl DelayNs for NoDelay {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
+
+/// Error returned by a [`Faulty`] wrapper.
+#[derive(Debug)]
+pub enum FaultError<E> {
+    /// The failure was injected.
+    Injected(ErrorKind),
+    /// The wrapped implementation failed.
+    Inner(E),
+}
+
+impl<E: Error> Error for FaultError<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            FaultError::Injected(kind) => *kind,
+            FaultError::Inner(e) => e.kind(),
+        }
+    }
+}
+
+/// A peripheral wrapped with fault injection.
+///
+/// Every operation that can fail counts as one operation, including ones
+/// that are rejected; queries such as sizes and capacities are forwarded
+/// unchanged.
+pub struct Faulty<'a, T, D = NoDelay> {
+    inner: T,
+    faults: &'a [Fault],
+    delay: RefCell<D>,
+    operations: Cell<u32>,
+    injected: Cell<u32>,
+}
+
+impl<'a, T> Faulty<'a, T> {
+    pub fn new(inner: T, faults: &'a [Fault]) -> Self {
+        Self::with_delay(inner, faults, NoDelay)
+    }
+}
+
+impl<'a, T, D: DelayNs> Faulty<'a, T, D> {
+    /// Wraps `inner`, using `delay` for latency faults.
+    pub fn with_delay(inner: T, faults: &'a [Fault], delay: D) -> Self {
+        Self {
+            inner,
+            faults,
+            delay: RefCell::new(delay),
+            operations: Cell::new(0),
+            injected: Cell::new(0),
+        }
+    }
+
+    /// Replaces the faults to inject and restarts the operation count.
+    pub fn set_faults(&mut self, faults: &'a [Fault]) {
+        self.faults = faults;
+        self.operations.set(0);
+    }
+
+    /// Number of operations seen so far.
+    pub fn operation_count(&self) -> u32 {
+        self.operations.get()
+    }
+
+    /// Number of failures injected so far.
+    pub fn injected_count(&self) -> u32 {
+        self.injected.get()
+    }
+
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Counts one operation, accessing `len` addresses from `addr` if
+    /// `access` is given, and decides whether it fails.
+    fn inject<E>(&self, access: Option<(usize, usize)>) -> Result<(), FaultError<E>> {
+        let count = self.operations.get().wrapping_add(1);
+        self.operations.set(count);
+        let mut failure = None;
+        for fault in self.faults {
+            match *fault {
+                Fault::Latency { n, ns } if n != 0 && count.is_multiple_of(n) => {
+                    self.delay.borrow_mut().delay_ns(ns);
+                }
+                Fault::EveryNth { n, kind } if n != 0 && count.is_multiple_of(n) => {
+                    failure = failure.or(Some(kind));
+                }
+                Fault::AddressRange { start, end, kind } => {
+                    let hit = access.is_some_and(|(addr, len)| {
+                        addr < end && addr.saturating_add(len.max(1)) > start
+                    });
+                    if hit {
+                        failure = failure.or(Some(kind));
+                    }
+                }
+                _ => {}
+            }
+        }
+        match failure {
+            Some(kind) => {
+                self.injected.set(self.injected.get() + 1);
+                Err(FaultError::Injected(kind))
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+macro_rules! fault_error_type {
+    ($($module:ident),*) => {$(
+        impl<T: $module::ErrorType, D> $module::ErrorType for Faulty<'_, T, D> {
+            type Error = FaultError<T::Error>;
+        }
+    )*};
+}
+
+fault_error_type!(
+    adc,
+    block_device,
+    can,
+    debug_port,
+    espi_target,
+    gpio,
+    hotplug,
+    i2c_target,
+    i3c_master,
+    i3c_target,
+    kv_store,
+    mailbox,
+    monotonic_counter,
+    otp,
+    pwm,
+    rtc,
+    serial,
+    spi_master,
+    spi_target,
+    system_control,
+    tach,
+    thermal
+);
+
+impl<T: block_device::BlockDevice, D: DelayNs> block_device::ReadBlockDevice for Faulty<'_, T, D> {
+    fn read_size(&self) -> usize {
+        self.inner.read_size()
+    }
+
+    fn read(&mut self, block_addr: usize, data: &mut [u8]) -> Result<(), Self::Error> {
+        self.inject(Some((block_addr, data.len())))?;
+        self.inner.read(block_addr, data).map_err(FaultError::Inner)
+    }
+
+    fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+}
+
+impl<T: block_device::BlockDevice, D: DelayNs> block_device::BlockDevice for Faulty<'_, T, D> {
+    fn erase_size(&self) -> usize {
+        self.inner.erase_size()
+    }
+
+    fn erase(&mut self, block_addr: usize, size_in_bytes: usize) -> Result<(), Self::Error> {
+        self.inject(Some((block_addr, size_in_bytes)))?;
+        self.inner.erase(block_addr, size_in_bytes).map_err(FaultError::Inner)
+    }
+
+    fn program_size(&self) -> usize {
+        self.inner.program_size()
+    }
+
+    fn program(&mut self, block_addr: usize, data: &[u8]) -> Result<(), Self::Error> {
+        self.inject(Some((block_addr, data.len())))?;
+        self.inner.program(block_addr, data).map_err(FaultError::Inner)
+    }
+}
+
+impl<T: otp::OtpMemory, D: DelayNs> otp::OtpMemory for Faulty<'_, T, D> {
+    fn read_word(&self, word_addr: usize) -> Result<u32, Self::Error> {
+        self.inject(Some((word_addr, 1)))?;
+        self.inner.read_word(word_addr).map_err(FaultError::Inner)
+    }
+
+    fn program_word(&mut self, word_addr: usize, value: u32) -> Result<(), Self::Error> {
+        self.inject(Some((word_addr, 1)))?;
+        self.inner.program_word(word_addr, value).map_err(FaultError::Inner)
+    }
+
+    fn capacity_words(&self) -> usize {
+        self.inner.capacity_words()
+    }
+}
+
+impl<T: mailbox::Mailbox, D: DelayNs> mailbox::Mailbox for Faulty<'_, T, D> {
+    const REGISTER_COUNT: usize = T::REGISTER_COUNT;
+
+    fn write_register(&mut self, index: usize, value: u32) -> Result<(), Self::Error> {
+        self.inject(Some((index, 1)))?;
+        self.inner.write_register(index, value).map_err(FaultError::Inner)
+    }
+
+    fn read_register(&mut self, index: usize) -> Result<u32, Self::Error> {
+        self.inject(Some((index, 1)))?;
+        self.inner.read_register(index).map_err(FaultError::Inner)
+    }
+
+    fn ring_doorbell(&mut self) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.ring_doorbell().map_err(FaultError::Inner)
+    }
+
+    fn is_acknowledged(&mut self) -> Result<bool, Self::Error> {
+        self.inject(None)?;
+        self.inner.is_acknowledged().map_err(FaultError::Inner)
+    }
+
+    fn wait_for_ack(&mut self) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.wait_for_ack().map_err(FaultError::Inner)
+    }
+
+    fn is_doorbell_pending(&mut self) -> Result<bool, Self::Error> {
+        self.inject(None)?;
+        self.inner.is_doorbell_pending().map_err(FaultError::Inner)
+    }
+
+    fn acknowledge(&mut self) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.acknowledge().map_err(FaultError::Inner)
+    }
+}
+
+impl<T: spi_master::SpiMaster, D: DelayNs> spi_master::SpiMaster for Faulty<'_, T, D> {
+    type ChipSelect = T::ChipSelect;
+
+    fn configure(&mut self, cs: Self::ChipSelect, config: &spi_master::Config) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.configure(cs, config).map_err(FaultError::Inner)
+    }
+
+    fn transaction(
+        &mut self,
+        cs: Self::ChipSelect,
+        operations: &mut [spi_master::Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.transaction(cs, operations).map_err(FaultError::Inner)
+    }
+}
+
+impl<T: i3c_master::I3cMaster, D: DelayNs> i3c_master::I3cMaster for Faulty<'_, T, D> {
+    fn write(&mut self, addr: u8, data: &[u8]) -> Result<(), Self::Error> {
+        self.inject(Some((addr.into(), 1)))?;
+        self.inner.write(addr, data).map_err(FaultError::Inner)
+    }
+
+    fn read(&mut self, addr: u8, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        self.inject(Some((addr.into(), 1)))?;
+        self.inner.read(addr, buf).map_err(FaultError::Inner)
+    }
+
+    fn write_read(&mut self, addr: u8, data: &[u8], buf: &mut [u8]) -> Result<usize, Self::Error> {
+        self.inject(Some((addr.into(), 1)))?;
+        self.inner.write_read(addr, data, buf).map_err(FaultError::Inner)
+    }
+}
+
+impl<T: ccc::I3cCcc, D: DelayNs> ccc::I3cCcc for Faulty<'_, T, D> {
+    fn ccc_write(&mut self, code: u8, addr: Option<u8>, payload: &[u8]) -> Result<(), Self::Error> {
+        self.inject(addr.map(|addr| (addr.into(), 1)))?;
+        self.inner.ccc_write(code, addr, payload).map_err(FaultError::Inner)
+    }
+
+    fn ccc_read(&mut self, code: u8, addr: u8, buf: &mut [u8]) -> Result<(), Self::Error> {
+        self.inject(Some((addr.into(), 1)))?;
+        self.inner.ccc_read(code, addr, buf).map_err(FaultError::Inner)
+    }
+}
+
+impl<T: i2c_target::I2cTarget, D: DelayNs> i2c_target::I2cTarget for Faulty<'_, T, D> {
+    fn set_address(&mut self, addr: u8) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.set_address(addr).map_err(FaultError::Inner)
+    }
+
+    fn enable(&mut self) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.enable().map_err(FaultError::Inner)
+    }
+
+    fn disable(&mut self) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.disable().map_err(FaultError::Inner)
+    }
+
+    fn poll_event(&mut self) -> Result<Option<i2c_target::Event>, Self::Error> {
+        self.inject(None)?;
+        self.inner.poll_event().map_err(FaultError::Inner)
+    }
+
+    fn read_received(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        self.inject(None)?;
+        self.inner.read_received(buf).map_err(FaultError::Inner)
+    }
+
+    fn set_response(&mut self, data: &[u8]) -> Result<usize, Self::Error> {
+        self.inject(None)?;
+        self.inner.set_response(data).map_err(FaultError::Inner)
+    }
+}
+
+impl<T: serial::SerialRead, D: DelayNs> serial::SerialRead for Faulty<'_, T, D> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        self.inject(None)?;
+        self.inner.read(buf).map_err(FaultError::Inner)
+    }
+}
+
+impl<T: serial::SerialWrite, D: DelayNs> serial::SerialWrite for Faulty<'_, T, D> {
+    fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.write(data).map_err(FaultError::Inner)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.flush().map_err(FaultError::Inner)
+    }
+
+    fn send_break(&mut self, bit_times: u32) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.send_break(bit_times).map_err(FaultError::Inner)
+    }
+}
+
+impl<T: gpio::InputPin, D: DelayNs> gpio::InputPin for Faulty<'_, T, D> {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        self.inject(None)?;
+        self.inner.is_high().map_err(FaultError::Inner)
+    }
+}
+
+impl<T: gpio::OutputPin, D: DelayNs> gpio::OutputPin for Faulty<'_, T, D> {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.set_low().map_err(FaultError::Inner)
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.set_high().map_err(FaultError::Inner)
+    }
+}
+
+impl<T: i3c_target::I3cTarget, D: DelayNs> i3c_target::I3cTarget for Faulty<'_, T, D> {
+    fn characteristics(&self) -> i3c_target::DeviceCharacteristics {
+        self.inner.characteristics()
+    }
+
+    fn dynamic_address(&self) -> Option<u8> {
+        self.inner.dynamic_address()
+    }
+
+    fn poll_event(&mut self) -> Result<Option<i3c_target::Event>, Self::Error> {
+        self.inject(None)?;
+        self.inner.poll_event().map_err(FaultError::Inner)
+    }
+
+    fn read_received(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        self.inject(None)?;
+        self.inner.read_received(buf).map_err(FaultError::Inner)
+    }
+
+    fn set_response(&mut self, data: &[u8]) -> Result<usize, Self::Error> {
+        self.inject(None)?;
+        self.inner.set_response(data).map_err(FaultError::Inner)
+    }
+}
+
+impl<T: spi_target::SpiTarget, D: DelayNs> spi_target::SpiTarget for Faulty<'_, T, D> {
+    fn configure(&mut self, mode: spi_target::Mode, bit_order: spi_target::BitOrder) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.configure(mode, bit_order).map_err(FaultError::Inner)
+    }
+
+    fn set_tx_data(&mut self, data: &[u8]) -> Result<usize, Self::Error> {
+        self.inject(None)?;
+        self.inner.set_tx_data(data).map_err(FaultError::Inner)
+    }
+
+    fn transfer(&mut self, rx: &mut [u8]) -> Result<usize, Self::Error> {
+        self.inject(None)?;
+        self.inner.transfer(rx).map_err(FaultError::Inner)
+    }
+
+    fn is_selected(&mut self) -> Result<bool, Self::Error> {
+        self.inject(None)?;
+        self.inner.is_selected().map_err(FaultError::Inner)
+    }
+
+    fn poll_event(&mut self) -> Result<Option<spi_target::Event>, Self::Error> {
+        self.inject(None)?;
+        self.inner.poll_event().map_err(FaultError::Inner)
+    }
+}
+
+impl<T: smbus_target::SmbusTarget, D: DelayNs> smbus_target::SmbusTarget for Faulty<'_, T, D> {
+    fn set_pec_enabled(&mut self, enabled: bool) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.set_pec_enabled(enabled).map_err(FaultError::Inner)
+    }
+
+    fn poll_command(&mut self) -> Result<Option<smbus_target::Command>, Self::Error> {
+        self.inject(None)?;
+        self.inner.poll_command().map_err(FaultError::Inner)
+    }
+
+    fn respond(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.respond(data).map_err(FaultError::Inner)
+    }
+
+    fn respond_block(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.respond_block(data).map_err(FaultError::Inner)
+    }
+
+    fn host_notify(&mut self, status: u16) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.host_notify(status).map_err(FaultError::Inner)
+    }
+}
+
+impl<T: espi_target::EspiTarget, D: DelayNs> espi_target::EspiTarget for Faulty<'_, T, D> {
+    fn is_channel_ready(&self, channel: espi_target::Channel) -> bool {
+        self.inner.is_channel_ready(channel)
+    }
+
+    fn poll_event(&mut self) -> Result<Option<espi_target::Event>, Self::Error> {
+        self.inject(None)?;
+        self.inner.poll_event().map_err(FaultError::Inner)
+    }
+}
+
+impl<T: adc::Adc, D: DelayNs> adc::Adc for Faulty<'_, T, D> {
+    type Channel = T::Channel;
+
+    fn resolution_bits(&self) -> u8 {
+        self.inner.resolution_bits()
+    }
+
+    fn reference_mv(&self) -> u32 {
+        self.inner.reference_mv()
+    }
+
+    fn read(&mut self, channel: Self::Channel) -> Result<u16, Self::Error> {
+        self.inject(None)?;
+        self.inner.read(channel).map_err(FaultError::Inner)
+    }
+}
+
+impl<T: pwm::PwmChannel, D: DelayNs> pwm::PwmChannel for Faulty<'_, T, D> {
+    fn enable(&mut self) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.enable().map_err(FaultError::Inner)
+    }
+
+    fn disable(&mut self) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.disable().map_err(FaultError::Inner)
+    }
+
+    fn set_duty_cycle(&mut self, duty: pwm::DutyCycle) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.set_duty_cycle(duty).map_err(FaultError::Inner)
+    }
+
+    fn duty_cycle(&self) -> pwm::DutyCycle {
+        self.inner.duty_cycle()
+    }
+
+    fn set_period_ns(&mut self, period_ns: u32) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.set_period_ns(period_ns).map_err(FaultError::Inner)
+    }
+
+    fn period_ns(&self) -> u32 {
+        self.inner.period_ns()
+    }
+}
+
+impl<T: tach::Tach, D: DelayNs> tach::Tach for Faulty<'_, T, D> {
+    fn set_pulses_per_revolution(&mut self, pulses: u8) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.set_pulses_per_revolution(pulses).map_err(FaultError::Inner)
+    }
+
+    fn rpm(&mut self) -> Result<u32, Self::Error> {
+        self.inject(None)?;
+        self.inner.rpm().map_err(FaultError::Inner)
+    }
+
+    fn set_stall_timeout_ms(&mut self, timeout_ms: u32) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.set_stall_timeout_ms(timeout_ms).map_err(FaultError::Inner)
+    }
+}
+
+impl<T: thermal::Thermal, D: DelayNs> thermal::Thermal for Faulty<'_, T, D> {
+    fn temperature(&mut self) -> Result<thermal::MilliCelsius, Self::Error> {
+        self.inject(None)?;
+        self.inner.temperature().map_err(FaultError::Inner)
+    }
+}
+
+impl<T: monotonic_counter::MonotonicCounter, D: DelayNs> monotonic_counter::MonotonicCounter for Faulty<'_, T, D> {
+    fn read(&self) -> Result<u64, Self::Error> {
+        self.inject(None)?;
+        self.inner.read().map_err(FaultError::Inner)
+    }
+
+    fn increment(&mut self) -> Result<u64, Self::Error> {
+        self.inject(None)?;
+        self.inner.increment().map_err(FaultError::Inner)
+    }
+
+    fn max_value(&self) -> u64 {
+        self.inner.max_value()
+    }
+
+    fn tamper_status(&self) -> Result<monotonic_counter::TamperStatus, Self::Error> {
+        self.inject(None)?;
+        self.inner.tamper_status().map_err(FaultError::Inner)
+    }
+}
+
+impl<T: kv_store::KvStore, D: DelayNs> kv_store::KvStore for Faulty<'_, T, D> {
+    const MAX_KEY_LEN: usize = T::MAX_KEY_LEN;
+    const MAX_VALUE_LEN: usize = T::MAX_VALUE_LEN;
+
+    fn get(&mut self, key: &[u8], buf: &mut [u8]) -> Result<kv_store::Record, Self::Error> {
+        self.inject(None)?;
+        self.inner.get(key, buf).map_err(FaultError::Inner)
+    }
+
+    fn put(&mut self, key: &[u8], value: &[u8]) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.put(key, value).map_err(FaultError::Inner)
+    }
+
+    fn delete(&mut self, key: &[u8]) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.delete(key).map_err(FaultError::Inner)
+    }
+}
+
+impl<T: rtc::Rtc, D: DelayNs> rtc::Rtc for Faulty<'_, T, D> {
+    fn status(&mut self) -> Result<rtc::RtcStatus, Self::Error> {
+        self.inject(None)?;
+        self.inner.status().map_err(FaultError::Inner)
+    }
+
+    fn datetime(&mut self) -> Result<rtc::DateTime, Self::Error> {
+        self.inject(None)?;
+        self.inner.datetime().map_err(FaultError::Inner)
+    }
+
+    fn set_datetime(&mut self, datetime: rtc::DateTime) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.set_datetime(datetime).map_err(FaultError::Inner)
+    }
+}
+
+impl<T: rtc::RtcAlarm, D: DelayNs> rtc::RtcAlarm for Faulty<'_, T, D> {
+    fn set_alarm(&mut self, datetime: rtc::DateTime) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.set_alarm(datetime).map_err(FaultError::Inner)
+    }
+
+    fn clear_alarm(&mut self) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.clear_alarm().map_err(FaultError::Inner)
+    }
+
+    fn take_alarm(&mut self) -> Result<bool, Self::Error> {
+        self.inject(None)?;
+        self.inner.take_alarm().map_err(FaultError::Inner)
+    }
+}
+
+impl<T: can::Can, D: DelayNs> can::Can for Faulty<'_, T, D> {
+    fn transmit(&mut self, frame: &can::Frame) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.transmit(frame).map_err(FaultError::Inner)
+    }
+
+    fn receive(&mut self) -> Result<Option<can::Frame>, Self::Error> {
+        self.inject(None)?;
+        self.inner.receive().map_err(FaultError::Inner)
+    }
+
+    fn bus_status(&mut self) -> Result<can::BusStatus, Self::Error> {
+        self.inject(None)?;
+        self.inner.bus_status().map_err(FaultError::Inner)
+    }
+
+    fn recover_from_bus_off(&mut self) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.recover_from_bus_off().map_err(FaultError::Inner)
+    }
+}
+
+impl<T: can::CanFilters, D: DelayNs> can::CanFilters for Faulty<'_, T, D> {
+    const FILTER_COUNT: usize = T::FILTER_COUNT;
+
+    fn set_filter(&mut self, index: usize, filter: can::Filter) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.set_filter(index, filter).map_err(FaultError::Inner)
+    }
+
+    fn clear_filter(&mut self, index: usize) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.clear_filter(index).map_err(FaultError::Inner)
+    }
+
+    fn accept_all(&mut self) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.accept_all().map_err(FaultError::Inner)
+    }
+}
+
+impl<T: debug_port::Jtag, D: DelayNs> debug_port::Jtag for Faulty<'_, T, D> {
+    fn reset(&mut self) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.reset().map_err(FaultError::Inner)
+    }
+
+    fn state(&self) -> debug_port::TapState {
+        self.inner.state()
+    }
+
+    fn move_to(&mut self, state: debug_port::TapState) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.move_to(state).map_err(FaultError::Inner)
+    }
+
+    fn run_test_idle(&mut self, cycles: u32) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.run_test_idle(cycles).map_err(FaultError::Inner)
+    }
+
+    fn scan_ir(
+        &mut self,
+        bits: usize,
+        tdi: &[u8],
+        tdo: &mut [u8],
+        end: debug_port::TapState,
+    ) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.scan_ir(bits, tdi, tdo, end).map_err(FaultError::Inner)
+    }
+
+    fn scan_dr(
+        &mut self,
+        bits: usize,
+        tdi: &[u8],
+        tdo: &mut [u8],
+        end: debug_port::TapState,
+    ) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.scan_dr(bits, tdi, tdo, end).map_err(FaultError::Inner)
+    }
+
+    fn set_tck_frequency(&mut self, hz: u32) -> Result<u32, Self::Error> {
+        self.inject(None)?;
+        self.inner.set_tck_frequency(hz).map_err(FaultError::Inner)
+    }
+}
+
+impl<T: debug_port::Swd, D: DelayNs> debug_port::Swd for Faulty<'_, T, D> {
+    fn line_reset(&mut self) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.line_reset().map_err(FaultError::Inner)
+    }
+
+    fn switch_from_jtag(&mut self) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.switch_from_jtag().map_err(FaultError::Inner)
+    }
+
+    fn read(&mut self, port: debug_port::SwdPort, address: u8) -> Result<u32, Self::Error> {
+        self.inject(None)?;
+        self.inner.read(port, address).map_err(FaultError::Inner)
+    }
+
+    fn write(&mut self, port: debug_port::SwdPort, address: u8, value: u32) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.write(port, address, value).map_err(FaultError::Inner)
+    }
+
+    fn set_swclk_frequency(&mut self, hz: u32) -> Result<u32, Self::Error> {
+        self.inject(None)?;
+        self.inner.set_swclk_frequency(hz).map_err(FaultError::Inner)
+    }
+}
+
+impl<T: hotplug::Hotplug, D: DelayNs> hotplug::Hotplug for Faulty<'_, T, D> {
+    type Slot = T::Slot;
+
+    fn presence(&mut self, slot: Self::Slot) -> Result<hotplug::Presence, Self::Error> {
+        self.inject(None)?;
+        self.inner.presence(slot).map_err(FaultError::Inner)
+    }
+
+    fn poll_hotplug_event(&mut self) -> Result<Option<hotplug::HotplugEvent<Self::Slot>>, Self::Error> {
+        self.inject(None)?;
+        self.inner.poll_hotplug_event().map_err(FaultError::Inner)
+    }
+
+    fn rescan(&mut self) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.rescan().map_err(FaultError::Inner)
+    }
+}
+
+impl<T: flash_partition::PartitionTable, D: DelayNs> flash_partition::PartitionTable for Faulty<'_, T, D> {
+    type PartitionId = T::PartitionId;
+
+    fn info(&self, id: Self::PartitionId) -> Result<flash_partition::PartitionInfo, Self::Error> {
+        self.inject(None)?;
+        self.inner.info(id).map_err(FaultError::Inner)
+    }
+
+    fn find(&self, name: &str) -> Option<Self::PartitionId> {
+        self.inner.find(name)
+    }
+}
+
+impl<T: clock::ClockGate, D: DelayNs> clock::ClockGate for Faulty<'_, T, D> {
+    type ClockId = T::ClockId;
+
+    fn enable(&mut self, clock: Self::ClockId) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.enable(clock).map_err(FaultError::Inner)
+    }
+
+    fn disable(&mut self, clock: Self::ClockId) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.disable(clock).map_err(FaultError::Inner)
+    }
+
+    fn is_enabled(&self, clock: Self::ClockId) -> Result<bool, Self::Error> {
+        self.inject(None)?;
+        self.inner.is_enabled(clock).map_err(FaultError::Inner)
+    }
+}
+
+impl<T: power::PowerControl, D: DelayNs> power::PowerControl for Faulty<'_, T, D> {
+    fn is_supported(&self, state: power::PowerState) -> bool {
+        self.inner.is_supported(state)
+    }
+
+    fn enter(&mut self, state: power::PowerState) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.enter(state).map_err(FaultError::Inner)
+    }
+}
+
+impl<T: reset::ResetCause, D: DelayNs> reset::ResetCause for Faulty<'_, T, D> {
+    fn reset_reason(&self) -> Result<reset::ResetReason, Self::Error> {
+        self.inject(None)?;
+        self.inner.reset_reason().map_err(FaultError::Inner)
+    }
+
+    fn clear_reset_reason(&mut self) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.clear_reset_reason().map_err(FaultError::Inner)
+    }
+}
+
+impl<T: watchdog::Watchdog, D: DelayNs> watchdog::Watchdog for Faulty<'_, T, D> {
+    type Timeout = T::Timeout;
+
+    fn start(&mut self, timeout: Self::Timeout) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.start(timeout).map_err(FaultError::Inner)
+    }
+
+    fn feed(&mut self) -> Result<(), Self::Error> {
+        self.inject(None)?;
+        self.inner.feed().map_err(FaultError::Inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block_device::{BlockDevice, ReadBlockDevice};
+    use crate::mock::block_device::RamBlockDevice;
+
+    /// Sums the requested delays instead of waiting.
+    #[derive(Default)]
+    struct TotalDelay(u64);
+
+    impl DelayNs for TotalDelay {
+        fn delay_ns(&mut self, ns: u32) {
+            self.0 += u64::from(ns);
+        }
+    }
+
+    fn device() -> RamBlockDevice<64> {
+        RamBlockDevice::new(16, 4).unwrap()
+    }
+
+    #[test]
+    fn every_nth_fails_and_counts() {
+        let faults = [Fault::EveryNth {
+            n: 3,
+            kind: ErrorKind::Timeout,
+        }];
+        let mut faulty = Faulty::new(device(), &faults);
+        let mut buf = [0u8; 4];
+        let results: [bool; 6] = core::array::from_fn(|_| faulty.read(0, &mut buf).is_ok());
+        assert_eq!(results, [true, true, false, true, true, false]);
+        assert_eq!((faulty.operation_count(), faulty.injected_count()), (6, 2));
+        assert!(matches!(faulty.read(0, &mut buf), Ok(())));
+
+        faulty.set_faults(&[]);
+        assert_eq!(faulty.operation_count(), 0);
+        assert!(faulty.read(0, &mut buf).is_ok());
+    }
+
+    #[test]
+    fn injected_failure_has_the_configured_kind_and_skips_the_device() {
+        let faults = [Fault::EveryNth {
+            n: 1,
+            kind: ErrorKind::ProgramError,
+        }];
+        let mut faulty = Faulty::new(device(), &faults);
+        let result = faulty.program(0, &[0; 4]);
+        assert!(matches!(result, Err(FaultError::Injected(ErrorKind::ProgramError))));
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::ProgramError);
+        assert_eq!(faulty.inner().as_bytes()[..4], [0xFF; 4]);
+    }
+
+    #[test]
+    fn address_range_matches_overlapping_accesses() {
+        let faults = [Fault::AddressRange {
+            start: 16,
+            end: 32,
+            kind: ErrorKind::OutOfBounds,
+        }];
+        let mut faulty = Faulty::new(device(), &faults);
+        let mut buf = [0u8; 4];
+        assert!(faulty.read(12, &mut buf).is_ok());
+        assert!(faulty.read(14, &mut buf).is_err());
+        assert!(faulty.read(16, &mut buf).is_err());
+        assert!(faulty.read(31, &mut buf[..1]).is_err());
+        assert!(faulty.read(32, &mut buf).is_ok());
+        // An empty access still touches its start address.
+        assert!(faulty.read(20, &mut []).is_err());
+        assert!(faulty.erase(0, 48).is_err());
+        assert!(faulty.erase(32, 16).is_ok());
+        assert_eq!(faulty.injected_count(), 5);
+    }
+
+    #[test]
+    fn device_errors_are_passed_through() {
+        let mut faulty = Faulty::new(device(), &[]);
+        let result = faulty.erase(1, 16);
+        assert!(matches!(result, Err(FaultError::Inner(ErrorKind::InvalidParameter))));
+        assert_eq!(faulty.injected_count(), 0);
+    }
+
+    #[test]
+    fn latency_delays_without_failing() {
+        let faults = [Fault::Latency { n: 2, ns: 500 }, Fault::Latency { n: 0, ns: 1 }];
+        let mut faulty = Faulty::with_delay(device(), &faults, TotalDelay::default());
+        let mut buf = [0u8; 4];
+        for _ in 0..5 {
+            faulty.read(0, &mut buf).unwrap();
+        }
+        assert_eq!(faulty.delay.borrow().0, 1000);
+        assert_eq!(faulty.injected_count(), 0);
+    }
+
+    #[test]
+    fn queries_are_not_counted() {
+        let faults = [Fault::EveryNth {
+            n: 1,
+            kind: ErrorKind::Other,
+        }];
+        let faulty = Faulty::new(device(), &faults);
+        assert_eq!((faulty.capacity(), faulty.erase_size(), faulty.program_size()), (64, 16, 4));
+        assert_eq!(faulty.operation_count(), 0);
+    }
+}
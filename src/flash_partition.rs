@@ -0,0 +1,143 @@
+//! Partitions over a [`BlockDevice`] and A/B image staging.
+
+use crate::block_device::{BlockDevice, ErrorType};
+
+/// Location and attributes of a partition.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct PartitionInfo {
+    /// Offset of the partition on the underlying device, in bytes.
+    pub offset: usize,
+    /// Size of the partition in bytes.
+    pub size: usize,
+    /// Erase granularity of the underlying device, in bytes.
+    pub erase_size: usize,
+    /// Whether the partition is currently write-protected.
+    pub write_protected: bool,
+}
+
+impl PartitionInfo {
+    /// Returns whether `len` bytes at partition-relative `offset` lie inside the partition.
+    pub fn contains(&self, offset: usize, len: usize) -> bool {
+        offset
+            .checked_add(len)
+            .is_some_and(|end| end <= self.size)
+    }
+
+    /// Returns whether partition-relative `offset` and `len` are multiples of the erase size.
+    pub fn is_erase_aligned(&self, offset: usize, len: usize) -> bool {
+        self.erase_size != 0
+            && self
+                .offset
+                .checked_add(offset)
+                .is_some_and(|addr| addr.is_multiple_of(self.erase_size))
+            && len.is_multiple_of(self.erase_size)
+    }
+}
+
+/// Lookup of partitions by index or name.
+pub trait PartitionTable: ErrorType {
+    /// Identifies a partition, typically an index or a platform-specific enum.
+    type PartitionId: Copy;
+
+    /// Returns the layout of `id`.
+    fn info(&self, id: Self::PartitionId) -> Result<PartitionInfo, Self::Error>;
+
+    /// Resolves a partition name, e.g. `"fw_a"`.
+    fn find(&self, name: &str) -> Option<Self::PartitionId>;
+}
+
+/// A device split into partitions that can be opened as block devices.
+///
+/// Addresses passed to an opened partition are relative to its start, and
+/// accesses outside it fail with `ErrorKind::OutOfBounds`.
+pub trait PartitionedDevice: PartitionTable {
+    type Partition<'a>: BlockDevice<Error = Self::Error>
+    where
+        Self: 'a;
+
+    /// Opens partition `id`.
+    fn open(&mut self, id: Self::PartitionId) -> Result<Self::Partition<'_>, Self::Error>;
+
+    /// Enables or disables write protection of `id`.
+    ///
+    /// Writes and erases on a protected partition fail with `ErrorKind::Locked`.
+    fn set_write_protect(&mut self, id: Self::PartitionId, enabled: bool) -> Result<(), Self::Error>;
+}
+
+/// One of two firmware image slots.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Slot {
+    A,
+    B,
+}
+
+impl Slot {
+    /// Returns the other slot.
+    pub fn other(self) -> Self {
+        match self {
+            Slot::A => Slot::B,
+            Slot::B => Slot::A,
+        }
+    }
+}
+
+/// A/B image staging.
+///
+/// A new image is written to the inactive slot, marked bootable once it has
+/// been verified, and marked active to be used on the next boot. If the new
+/// image fails to confirm itself, [`AbImage::rollback`] returns to the previous slot.
+pub trait AbImage: PartitionTable {
+    /// Returns the partition holding `slot`.
+    fn slot_partition(&self, slot: Slot) -> Self::PartitionId;
+
+    /// Returns the slot booted from.
+    fn active_slot(&self) -> Result<Slot, Self::Error>;
+
+    /// Returns the slot a new image should be staged in.
+    fn staging_slot(&self) -> Result<Slot, Self::Error> {
+        Ok(self.active_slot()?.other())
+    }
+
+    /// Records that `slot` holds a verified image.
+    fn mark_bootable(&mut self, slot: Slot) -> Result<(), Self::Error>;
+
+    /// Returns whether `slot` has been marked bootable.
+    fn is_bootable(&self, slot: Slot) -> Result<bool, Self::Error>;
+
+    /// Selects `slot` for the next boot. `slot` must be bootable.
+    fn mark_active(&mut self, slot: Slot) -> Result<(), Self::Error>;
+
+    /// Reverts to the slot that was active before the last `mark_active`.
+    fn rollback(&mut self) -> Result<(), Self::Error>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INFO: PartitionInfo = PartitionInfo {
+        offset: 0x1000,
+        size: 0x2000,
+        erase_size: 0x1000,
+        write_protected: false,
+    };
+
+    #[test]
+    fn erase_alignment() {
+        assert!(INFO.is_erase_aligned(0, 0x1000));
+        assert!(INFO.is_erase_aligned(0x1000, 0x1000));
+        assert!(!INFO.is_erase_aligned(0x800, 0x1000));
+        assert!(!INFO.is_erase_aligned(0, 0x800));
+        assert!(!INFO.is_erase_aligned(usize::MAX, 0));
+        let unknown = PartitionInfo { erase_size: 0, ..INFO };
+        assert!(!unknown.is_erase_aligned(0, 0));
+    }
+
+    #[test]
+    fn containment() {
+        assert!(INFO.contains(0, 0x2000));
+        assert!(INFO.contains(0x1FFF, 1));
+        assert!(!INFO.contains(0x1FFF, 2));
+        assert!(!INFO.contains(usize::MAX, 1));
+    }
+}
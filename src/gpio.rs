@@ -0,0 +1,136 @@
+use core::fmt::Debug;
+
+pub use crate::error::{Error, ErrorKind};
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// Logic level of a pin.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Level {
+    Low,
+    High,
+}
+
+/// Condition that raises a pin interrupt.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Trigger {
+    RisingEdge,
+    FallingEdge,
+    AnyEdge,
+    LevelLow,
+    LevelHigh,
+}
+
+/// Pin configured as an input.
+pub trait InputPin: ErrorType {
+    fn is_high(&mut self) -> Result<bool, Self::Error>;
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.is_high()?)
+    }
+}
+
+/// Push-pull output pin.
+pub trait OutputPin: ErrorType {
+    fn set_low(&mut self) -> Result<(), Self::Error>;
+
+    fn set_high(&mut self) -> Result<(), Self::Error>;
+
+    fn set_level(&mut self, level: Level) -> Result<(), Self::Error> {
+        match level {
+            Level::Low => self.set_low(),
+            Level::High => self.set_high(),
+        }
+    }
+}
+
+/// Open-drain pin, e.g. a shared reset line.
+///
+/// Releasing the pin lets an external pull-up or another driver set the
+/// level, which can be read back through [`InputPin`].
+pub trait OpenDrainPin: InputPin {
+    /// Actively drives the line low.
+    fn drive_low(&mut self) -> Result<(), Self::Error>;
+
+    /// Stops driving the line.
+    fn release(&mut self) -> Result<(), Self::Error>;
+}
+
+/// Interrupt configuration of an input pin.
+pub trait InterruptPin: InputPin {
+    /// Selects the interrupt condition and enables the interrupt.
+    fn enable_interrupt(&mut self, trigger: Trigger) -> Result<(), Self::Error>;
+
+    fn disable_interrupt(&mut self) -> Result<(), Self::Error>;
+
+    /// Returns whether the interrupt condition has occurred since the last clear.
+    fn is_interrupt_pending(&mut self) -> Result<bool, Self::Error>;
+
+    fn clear_interrupt(&mut self) -> Result<(), Self::Error>;
+}
+
+/// Blocking wait for a pin event.
+pub trait Wait: InputPin {
+    /// Blocks until `trigger` occurs on the pin.
+    ///
+    /// Level triggers return immediately if the pin is already at that level.
+    fn wait_for(&mut self, trigger: Trigger) -> Result<(), Self::Error>;
+}
+
+/// Several pins read and written together, bit `n` of each mask being pin `n`.
+///
+/// Suits port-wide GPIO registers and I2C or SPI GPIO expanders. Each call
+/// is atomic with respect to the pins it touches: all of them are sampled or
+/// changed in a single register access or bus transaction, so a parallel bus
+/// never shows a half-written value and a presence-detect scan sees one
+/// consistent snapshot.
+pub trait PinGroup: ErrorType {
+    /// Number of pins in the group, at most 32. Bits at or above this are ignored.
+    const WIDTH: u32;
+
+    /// Returns the level of every pin, set bits being high.
+    ///
+    /// Output pins read back the level on the line.
+    fn read(&mut self) -> Result<u32, Self::Error>;
+
+    /// Drives the output pins selected by `mask` to the levels in `values`.
+    ///
+    /// Pins outside `mask`, and pins configured as inputs, are left unchanged.
+    fn write(&mut self, mask: u32, values: u32) -> Result<(), Self::Error>;
+
+    /// Returns the output latch: the levels last written to each pin.
+    fn output_levels(&mut self) -> Result<u32, Self::Error>;
+
+    /// Configures the pins selected by `mask` as outputs where `outputs` has
+    /// bits set and as inputs where it has them clear.
+    ///
+    /// A pin switched to output drives the level already in its output latch,
+    /// so writing the latch first avoids a glitch.
+    fn set_direction(&mut self, mask: u32, outputs: u32) -> Result<(), Self::Error>;
+
+    /// Returns the direction of every pin, set bits being outputs.
+    fn direction(&mut self) -> Result<u32, Self::Error>;
+
+    /// Drives the pins in `mask` high.
+    fn set_high(&mut self, mask: u32) -> Result<(), Self::Error> {
+        self.write(mask, mask)
+    }
+
+    /// Drives the pins in `mask` low.
+    fn set_low(&mut self, mask: u32) -> Result<(), Self::Error> {
+        self.write(mask, 0)
+    }
+
+    /// Inverts the output latch of the pins in `mask`.
+    ///
+    /// Implementations with a hardware toggle register should override this;
+    /// the default reads the latch first and is only atomic if nothing else
+    /// writes the group in between.
+    fn toggle(&mut self, mask: u32) -> Result<(), Self::Error> {
+        let levels = self.output_levels()?;
+        self.write(mask, !levels)
+    }
+}
@@ -0,0 +1,91 @@
+//! Devices that appear and disappear at runtime.
+//!
+//! Covers removable block devices behind a card-detect line, I3C targets
+//! joining with a hot-join request, and presence-detect pins of FRU slots.
+//! A [`Hotplug`] source reports insertion and removal per slot, either
+//! polled or pushed into a [`crate::event_queue`] queue, so the layer that
+//! binds drivers can react without polling each device.
+
+use crate::event_queue::EventProducer;
+
+pub use crate::error::{Error, ErrorKind};
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// Whether a device occupies a slot.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Presence {
+    Present,
+    Absent,
+}
+
+/// Change of presence in a slot.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum HotplugEvent<S> {
+    /// A device appeared and can be bound.
+    Inserted(S),
+    /// The device went away; its driver must stop using it.
+    Removed(S),
+}
+
+impl<S: Copy> HotplugEvent<S> {
+    pub fn slot(&self) -> S {
+        match *self {
+            HotplugEvent::Inserted(slot) | HotplugEvent::Removed(slot) => slot,
+        }
+    }
+}
+
+/// Source of presence changes.
+///
+/// Events are reported after debouncing. A device removed and reinserted
+/// faster than it is observed yields `Removed` followed by `Inserted`, never
+/// a lone `Inserted`, so a driver bound to the old device is always unbound
+/// first.
+pub trait Hotplug: ErrorType {
+    /// Identifies a slot: a card slot index, or for an I3C controller the
+    /// dynamic address assigned to a device that joined.
+    type Slot: Copy + Eq;
+
+    /// Returns the current presence in `slot`.
+    fn presence(&mut self, slot: Self::Slot) -> Result<Presence, Self::Error>;
+
+    /// Returns the oldest unreported presence change, without blocking.
+    fn poll_hotplug_event(&mut self) -> Result<Option<HotplugEvent<Self::Slot>>, Self::Error>;
+
+    /// Compares every slot with its last reported presence and queues events
+    /// for the differences.
+    ///
+    /// Call after events were lost, e.g. because an event queue overflowed,
+    /// to bring the consumer back in sync.
+    fn rescan(&mut self) -> Result<(), Self::Error>;
+
+    /// Moves every pending event into `queue`.
+    ///
+    /// Events that do not fit are dropped and recorded with
+    /// [`EventProducer::record_dropped`]; follow up with [`Hotplug::rescan`].
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of events forwarded.
+    fn forward_events(&mut self, queue: &impl EventProducer<HotplugEvent<Self::Slot>>) -> Result<usize, Self::Error> {
+        let mut forwarded = 0;
+        while let Some(event) = self.poll_hotplug_event()? {
+            match queue.try_send(event) {
+                Ok(()) => forwarded += 1,
+                Err(_) => queue.record_dropped(),
+            }
+        }
+        Ok(forwarded)
+    }
+}
+
+/// A [`Hotplug`] source that pushes events from its interrupt handler.
+pub trait HotplugNotify: Hotplug {
+    /// Routes events to `queue` as they occur, instead of holding them for
+    /// [`Hotplug::poll_hotplug_event`]. `None` returns to polling.
+    fn set_event_queue(&mut self, queue: Option<&'static dyn EventProducer<HotplugEvent<Self::Slot>>>);
+}
@@ -0,0 +1,219 @@
+use core::fmt::Debug;
+
+use crate::time::{Deadline, Monotonic};
+
+pub use crate::error::{Error, ErrorKind};
+pub use crate::rx_grant::RxGrant;
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// Bus events observed by an I2C target.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum Event {
+    /// The controller addressed the target for a write.
+    WriteRequest,
+    /// The controller addressed the target for a read; staged data is sent.
+    ReadRequest,
+    /// The controller finished writing `len` bytes, ended by a STOP or repeated START.
+    WriteComplete { len: usize },
+    /// The controller finished a read after clocking out `sent` staged bytes,
+    /// ended by a STOP or repeated START.
+    ReadComplete { sent: usize },
+    /// The controller read with nothing staged while clock stretching was
+    /// disabled, and was sent filler bytes. Reported before the
+    /// [`Event::ReadComplete`] of the same read.
+    Underrun,
+    /// A STOP condition ended the transaction.
+    Stop,
+    /// The transaction was abandoned because of a bus fault.
+    BusError(BusFault),
+}
+
+/// Bus faults reported as [`Event::BusError`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum BusFault {
+    /// The target held SCL longer than the limit set with [`ClockStretch::set_stretch_limit`]
+    /// and released it.
+    StretchTimeout,
+    /// SDA stayed low longer than the stuck-bus timeout.
+    SdaStuckLow,
+    /// SCL stayed low longer than the stuck-bus timeout.
+    SclStuckLow,
+    /// A misplaced START or STOP condition.
+    Protocol,
+}
+
+/// I2C target (peripheral) role.
+///
+/// Drivers should also implement [`crate::system_control::power::PowerManaged`].
+pub trait I2cTarget: ErrorType {
+    /// Sets the 7-bit address the target responds to.
+    fn set_address(&mut self, addr: u8) -> Result<(), Self::Error>;
+
+    /// Starts responding on the bus.
+    fn enable(&mut self) -> Result<(), Self::Error>;
+
+    /// Stops responding on the bus.
+    fn disable(&mut self) -> Result<(), Self::Error>;
+
+    /// Returns the next pending bus event, if any, without blocking.
+    fn poll_event(&mut self) -> Result<Option<Event>, Self::Error>;
+
+    /// Copies the data of the last completed write into `buf`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes copied.
+    fn read_received(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error>;
+
+    /// Stages data for the next controller read.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes accepted.
+    fn set_response(&mut self, data: &[u8]) -> Result<usize, Self::Error>;
+
+    /// Polls for the next bus event until `deadline` passes.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the event, or `None` if the deadline passed first.
+    fn wait_event_with_deadline(
+        &mut self,
+        clock: &impl Monotonic,
+        deadline: Deadline,
+    ) -> Result<Option<Event>, Self::Error> {
+        loop {
+            if let Some(event) = self.poll_event()? {
+                return Ok(Some(event));
+            }
+            if deadline.has_expired(clock) {
+                return Ok(None);
+            }
+        }
+    }
+}
+
+/// Fixed limits of an I2C target.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Capabilities {
+    /// Highest bus clock the target keeps up with, in Hz (e.g. 1 MHz for Fast-mode Plus).
+    pub max_clock_hz: u32,
+    /// Longest write the target buffers, and longest response it can stage, in bytes.
+    pub max_transfer_len: usize,
+    pub ten_bit_addressing: bool,
+    /// Whether the target can respond to several addresses at once.
+    pub multiple_addresses: bool,
+}
+
+/// Capability discovery at compile time.
+pub trait I2cTargetCapabilities: I2cTarget {
+    const CAPABILITIES: Capabilities;
+}
+
+/// Clock stretching control.
+///
+/// A target stretches the clock by holding SCL low while its response is
+/// not ready. Without a limit, a stalled target blocks the whole bus.
+pub trait ClockStretch: I2cTarget {
+    /// Allows or forbids clock stretching. Without it, a read with nothing
+    /// staged is answered with filler bytes and reported as [`Event::Underrun`].
+    fn set_clock_stretching(&mut self, enabled: bool) -> Result<(), Self::Error>;
+
+    /// Sets the longest time, in microseconds, the target may hold SCL, or
+    /// `None` for no limit.
+    ///
+    /// When the limit is hit the target releases SCL, abandons the transfer
+    /// and reports [`BusFault::StretchTimeout`].
+    fn set_stretch_limit(&mut self, limit_us: Option<u32>) -> Result<(), Self::Error>;
+}
+
+/// Level of the bus lines as sampled by the target.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct BusLines {
+    pub scl_high: bool,
+    pub sda_high: bool,
+}
+
+/// Detection of and recovery from a stuck bus.
+pub trait BusRecovery: I2cTarget {
+    /// Sets how long, in microseconds, SCL or SDA may stay low before the
+    /// target reports [`BusFault::SclStuckLow`] or [`BusFault::SdaStuckLow`],
+    /// or `None` to disable detection.
+    fn set_stuck_timeout(&mut self, timeout_us: Option<u32>) -> Result<(), Self::Error>;
+
+    /// Samples the bus lines.
+    fn bus_lines(&mut self) -> Result<BusLines, Self::Error>;
+
+    /// Releases SDA and SCL and resets the target state machine, abandoning
+    /// any transfer in progress, e.g. after the target was left driving SDA
+    /// low by a controller reset in the middle of a read.
+    fn release_bus(&mut self) -> Result<(), Self::Error>;
+}
+
+/// Zero-copy receive path.
+///
+/// Alternative to [`I2cTarget::read_received`] that lends the received data
+/// instead of copying it into a caller buffer.
+pub trait ZeroCopyReceive: I2cTarget {
+    type RxGrant<'a>: RxGrant
+    where
+        Self: 'a;
+
+    /// Lends the data of the oldest completed write that has not been received yet.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the grant, or `None` if no data is pending.
+    fn receive_grant(&mut self) -> Result<Option<Self::RxGrant<'_>>, Self::Error>;
+}
+
+/// Address a target responds to.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Address {
+    /// 7-bit address, `0x00..=0x7F`.
+    SevenBit(u8),
+    /// 10-bit address, `0x000..=0x3FF`.
+    TenBit(u16),
+}
+
+/// An [`Event`] together with the address the controller addressed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct AddressedEvent {
+    pub address: Address,
+    pub event: Event,
+}
+
+/// Responding on several addresses at once, e.g. a fixed address and one
+/// assigned through SMBus ARP.
+///
+/// The single address of [`I2cTarget::set_address`] is one entry of the set.
+pub trait MultiAddress: I2cTarget {
+    /// Maximum number of addresses the hardware matches simultaneously.
+    const MAX_ADDRESSES: usize;
+
+    /// Adds `address` to the set of matched addresses.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success, or an error of kind [`ErrorKind::InvalidAddress`]
+    /// if the address is out of range, or [`ErrorKind::InUse`] if the set is full.
+    fn add_address(&mut self, address: Address) -> Result<(), Self::Error>;
+
+    /// Stops matching `address`.
+    fn remove_address(&mut self, address: Address) -> Result<(), Self::Error>;
+
+    /// Like [`I2cTarget::poll_event`], but reports which address matched.
+    fn poll_addressed_event(&mut self) -> Result<Option<AddressedEvent>, Self::Error>;
+}
+
+/// 10-bit addressing.
+pub trait TenBitAddress: I2cTarget {
+    /// Sets the 10-bit address the target responds to, replacing the 7-bit one.
+    fn set_ten_bit_address(&mut self, addr: u16) -> Result<(), Self::Error>;
+}
@@ -0,0 +1,155 @@
+pub mod ccc;
+pub mod daa;
+pub mod handoff;
+
+pub use crate::error::{Error, ErrorKind};
+use crate::time::{Deadline, Monotonic};
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// I3C controller private transfers.
+///
+/// Addresses are 7-bit dynamic addresses assigned during bus initialization.
+///
+/// Drivers should also implement [`crate::system_control::power::PowerManaged`].
+pub trait I3cMaster: ErrorType {
+    /// Private write to `addr`.
+    fn write(&mut self, addr: u8, data: &[u8]) -> Result<(), Self::Error>;
+
+    /// Private read from `addr`. The target may end the read early.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes read.
+    fn read(&mut self, addr: u8, buf: &mut [u8]) -> Result<usize, Self::Error>;
+
+    /// Private write followed by a read, separated by a repeated start.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes read.
+    fn write_read(&mut self, addr: u8, data: &[u8], buf: &mut [u8]) -> Result<usize, Self::Error>;
+}
+
+/// Private transfers bounded by a [`Deadline`].
+///
+/// Each method behaves like its [`I3cMaster`] counterpart, but aborts the
+/// transfer and fails with [`ErrorKind::Timeout`] once `deadline` passes.
+pub trait I3cMasterDeadline: I3cMaster {
+    fn write_with_deadline(
+        &mut self,
+        addr: u8,
+        data: &[u8],
+        clock: &impl Monotonic,
+        deadline: Deadline,
+    ) -> Result<(), Self::Error>;
+
+    fn read_with_deadline(
+        &mut self,
+        addr: u8,
+        buf: &mut [u8],
+        clock: &impl Monotonic,
+        deadline: Deadline,
+    ) -> Result<usize, Self::Error>;
+
+    fn write_read_with_deadline(
+        &mut self,
+        addr: u8,
+        data: &[u8],
+        buf: &mut [u8],
+        clock: &impl Monotonic,
+        deadline: Deadline,
+    ) -> Result<usize, Self::Error>;
+}
+
+/// An in-band interrupt received from a target.
+pub trait IbiDescriptor {
+    /// Dynamic address of the target that raised the interrupt.
+    fn address(&self) -> u8;
+
+    /// Mandatory data byte, present when the target's BCR advertises an IBI payload.
+    fn mandatory_byte(&self) -> Option<u8>;
+
+    /// Additional payload bytes following the mandatory byte.
+    fn payload(&self) -> &[u8];
+}
+
+/// Receives in-band interrupts from [`I3cIbi::dispatch_ibis`].
+pub trait IbiHandler<I: IbiDescriptor> {
+    fn on_ibi(&mut self, ibi: &I);
+}
+
+/// In-band interrupt handling.
+pub trait I3cIbi: ErrorType {
+    /// Descriptor describing a received interrupt.
+    type Ibi: IbiDescriptor;
+
+    /// Accepts IBIs from `addr`, sending ENEC to the target if required.
+    fn enable_ibi(&mut self, addr: u8) -> Result<(), Self::Error>;
+
+    /// NACKs further IBIs from `addr`, sending DISEC to the target if required.
+    fn disable_ibi(&mut self, addr: u8) -> Result<(), Self::Error>;
+
+    /// Takes the oldest pending IBI without blocking.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the descriptor, or `None` if no IBI is pending.
+    fn poll_ibi(&mut self) -> Result<Option<Self::Ibi>, Self::Error>;
+
+    /// Hands every pending IBI to `handler`.
+    fn dispatch_ibis(&mut self, handler: &mut impl IbiHandler<Self::Ibi>) -> Result<(), Self::Error> {
+        while let Some(ibi) = self.poll_ibi()? {
+            handler.on_ibi(&ibi);
+        }
+        Ok(())
+    }
+}
+
+/// HDR modes a controller supports. Values combine with `|`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct HdrModes(pub u8);
+
+impl HdrModes {
+    pub const NONE: Self = Self(0);
+    /// HDR Double Data Rate.
+    pub const DDR: Self = Self(1 << 0);
+    /// HDR Ternary Symbol Pure-bus.
+    pub const TSP: Self = Self(1 << 1);
+    /// HDR Ternary Symbol Legacy-inclusive-bus.
+    pub const TSL: Self = Self(1 << 2);
+    /// HDR Bulk Transport.
+    pub const BT: Self = Self(1 << 3);
+
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl core::ops::BitOr for HdrModes {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// Fixed limits of an I3C controller.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Capabilities {
+    /// Longest private transfer in one call, in bytes.
+    pub max_transfer_len: usize,
+    /// Highest SDR clock, in Hz (12.5 MHz for a full-speed controller).
+    pub max_sdr_hz: u32,
+    pub hdr_modes: HdrModes,
+    /// Longest IBI payload the controller can receive, in bytes.
+    pub max_ibi_payload_len: usize,
+}
+
+/// Capability discovery at compile time.
+pub trait I3cMasterCapabilities: I3cMaster {
+    const CAPABILITIES: Capabilities;
+}
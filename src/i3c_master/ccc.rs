@@ -0,0 +1,440 @@
+//! Typed common command codes (CCCs).
+//!
+//! The command enums only admit valid combinations, e.g. SETDASA exists only
+//! as a direct command, and encode their own code and payload, so neither
+//! implementations nor users frame CCCs by hand.
+
+use super::ErrorType;
+
+// Broadcast codes; the direct variants of ENEC/DISEC/SETMWL/SETMRL add `DIRECT`.
+const ENEC: u8 = 0x00;
+const DISEC: u8 = 0x01;
+const RSTDAA: u8 = 0x06;
+const SETMWL: u8 = 0x09;
+const SETMRL: u8 = 0x0A;
+const SETAASA: u8 = 0x29;
+
+// Bit set in every direct code.
+const DIRECT: u8 = 0x80;
+
+const SETDASA: u8 = 0x87;
+const SETNEWDA: u8 = 0x88;
+const GETMWL: u8 = 0x8B;
+const GETMRL: u8 = 0x8C;
+const GETPID: u8 = 0x8D;
+const GETBCR: u8 = 0x8E;
+const GETDCR: u8 = 0x8F;
+const GETSTATUS: u8 = 0x90;
+const GETMXDS: u8 = 0x94;
+
+/// Largest payload of a write CCC, in bytes.
+pub const MAX_PAYLOAD: usize = 3;
+
+/// Target events enabled by ENEC or disabled by DISEC.
+///
+/// Values combine with `|`, e.g. `Events::INTERRUPT | Events::HOT_JOIN`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Events(pub u8);
+
+impl Events {
+    /// In-band interrupts.
+    pub const INTERRUPT: Self = Self(1 << 0);
+    /// Controller role requests.
+    pub const CONTROLLER_REQUEST: Self = Self(1 << 1);
+    /// Hot-join requests.
+    pub const HOT_JOIN: Self = Self(1 << 3);
+}
+
+impl core::ops::BitOr for Events {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// A 7-bit address that may be assigned as a dynamic address.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct DynamicAddress(u8);
+
+impl DynamicAddress {
+    /// Returns `None` for values above 0x7F, the reserved addresses 0x00 to
+    /// 0x07, the broadcast address 0x7E and the addresses that differ from it
+    /// in a single bit.
+    pub const fn new(addr: u8) -> Option<Self> {
+        if addr > 0x7F || addr < 0x08 || (addr ^ 0x7E).count_ones() <= 1 {
+            None
+        } else {
+            Some(Self(addr))
+        }
+    }
+
+    pub const fn get(self) -> u8 {
+        self.0
+    }
+}
+
+/// CCCs sent to all targets.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum BroadcastCcc {
+    Enec(Events),
+    Disec(Events),
+    /// Reset all dynamic addresses.
+    Rstdaa,
+    /// Set the maximum write length.
+    Setmwl(u16),
+    /// Set the maximum read length and, optionally, the maximum IBI payload size.
+    Setmrl {
+        max_read_len: u16,
+        max_ibi_len: Option<u8>,
+    },
+    /// Assign each target's static address as its dynamic address.
+    Setaasa,
+}
+
+/// CCCs that write to one target.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum DirectCcc {
+    Enec(Events),
+    Disec(Events),
+    /// Assign a dynamic address to the target at the addressed static address.
+    Setdasa(DynamicAddress),
+    /// Replace the target's dynamic address.
+    Setnewda(DynamicAddress),
+    Setmwl(u16),
+    Setmrl {
+        max_read_len: u16,
+        max_ibi_len: Option<u8>,
+    },
+}
+
+/// CCCs that read from one target.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum DirectGetCcc {
+    Getmwl,
+    /// Maximum read length, with the maximum IBI payload size if the target has one.
+    Getmrl { with_ibi_len: bool },
+    Getpid,
+    Getbcr,
+    Getdcr,
+    Getstatus,
+    /// Maximum data speed, with the maximum read turnaround if requested.
+    Getmxds { with_turnaround: bool },
+}
+
+/// A write CCC and its destination.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Ccc {
+    Broadcast(BroadcastCcc),
+    /// Direct CCC to the target at `addr`.
+    Direct { addr: u8, ccc: DirectCcc },
+}
+
+fn encode_length(max_len: u16, extra: Option<u8>, buf: &mut [u8; MAX_PAYLOAD]) -> usize {
+    buf[..2].copy_from_slice(&max_len.to_be_bytes());
+    match extra {
+        Some(b) => {
+            buf[2] = b;
+            3
+        }
+        None => 2,
+    }
+}
+
+impl Ccc {
+    /// Returns the command code.
+    pub fn code(&self) -> u8 {
+        match self {
+            Ccc::Broadcast(ccc) => match ccc {
+                BroadcastCcc::Enec(_) => ENEC,
+                BroadcastCcc::Disec(_) => DISEC,
+                BroadcastCcc::Rstdaa => RSTDAA,
+                BroadcastCcc::Setmwl(_) => SETMWL,
+                BroadcastCcc::Setmrl { .. } => SETMRL,
+                BroadcastCcc::Setaasa => SETAASA,
+            },
+            Ccc::Direct { ccc, .. } => match ccc {
+                DirectCcc::Enec(_) => ENEC | DIRECT,
+                DirectCcc::Disec(_) => DISEC | DIRECT,
+                DirectCcc::Setdasa(_) => SETDASA,
+                DirectCcc::Setnewda(_) => SETNEWDA,
+                DirectCcc::Setmwl(_) => SETMWL | DIRECT,
+                DirectCcc::Setmrl { .. } => SETMRL | DIRECT,
+            },
+        }
+    }
+
+    /// Returns the address of a direct CCC, or `None` for a broadcast.
+    pub fn address(&self) -> Option<u8> {
+        match self {
+            Ccc::Broadcast(_) => None,
+            Ccc::Direct { addr, .. } => Some(*addr),
+        }
+    }
+
+    /// Encodes the payload into `buf`.
+    ///
+    /// # Returns
+    ///
+    /// The payload length.
+    pub fn encode_payload(&self, buf: &mut [u8; MAX_PAYLOAD]) -> usize {
+        let one = |buf: &mut [u8; MAX_PAYLOAD], b: u8| {
+            buf[0] = b;
+            1
+        };
+        match self {
+            Ccc::Broadcast(ccc) => match *ccc {
+                BroadcastCcc::Enec(e) | BroadcastCcc::Disec(e) => one(buf, e.0),
+                BroadcastCcc::Rstdaa | BroadcastCcc::Setaasa => 0,
+                BroadcastCcc::Setmwl(len) => encode_length(len, None, buf),
+                BroadcastCcc::Setmrl {
+                    max_read_len,
+                    max_ibi_len,
+                } => encode_length(max_read_len, max_ibi_len, buf),
+            },
+            Ccc::Direct { ccc, .. } => match *ccc {
+                DirectCcc::Enec(e) | DirectCcc::Disec(e) => one(buf, e.0),
+                DirectCcc::Setdasa(a) | DirectCcc::Setnewda(a) => one(buf, a.get() << 1),
+                DirectCcc::Setmwl(len) => encode_length(len, None, buf),
+                DirectCcc::Setmrl {
+                    max_read_len,
+                    max_ibi_len,
+                } => encode_length(max_read_len, max_ibi_len, buf),
+            },
+        }
+    }
+}
+
+impl DirectGetCcc {
+    /// Returns the command code.
+    pub fn code(&self) -> u8 {
+        match self {
+            DirectGetCcc::Getmwl => GETMWL,
+            DirectGetCcc::Getmrl { .. } => GETMRL,
+            DirectGetCcc::Getpid => GETPID,
+            DirectGetCcc::Getbcr => GETBCR,
+            DirectGetCcc::Getdcr => GETDCR,
+            DirectGetCcc::Getstatus => GETSTATUS,
+            DirectGetCcc::Getmxds { .. } => GETMXDS,
+        }
+    }
+
+    /// Returns the length of the response, in bytes.
+    pub fn response_len(&self) -> usize {
+        match self {
+            DirectGetCcc::Getbcr | DirectGetCcc::Getdcr => 1,
+            DirectGetCcc::Getmwl | DirectGetCcc::Getstatus => 2,
+            DirectGetCcc::Getmrl { with_ibi_len } => 2 + usize::from(*with_ibi_len),
+            DirectGetCcc::Getpid => 6,
+            DirectGetCcc::Getmxds { with_turnaround } => {
+                if *with_turnaround {
+                    5
+                } else {
+                    2
+                }
+            }
+        }
+    }
+}
+
+/// CCC transfers on an I3C controller.
+pub trait I3cCcc: ErrorType {
+    /// Sends the write CCC `code` with `payload`, broadcast if `addr` is `None`.
+    fn ccc_write(&mut self, code: u8, addr: Option<u8>, payload: &[u8]) -> Result<(), Self::Error>;
+
+    /// Sends the direct read CCC `code` to `addr` and reads exactly `buf.len()` bytes.
+    ///
+    /// Fails with `ErrorKind::FrameFormat` if the target ends the read early.
+    fn ccc_read(&mut self, code: u8, addr: u8, buf: &mut [u8]) -> Result<(), Self::Error>;
+
+    /// Sends a typed write CCC.
+    fn send_ccc(&mut self, ccc: Ccc) -> Result<(), Self::Error> {
+        let mut payload = [0u8; MAX_PAYLOAD];
+        let len = ccc.encode_payload(&mut payload);
+        self.ccc_write(ccc.code(), ccc.address(), &payload[..len])
+    }
+
+    /// Sends a typed read CCC to `addr`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the response buffer and the number of valid bytes in it.
+    fn get_ccc(&mut self, addr: u8, ccc: DirectGetCcc) -> Result<([u8; 6], usize), Self::Error> {
+        let mut buf = [0u8; 6];
+        let len = ccc.response_len();
+        self.ccc_read(ccc.code(), addr, &mut buf[..len])?;
+        Ok((buf, len))
+    }
+
+    /// Reads the 48-bit provisioned ID of the target at `addr`.
+    fn get_pid(&mut self, addr: u8) -> Result<u64, Self::Error> {
+        let mut buf = [0u8; 8];
+        self.ccc_read(GETPID, addr, &mut buf[2..])?;
+        Ok(u64::from_be_bytes(buf))
+    }
+
+    fn get_bcr(&mut self, addr: u8) -> Result<u8, Self::Error> {
+        let mut buf = [0u8; 1];
+        self.ccc_read(GETBCR, addr, &mut buf)?;
+        Ok(buf[0])
+    }
+
+    fn get_dcr(&mut self, addr: u8) -> Result<u8, Self::Error> {
+        let mut buf = [0u8; 1];
+        self.ccc_read(GETDCR, addr, &mut buf)?;
+        Ok(buf[0])
+    }
+
+    fn get_status(&mut self, addr: u8) -> Result<u16, Self::Error> {
+        let mut buf = [0u8; 2];
+        self.ccc_read(GETSTATUS, addr, &mut buf)?;
+        Ok(u16::from_be_bytes(buf))
+    }
+
+    /// Resets the dynamic addresses of all targets.
+    fn reset_dynamic_addresses(&mut self) -> Result<(), Self::Error> {
+        self.send_ccc(Ccc::Broadcast(BroadcastCcc::Rstdaa))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorKind;
+
+    fn payload(ccc: Ccc) -> ([u8; MAX_PAYLOAD], usize) {
+        let mut buf = [0u8; MAX_PAYLOAD];
+        let len = ccc.encode_payload(&mut buf);
+        (buf, len)
+    }
+
+    #[test]
+    fn broadcast_codes_and_payloads() {
+        let events = Events::INTERRUPT | Events::HOT_JOIN;
+        let enec = Ccc::Broadcast(BroadcastCcc::Enec(events));
+        assert_eq!((enec.code(), enec.address()), (0x00, None));
+        assert_eq!(payload(enec), ([0x09, 0, 0], 1));
+
+        let rstdaa = Ccc::Broadcast(BroadcastCcc::Rstdaa);
+        assert_eq!(rstdaa.code(), 0x06);
+        assert_eq!(payload(rstdaa).1, 0);
+
+        let setmwl = Ccc::Broadcast(BroadcastCcc::Setmwl(0x0102));
+        assert_eq!(setmwl.code(), 0x09);
+        assert_eq!(payload(setmwl), ([0x01, 0x02, 0], 2));
+
+        let setmrl = Ccc::Broadcast(BroadcastCcc::Setmrl {
+            max_read_len: 0x0304,
+            max_ibi_len: Some(8),
+        });
+        assert_eq!(setmrl.code(), 0x0A);
+        assert_eq!(payload(setmrl), ([0x03, 0x04, 8], 3));
+    }
+
+    #[test]
+    fn direct_codes_set_the_direct_bit() {
+        let direct = |ccc| Ccc::Direct { addr: 0x30, ccc };
+        assert_eq!(direct(DirectCcc::Enec(Events::INTERRUPT)).code(), 0x80);
+        assert_eq!(direct(DirectCcc::Disec(Events::INTERRUPT)).code(), 0x81);
+        assert_eq!(direct(DirectCcc::Setmwl(64)).code(), 0x89);
+        assert_eq!(direct(DirectCcc::Disec(Events::INTERRUPT)).address(), Some(0x30));
+
+        let setmrl = direct(DirectCcc::Setmrl {
+            max_read_len: 256,
+            max_ibi_len: None,
+        });
+        assert_eq!(setmrl.code(), 0x8A);
+        assert_eq!(payload(setmrl), ([0x01, 0x00, 0], 2));
+    }
+
+    #[test]
+    fn address_assignment_shifts_the_address() {
+        let addr = DynamicAddress::new(0x31).unwrap();
+        let setdasa = Ccc::Direct {
+            addr: 0x50,
+            ccc: DirectCcc::Setdasa(addr),
+        };
+        assert_eq!(setdasa.code(), 0x87);
+        assert_eq!(payload(setdasa), ([0x62, 0, 0], 1));
+
+        let setnewda = Ccc::Direct {
+            addr: 0x31,
+            ccc: DirectCcc::Setnewda(addr),
+        };
+        assert_eq!(setnewda.code(), 0x88);
+        assert_eq!(payload(setnewda), ([0x62, 0, 0], 1));
+    }
+
+    #[test]
+    fn dynamic_address_rejects_reserved_values() {
+        for addr in [0x00, 0x01, 0x07, 0x7E, 0x7F, 0x7C, 0x3E, 0x80] {
+            assert_eq!(DynamicAddress::new(addr), None, "{addr:#x}");
+        }
+        assert_eq!(DynamicAddress::new(0x08).map(DynamicAddress::get), Some(0x08));
+    }
+
+    #[test]
+    fn get_ccc_response_lengths() {
+        assert_eq!((DirectGetCcc::Getpid.code(), DirectGetCcc::Getpid.response_len()), (0x8D, 6));
+        assert_eq!(DirectGetCcc::Getbcr.response_len(), 1);
+        assert_eq!(DirectGetCcc::Getstatus.response_len(), 2);
+        assert_eq!(DirectGetCcc::Getmrl { with_ibi_len: true }.response_len(), 3);
+        assert_eq!(DirectGetCcc::Getmxds { with_turnaround: true }.response_len(), 5);
+        assert_eq!(DirectGetCcc::Getmxds { with_turnaround: false }.response_len(), 2);
+    }
+
+    /// Records the last write CCC and answers reads from a fixed response.
+    struct Recorder {
+        code: u8,
+        addr: Option<u8>,
+        payload: [u8; MAX_PAYLOAD],
+        len: usize,
+    }
+
+    impl ErrorType for Recorder {
+        type Error = ErrorKind;
+    }
+
+    impl I3cCcc for Recorder {
+        fn ccc_write(&mut self, code: u8, addr: Option<u8>, payload: &[u8]) -> Result<(), Self::Error> {
+            self.code = code;
+            self.addr = addr;
+            self.len = payload.len();
+            self.payload[..payload.len()].copy_from_slice(payload);
+            Ok(())
+        }
+
+        fn ccc_read(&mut self, code: u8, addr: u8, buf: &mut [u8]) -> Result<(), Self::Error> {
+            self.code = code;
+            self.addr = Some(addr);
+            for (i, byte) in buf.iter_mut().enumerate() {
+                *byte = 0xA0 + i as u8;
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn send_ccc_frames_the_typed_command() {
+        let mut bus = Recorder {
+            code: 0xFF,
+            addr: None,
+            payload: [0; MAX_PAYLOAD],
+            len: 0,
+        };
+        bus.send_ccc(Ccc::Direct {
+            addr: 0x20,
+            ccc: DirectCcc::Setmwl(0x0040),
+        })
+        .unwrap();
+        assert_eq!((bus.code, bus.addr), (0x89, Some(0x20)));
+        assert_eq!(&bus.payload[..bus.len], &[0x00, 0x40]);
+
+        bus.reset_dynamic_addresses().unwrap();
+        assert_eq!((bus.code, bus.addr, bus.len), (0x06, None, 0));
+
+        assert_eq!(bus.get_pid(0x21), Ok(0xA0A1_A2A3_A4A5));
+        assert_eq!((bus.code, bus.addr), (0x8D, Some(0x21)));
+        assert_eq!(bus.get_status(0x21), Ok(0xA0A1));
+    }
+}
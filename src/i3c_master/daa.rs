@@ -0,0 +1,103 @@
+//! Dynamic address assignment and bus initialization.
+//!
+//! [`BusInit::initialize_bus`] runs the usual power-up sequence: RSTDAA,
+//! SETDASA for targets with a known static address, then ENTDAA rounds
+//! until no target is left without an address.
+
+use crate::i3c_target::DeviceCharacteristics;
+
+use super::ccc::{BroadcastCcc, Ccc, DirectCcc, DynamicAddress, I3cCcc};
+
+/// A target that was assigned a dynamic address.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct AssignedTarget {
+    pub address: DynamicAddress,
+    pub characteristics: DeviceCharacteristics,
+}
+
+/// A target addressed through SETDASA during bus initialization.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct StaticTarget {
+    /// The target's I2C static address.
+    pub static_address: u8,
+    /// Dynamic address to assign.
+    pub address: DynamicAddress,
+}
+
+/// Chooses addresses and records the targets found by [`BusInit::initialize_bus`].
+pub trait BusInitHandler {
+    /// Returns the address the next ENTDAA round may assign, or `None` once
+    /// no more are available.
+    ///
+    /// The address is only taken once [`BusInitHandler::on_target`] reports
+    /// it; a round that finds no target leaves it free.
+    fn next_address(&mut self) -> Option<DynamicAddress>;
+
+    /// Called for every target that was assigned an address.
+    fn on_target(&mut self, target: &AssignedTarget);
+}
+
+/// Dynamic address assignment on an I3C controller.
+pub trait BusInit: I3cCcc {
+    /// Runs one ENTDAA round, assigning `address` to the target that wins
+    /// arbitration.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the characteristics of the addressed target, or
+    /// `None` if no target without an address responded.
+    fn entdaa_round(&mut self, address: DynamicAddress) -> Result<Option<DeviceCharacteristics>, Self::Error>;
+
+    /// Assigns `target.address` to the target at its static address using SETDASA.
+    fn set_dynamic_address_from_static(&mut self, target: StaticTarget) -> Result<(), Self::Error> {
+        self.send_ccc(Ccc::Direct {
+            addr: target.static_address,
+            ccc: DirectCcc::Setdasa(target.address),
+        })
+    }
+
+    /// Resets all dynamic addresses and assigns new ones.
+    ///
+    /// # Parameters
+    ///
+    /// - `static_targets`: Targets to address with SETDASA before ENTDAA.
+    /// - `handler`: Supplies addresses for ENTDAA and receives every assigned target.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of targets assigned. Enumeration
+    /// stops early if `handler` runs out of addresses.
+    fn initialize_bus(
+        &mut self,
+        static_targets: &[StaticTarget],
+        handler: &mut impl BusInitHandler,
+    ) -> Result<usize, Self::Error> {
+        self.send_ccc(Ccc::Broadcast(BroadcastCcc::Rstdaa))?;
+        let mut count = 0;
+        for &target in static_targets {
+            self.set_dynamic_address_from_static(target)?;
+            let addr = target.address.get();
+            let characteristics = DeviceCharacteristics {
+                pid: self.get_pid(addr)?,
+                bcr: self.get_bcr(addr)?,
+                dcr: self.get_dcr(addr)?,
+            };
+            handler.on_target(&AssignedTarget {
+                address: target.address,
+                characteristics,
+            });
+            count += 1;
+        }
+        while let Some(address) = handler.next_address() {
+            let Some(characteristics) = self.entdaa_round(address)? else {
+                break;
+            };
+            handler.on_target(&AssignedTarget {
+                address,
+                characteristics,
+            });
+            count += 1;
+        }
+        Ok(count)
+    }
+}
@@ -0,0 +1,109 @@
+//! Controller role handoff between I3C controllers.
+//!
+//! On boards with two management controllers, one is the active controller
+//! and the other waits as a secondary controller. The secondary requests
+//! the controller role with a Controller Role Request IBI; the active
+//! controller accepts it by sending GETACCCR, after which the secondary
+//! drives the bus.
+
+use super::ccc::{DynamicAddress, I3cCcc};
+use super::IbiDescriptor;
+
+/// GETACCCR: direct read CCC that passes the controller role to the addressed device.
+pub const GETACCCR: u8 = 0x91;
+
+/// DEFTGTS: broadcast CCC telling secondary controllers which targets are on the bus.
+pub const DEFTGTS: u8 = 0x08;
+
+/// Whether this controller currently drives the bus.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ControllerRole {
+    Active,
+    /// Acting as a target until the role is handed over.
+    Secondary,
+}
+
+/// Change in controller role.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum HandoffEvent {
+    /// This controller became the active controller.
+    Acquired,
+    /// Another controller took the role; this one is now secondary.
+    Lost {
+        /// Address the new controller used on the bus, if known.
+        new_controller: Option<DynamicAddress>,
+    },
+    /// A request for the role was declined by the active controller.
+    Declined,
+}
+
+/// Receives role changes from [`ControllerHandoff::dispatch_handoff_events`].
+pub trait HandoffHandler {
+    fn on_handoff(&mut self, event: HandoffEvent);
+}
+
+/// Secondary-controller operation.
+///
+/// Must be implemented by any controller that can share the bus with another
+/// controller, whichever role it starts in.
+pub trait ControllerHandoff: I3cCcc {
+    /// Returns the current role.
+    fn role(&self) -> ControllerRole;
+
+    /// As a secondary controller, raises a Controller Role Request.
+    ///
+    /// Returns immediately; the outcome is reported as a [`HandoffEvent`].
+    /// Fails with [`super::ErrorKind::Busy`] if a request is already pending.
+    fn request_control(&mut self) -> Result<(), Self::Error>;
+
+    /// As the active controller, passes the role to the secondary controller at `addr`.
+    ///
+    /// Sends GETACCCR and waits for the handoff to complete; the bus must be
+    /// idle. On success this controller becomes secondary.
+    fn hand_off(&mut self, addr: DynamicAddress) -> Result<(), Self::Error>;
+
+    /// Takes the oldest pending role change without blocking.
+    fn poll_handoff_event(&mut self) -> Result<Option<HandoffEvent>, Self::Error>;
+
+    /// Hands every pending role change to `handler`.
+    fn dispatch_handoff_events(&mut self, handler: &mut impl HandoffHandler) -> Result<(), Self::Error> {
+        while let Some(event) = self.poll_handoff_event()? {
+            handler.on_handoff(event);
+        }
+        Ok(())
+    }
+}
+
+/// Decision on a Controller Role Request received as an IBI.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum RoleRequestDecision {
+    /// Hand the role over with [`ControllerHandoff::hand_off`].
+    Accept,
+    /// NACK the request; the requester sees [`HandoffEvent::Declined`].
+    Decline,
+}
+
+/// Classification of in-band interrupts received by an active controller.
+pub trait RoleRequest: IbiDescriptor {
+    /// Returns whether the IBI is a Controller Role Request rather than a target interrupt.
+    fn is_controller_role_request(&self) -> bool;
+}
+
+/// Active-controller side: answers Controller Role Requests.
+pub trait RoleRequestHandling: ControllerHandoff {
+    /// Informs secondary controllers of the current targets with DEFTGTS.
+    ///
+    /// Should be sent after bus initialization and whenever targets change,
+    /// so a controller taking over knows the bus population.
+    fn define_targets(&mut self) -> Result<(), Self::Error>;
+
+    /// Responds to a Controller Role Request from `addr`.
+    ///
+    /// Declining NACKs the request; accepting runs [`ControllerHandoff::hand_off`].
+    fn answer_role_request(
+        &mut self,
+        addr: DynamicAddress,
+        decision: RoleRequestDecision,
+    ) -> Result<(), Self::Error>;
+}
@@ -0,0 +1,576 @@
+pub use crate::error::{Error, ErrorKind};
+pub use crate::rx_grant::RxGrant;
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// Identity a target reports during dynamic address assignment.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct DeviceCharacteristics {
+    /// 48-bit provisioned ID.
+    pub pid: u64,
+    /// Bus characteristics register.
+    pub bcr: u8,
+    /// Device characteristics register.
+    pub dcr: u8,
+}
+
+/// Bus events observed by an I3C target.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum Event {
+    /// The controller finished a private write of `len` bytes.
+    PrivateWrite { len: usize },
+    /// The controller started a private read; staged data is sent.
+    PrivateRead,
+}
+
+/// I3C target (peripheral) role.
+///
+/// Drivers should also implement [`crate::system_control::power::PowerManaged`].
+pub trait I3cTarget: ErrorType {
+    /// Returns the identity reported during dynamic address assignment.
+    fn characteristics(&self) -> DeviceCharacteristics;
+
+    /// Returns the current dynamic address, or `None` before one is assigned.
+    fn dynamic_address(&self) -> Option<u8>;
+
+    /// Returns the next pending bus event, if any, without blocking.
+    fn poll_event(&mut self) -> Result<Option<Event>, Self::Error>;
+
+    /// Copies the data of the last completed private write into `buf`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes copied.
+    fn read_received(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error>;
+
+    /// Stages data for the next private read.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes accepted.
+    fn set_response(&mut self, data: &[u8]) -> Result<usize, Self::Error>;
+}
+
+/// Zero-copy receive path.
+///
+/// Alternative to [`I3cTarget::read_received`] that lends the received data
+/// instead of copying it into a caller buffer.
+pub trait ZeroCopyReceive: I3cTarget {
+    type RxGrant<'a>: RxGrant
+    where
+        Self: 'a;
+
+    /// Lends the data of the oldest completed private write that has not been received yet.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the grant, or `None` if no data is pending.
+    fn receive_grant(&mut self) -> Result<Option<Self::RxGrant<'_>>, Self::Error>;
+}
+
+/// What ended a private transfer.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Boundary {
+    /// A STOP; the bus is free.
+    Stop,
+    /// A repeated START; another transfer follows in the same transaction.
+    RepeatedStart,
+}
+
+/// Position of a target in the private transfer state machine.
+///
+/// ```text
+///            START/Sr + addr/W                        STOP
+///   Idle ─────────────────────▶ Writing ───────────────────────▶ Idle
+///    │                            │ Sr + addr/R
+///    │ START/Sr + addr/R          ▼                   STOP
+///    └──────────────────────▶ Reading ───────────────────────▶ Idle
+/// ```
+///
+/// Every transition out of `Writing` or `Reading` produces one
+/// [`TransactionEvent`]. A repeated START moves between the two active
+/// states directly without passing through `Idle`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TransactionState {
+    Idle,
+    Writing,
+    Reading,
+}
+
+/// Completed private transfers.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TransactionEvent {
+    /// The controller wrote `len` bytes, now available through [`I3cTarget::read_received`].
+    WriteComplete { len: usize, ended_by: Boundary },
+    /// The controller read `sent` bytes of the preloaded data.
+    ///
+    /// Preloaded bytes that were not read are discarded.
+    ReadComplete { sent: usize, ended_by: Boundary },
+    /// The controller read with no data preloaded and was NACKed.
+    ReadNacked,
+}
+
+/// Receives completed transfers from [`Transactions::dispatch_transactions`].
+pub trait TransactionHandler {
+    /// Called with the data of a completed private write.
+    fn on_write(&mut self, data: &[u8], ended_by: Boundary);
+
+    /// Called when a private read ended.
+    fn on_read(&mut self, event: TransactionEvent);
+}
+
+/// Failure of a dispatch loop such as [`Transactions::dispatch_transactions`].
+#[derive(Debug)]
+pub enum DispatchError<E> {
+    /// A write of `len` bytes does not fit the dispatch buffer. Its data was
+    /// left unread, so it is not truncated, and no handler was called for it.
+    BufferTooSmall { len: usize },
+    /// Like `BufferTooSmall`, for a write of `len` bytes to `endpoint` of a
+    /// [`MultiEndpoint`] target. The data stays with [`MultiEndpoint::read_endpoint`].
+    EndpointBufferTooSmall { endpoint: EndpointId, len: usize },
+    /// The target failed.
+    Target(E),
+}
+
+impl<E: Error> Error for DispatchError<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            DispatchError::BufferTooSmall { .. } | DispatchError::EndpointBufferTooSmall { .. } => {
+                ErrorKind::BufferTooSmall
+            }
+            DispatchError::Target(e) => e.kind(),
+        }
+    }
+}
+
+/// Explicit private transfer model, e.g. for an MCTP responder.
+///
+/// The target holds the data of one completed write at a time:
+/// `poll_transaction` does not report another
+/// [`TransactionEvent::WriteComplete`] until the data of the previous one
+/// was taken with [`I3cTarget::read_received`], and further private writes
+/// are NACKed in the meantime. [`I3cTarget::read_received`] therefore always
+/// returns the data of the write most recently reported.
+pub trait Transactions: I3cTarget {
+    /// Returns the current [`TransactionState`].
+    fn state(&self) -> TransactionState;
+
+    /// Loads `data` into the TX FIFO for the next private read.
+    ///
+    /// Data can be preloaded in several calls until the FIFO is full. It is
+    /// only sent to the next read; the controller sees the end of data (T-bit)
+    /// after the last preloaded byte.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes accepted.
+    fn preload_tx(&mut self, data: &[u8]) -> Result<usize, Self::Error>;
+
+    /// Returns the free space in the TX FIFO, in bytes.
+    fn tx_free(&self) -> usize;
+
+    /// Discards preloaded data that has not been read.
+    fn flush_tx(&mut self) -> Result<(), Self::Error>;
+
+    /// Returns the oldest completed transfer without blocking.
+    fn poll_transaction(&mut self) -> Result<Option<TransactionEvent>, Self::Error>;
+
+    /// Hands every completed transfer to `handler`, copying written data through `buf`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success, or [`DispatchError::BufferTooSmall`] if a
+    /// write is longer than `buf`. Its data can still be taken with
+    /// [`I3cTarget::read_received`] and a larger buffer.
+    fn dispatch_transactions(
+        &mut self,
+        handler: &mut impl TransactionHandler,
+        buf: &mut [u8],
+    ) -> Result<(), DispatchError<Self::Error>> {
+        while let Some(event) = self.poll_transaction().map_err(DispatchError::Target)? {
+            match event {
+                TransactionEvent::WriteComplete { len, ended_by } => {
+                    if len > buf.len() {
+                        return Err(DispatchError::BufferTooSmall { len });
+                    }
+                    let len = self.read_received(buf).map_err(DispatchError::Target)?;
+                    handler.on_write(&buf[..len], ended_by);
+                }
+                event => handler.on_read(event),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// How a dynamic address was assigned.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum AssignmentMethod {
+    /// ENTDAA arbitration.
+    Entdaa,
+    /// SETDASA, based on the target's static address.
+    Setdasa,
+    /// SETNEWDA, replacing a previous dynamic address.
+    Setnewda,
+}
+
+/// Dynamic address lifecycle events.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum AddressEvent {
+    /// The controller assigned `address`.
+    Assigned {
+        address: u8,
+        method: AssignmentMethod,
+    },
+    /// The controller reset the dynamic address with RSTDAA.
+    Reset,
+    /// A hot-join request was acknowledged by the controller.
+    HotJoinAccepted,
+    /// A hot-join request was NACKed or hot-join is disabled by the controller.
+    HotJoinRejected,
+    /// The controller added the target to the group at `address` with SETGRPA.
+    GroupAssigned { address: u8 },
+    /// The controller removed the target from every group with RSTGRPA.
+    GroupReset,
+}
+
+/// Receives address lifecycle events from [`DynamicAddressing::dispatch_address_events`].
+pub trait AddressEventHandler {
+    fn on_address_event(&mut self, event: AddressEvent);
+}
+
+/// Participation in bus initialization.
+pub trait DynamicAddressing: I3cTarget {
+    /// Returns the oldest pending address event without blocking.
+    fn poll_address_event(&mut self) -> Result<Option<AddressEvent>, Self::Error>;
+
+    /// Hands every pending address event to `handler`.
+    fn dispatch_address_events(
+        &mut self,
+        handler: &mut dyn AddressEventHandler,
+    ) -> Result<(), Self::Error> {
+        while let Some(event) = self.poll_address_event()? {
+            handler.on_address_event(event);
+        }
+        Ok(())
+    }
+}
+
+/// Hot-join for targets attached after the bus was initialized.
+pub trait HotJoin: DynamicAddressing {
+    /// Issues a hot-join request on the bus.
+    ///
+    /// The outcome, and the address assigned afterwards, are reported as
+    /// [`AddressEvent`]s.
+    fn request_hot_join(&mut self) -> Result<(), Self::Error>;
+}
+
+/// SETGRPA: direct CCC that adds the addressed target to a group (I3C v1.1).
+pub const SETGRPA: u8 = 0x9B;
+
+/// RSTGRPA: broadcast CCC that removes every target from its groups (I3C v1.1).
+pub const RSTGRPA: u8 = 0x2C;
+
+/// RSTGRPA: direct form, removing only the addressed target from its groups.
+pub const RSTGRPA_DIRECT: u8 = 0x9C;
+
+/// Membership in address groups.
+///
+/// A write to a group address reaches every member at once; group
+/// addresses are never read from. Assignments are reported as
+/// [`AddressEvent::GroupAssigned`] and [`AddressEvent::GroupReset`].
+pub trait GroupAddressing: DynamicAddressing {
+    /// Maximum number of groups the target can join.
+    fn max_groups(&self) -> usize;
+
+    /// Returns the group addresses currently assigned.
+    fn group_addresses(&self) -> &[u8];
+}
+
+/// Identifies one logical endpoint of a composite target.
+///
+/// Endpoint 0 is the physical target itself; further endpoints are virtual
+/// targets with their own dynamic address.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct EndpointId(pub u8);
+
+/// Address a transfer to an endpoint was sent to.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Addressed {
+    /// The endpoint's own dynamic address.
+    Dynamic,
+    /// A group address the endpoint is a member of.
+    Group(u8),
+}
+
+/// Event of one endpoint, from [`MultiEndpoint::poll_endpoint_event`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum EndpointEvent {
+    /// A private write of `len` bytes to the endpoint completed.
+    ///
+    /// A group write is reported once for every member endpoint.
+    Write { len: usize, addressed: Addressed },
+    /// The controller read the endpoint; data queued for it was sent.
+    Read { sent: usize },
+    /// The controller read the endpoint with nothing queued and was NACKed.
+    ReadNacked,
+    /// An address lifecycle event of the endpoint.
+    Address(AddressEvent),
+}
+
+/// Receives routed events from [`MultiEndpoint::dispatch_endpoint_events`].
+pub trait EndpointHandler {
+    /// Called with the data of a completed write to `endpoint`.
+    fn on_write(&mut self, endpoint: EndpointId, data: &[u8], addressed: Addressed);
+
+    /// Called for every other event of `endpoint`.
+    fn on_event(&mut self, endpoint: EndpointId, event: EndpointEvent);
+}
+
+/// One physical target exposing several logical endpoints, e.g. a hub, a
+/// debug port and an MCTP endpoint behind one controller.
+///
+/// Every endpoint has its own identity, address, receive buffer and TX queue,
+/// so the functions can be implemented independently. The [`I3cTarget`]
+/// methods act on endpoint 0.
+///
+/// Like [`Transactions`], each endpoint holds the data of one completed write
+/// at a time: no further [`EndpointEvent::Write`] is reported for an endpoint
+/// until [`MultiEndpoint::read_endpoint`] has taken the previous one, and
+/// writes to it are NACKed meanwhile.
+///
+/// The trait is dyn compatible, so different composite targets can be driven
+/// through one `&mut dyn MultiEndpoint<Error = E>`.
+pub trait MultiEndpoint: I3cTarget {
+    /// Number of endpoints, including endpoint 0.
+    fn endpoint_count(&self) -> usize;
+
+    /// Returns the identity `endpoint` reports during dynamic address assignment.
+    fn endpoint_characteristics(&self, endpoint: EndpointId) -> DeviceCharacteristics;
+
+    /// Returns the dynamic address of `endpoint`, or `None` before one is assigned.
+    fn endpoint_address(&self, endpoint: EndpointId) -> Option<u8>;
+
+    /// Returns the group addresses `endpoint` is a member of.
+    fn endpoint_groups(&self, endpoint: EndpointId) -> &[u8];
+
+    /// Returns the oldest pending event of any endpoint, without blocking.
+    fn poll_endpoint_event(&mut self) -> Result<Option<(EndpointId, EndpointEvent)>, Self::Error>;
+
+    /// Copies the data of the last completed write to `endpoint` into `buf`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes copied.
+    fn read_endpoint(&mut self, endpoint: EndpointId, buf: &mut [u8]) -> Result<usize, Self::Error>;
+
+    /// Appends `data` to the TX queue of `endpoint`, sent on its next reads.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes accepted.
+    fn queue_tx(&mut self, endpoint: EndpointId, data: &[u8]) -> Result<usize, Self::Error>;
+
+    /// Returns the free space in the TX queue of `endpoint`, in bytes.
+    fn tx_free(&self, endpoint: EndpointId) -> usize;
+
+    /// Discards data queued for `endpoint` that has not been read.
+    fn flush_tx(&mut self, endpoint: EndpointId) -> Result<(), Self::Error>;
+
+    /// Hands every pending event to `handler`, copying written data through `buf`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success, or [`DispatchError::EndpointBufferTooSmall`]
+    /// if a write is longer than `buf`. Its data can still be taken from the
+    /// endpoint named in the error with [`MultiEndpoint::read_endpoint`] and a
+    /// larger buffer.
+    fn dispatch_endpoint_events(
+        &mut self,
+        handler: &mut dyn EndpointHandler,
+        buf: &mut [u8],
+    ) -> Result<(), DispatchError<Self::Error>> {
+        while let Some((endpoint, event)) = self.poll_endpoint_event().map_err(DispatchError::Target)? {
+            match event {
+                EndpointEvent::Write { len, addressed } => {
+                    if len > buf.len() {
+                        return Err(DispatchError::EndpointBufferTooSmall { endpoint, len });
+                    }
+                    let len = self.read_endpoint(endpoint, buf).map_err(DispatchError::Target)?;
+                    handler.on_write(endpoint, &buf[..len], addressed);
+                }
+                event => handler.on_event(endpoint, event),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two endpoints; endpoint 1 holds one pending write of `written` bytes.
+    struct Composite {
+        written: usize,
+        pending: Option<(EndpointId, EndpointEvent)>,
+    }
+
+    impl Composite {
+        fn with_write(len: usize) -> Self {
+            Self {
+                written: len,
+                pending: Some((
+                    EndpointId(1),
+                    EndpointEvent::Write {
+                        len,
+                        addressed: Addressed::Dynamic,
+                    },
+                )),
+            }
+        }
+    }
+
+    impl ErrorType for Composite {
+        type Error = ErrorKind;
+    }
+
+    impl I3cTarget for Composite {
+        fn characteristics(&self) -> DeviceCharacteristics {
+            self.endpoint_characteristics(EndpointId(0))
+        }
+
+        fn dynamic_address(&self) -> Option<u8> {
+            self.endpoint_address(EndpointId(0))
+        }
+
+        fn poll_event(&mut self) -> Result<Option<Event>, Self::Error> {
+            Ok(None)
+        }
+
+        fn read_received(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            self.read_endpoint(EndpointId(0), buf)
+        }
+
+        fn set_response(&mut self, data: &[u8]) -> Result<usize, Self::Error> {
+            self.queue_tx(EndpointId(0), data)
+        }
+    }
+
+    impl DynamicAddressing for Composite {
+        fn poll_address_event(&mut self) -> Result<Option<AddressEvent>, Self::Error> {
+            Ok(None)
+        }
+    }
+
+    impl GroupAddressing for Composite {
+        fn max_groups(&self) -> usize {
+            1
+        }
+
+        fn group_addresses(&self) -> &[u8] {
+            &[]
+        }
+    }
+
+    impl MultiEndpoint for Composite {
+        fn endpoint_count(&self) -> usize {
+            2
+        }
+
+        fn endpoint_characteristics(&self, endpoint: EndpointId) -> DeviceCharacteristics {
+            DeviceCharacteristics {
+                pid: u64::from(endpoint.0),
+                bcr: 0,
+                dcr: 0,
+            }
+        }
+
+        fn endpoint_address(&self, endpoint: EndpointId) -> Option<u8> {
+            Some(0x30 + endpoint.0)
+        }
+
+        fn endpoint_groups(&self, _endpoint: EndpointId) -> &[u8] {
+            &[]
+        }
+
+        fn poll_endpoint_event(&mut self) -> Result<Option<(EndpointId, EndpointEvent)>, Self::Error> {
+            Ok(self.pending.take())
+        }
+
+        fn read_endpoint(&mut self, endpoint: EndpointId, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            if endpoint != EndpointId(1) {
+                return Ok(0);
+            }
+            let len = core::mem::take(&mut self.written);
+            let data = buf.get_mut(..len).ok_or(ErrorKind::BufferTooSmall)?;
+            data.fill(0xA5);
+            Ok(len)
+        }
+
+        fn queue_tx(&mut self, _endpoint: EndpointId, data: &[u8]) -> Result<usize, Self::Error> {
+            Ok(data.len())
+        }
+
+        fn tx_free(&self, _endpoint: EndpointId) -> usize {
+            0
+        }
+
+        fn flush_tx(&mut self, _endpoint: EndpointId) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct Recorder {
+        writes: usize,
+        last: Option<(EndpointId, usize)>,
+    }
+
+    impl EndpointHandler for Recorder {
+        fn on_write(&mut self, endpoint: EndpointId, data: &[u8], _addressed: Addressed) {
+            self.writes += 1;
+            self.last = Some((endpoint, data.len()));
+        }
+
+        fn on_event(&mut self, _endpoint: EndpointId, _event: EndpointEvent) {}
+    }
+
+    #[test]
+    fn traits_are_dyn_compatible() {
+        let mut composite = Composite::with_write(0);
+        let groups: &dyn GroupAddressing<Error = ErrorKind> = &composite;
+        assert_eq!(groups.max_groups(), 1);
+        let target: &mut dyn MultiEndpoint<Error = ErrorKind> = &mut composite;
+        assert_eq!(target.endpoint_count(), 2);
+    }
+
+    #[test]
+    fn dispatch_routes_writes_through_dyn() {
+        let mut composite = Composite::with_write(4);
+        let target: &mut dyn MultiEndpoint<Error = ErrorKind> = &mut composite;
+        let mut handler = Recorder::default();
+        target.dispatch_endpoint_events(&mut handler, &mut [0; 8]).unwrap();
+        assert_eq!((handler.writes, handler.last), (1, Some((EndpointId(1), 4))));
+    }
+
+    #[test]
+    fn oversized_write_names_its_endpoint() {
+        let mut composite = Composite::with_write(16);
+        let mut handler = Recorder::default();
+        let mut buf = [0u8; 8];
+        let endpoint = match composite.dispatch_endpoint_events(&mut handler, &mut buf) {
+            Err(DispatchError::EndpointBufferTooSmall { endpoint, len: 16 }) => endpoint,
+            other => panic!("unexpected result: {other:?}"),
+        };
+        assert_eq!(handler.writes, 0);
+
+        let mut large = [0u8; 16];
+        assert_eq!(composite.read_endpoint(endpoint, &mut large), Ok(16));
+        assert_eq!(large, [0xA5; 16]);
+    }
+}
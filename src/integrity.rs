@@ -0,0 +1,158 @@
+//! Hashing and MAC verification of block device regions.
+//!
+//! Secure-boot verifiers read an image from flash and run it through a
+//! digest or MAC. [`DigestRegion`] and [`MacRegion`] provide that chunked
+//! read loop once for every [`Digest`] and [`Mac`] implementation.
+
+use core::ops::Range;
+
+use crate::block_device::ReadBlockDevice;
+use crate::common::ConstantTimeEq;
+use crate::digest::Digest;
+use crate::mac::Mac;
+
+/// Largest digest [`DigestRegion::verify_region`] compares, in bytes (SHA-512).
+pub const MAX_DIGEST_SIZE: usize = 64;
+
+/// Failure of a region check.
+#[derive(Debug)]
+pub enum IntegrityError<D, C> {
+    /// Reading the device failed.
+    Device(D),
+    /// The digest or MAC failed.
+    Crypto(C),
+    /// The range start, or the scratch buffer length, is not a multiple of
+    /// the device read size, or the range lies outside the device.
+    InvalidRange,
+    /// The computed value does not match the expected one.
+    Mismatch,
+}
+
+/// Reads `range` of `device` through `buf` and feeds every chunk to `update`.
+fn for_each_chunk<B, C>(
+    device: &mut B,
+    range: Range<usize>,
+    buf: &mut [u8],
+    mut update: impl FnMut(&mut [u8]) -> Result<(), C>,
+) -> Result<(), IntegrityError<B::Error, C>>
+where
+    B: ReadBlockDevice + ?Sized,
+{
+    let read_size = device.read_size().max(1);
+    let chunk_len = buf.len() - buf.len() % read_size;
+    if chunk_len == 0
+        || !range.start.is_multiple_of(read_size)
+        || range.start > range.end
+        || range.end > device.capacity()
+    {
+        return Err(IntegrityError::InvalidRange);
+    }
+    let mut addr = range.start;
+    while addr < range.end {
+        let len = chunk_len.min(range.end - addr);
+        // The last read is rounded up to the read size; only `len` bytes are hashed.
+        let read_len = len.div_ceil(read_size) * read_size;
+        if addr + read_len > device.capacity() {
+            return Err(IntegrityError::InvalidRange);
+        }
+        device
+            .read(addr, &mut buf[..read_len])
+            .map_err(IntegrityError::Device)?;
+        update(&mut buf[..len]).map_err(IntegrityError::Crypto)?;
+        addr += len;
+    }
+    Ok(())
+}
+
+/// Digest of a block device region.
+///
+/// Implemented for every [`Digest`].
+pub trait DigestRegion: Digest {
+    /// Hashes `range` of `device`, reading it in chunks of `buf`.
+    ///
+    /// Resets the digest first.
+    ///
+    /// # Parameters
+    ///
+    /// - `device`: The device to read. A [`crate::block_device::ReadOnly`] view is enough.
+    /// - `range`: Byte range to hash. Its start must be aligned to the device read size.
+    /// - `buf`: Scratch buffer, at least one read size long.
+    /// - `out`: Buffer the digest is written into.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the digest length.
+    fn digest_region<B: ReadBlockDevice + ?Sized>(
+        &mut self,
+        device: &mut B,
+        range: Range<usize>,
+        buf: &mut [u8],
+        out: &mut [u8],
+    ) -> Result<usize, IntegrityError<B::Error, Self::Error>> {
+        self.reset().map_err(IntegrityError::Crypto)?;
+        for_each_chunk(device, range, buf, |chunk| self.update(chunk))?;
+        self.finalize(out).map_err(IntegrityError::Crypto)
+    }
+
+    /// Hashes `range` of `device` and compares the digest with `expected` in constant time.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success, or [`IntegrityError::Mismatch`].
+    fn verify_region<B: ReadBlockDevice + ?Sized>(
+        &mut self,
+        device: &mut B,
+        range: Range<usize>,
+        buf: &mut [u8],
+        expected: &[u8],
+    ) -> Result<(), IntegrityError<B::Error, Self::Error>> {
+        let mut digest = [0u8; MAX_DIGEST_SIZE];
+        let len = self.digest_region(device, range, buf, &mut digest)?;
+        if digest[..len].ct_eq(expected) {
+            Ok(())
+        } else {
+            Err(IntegrityError::Mismatch)
+        }
+    }
+}
+
+impl<T: Digest + ?Sized> DigestRegion for T {}
+
+/// MAC over a block device region.
+///
+/// Implemented for every [`Mac`]. The key must be set before use.
+pub trait MacRegion: Mac {
+    /// Computes the MAC of `range` of `device` into `out`, reading it in chunks of `buf`.
+    ///
+    /// Resets the computation first.
+    fn mac_region<B: ReadBlockDevice + ?Sized>(
+        &mut self,
+        device: &mut B,
+        range: Range<usize>,
+        buf: &mut [u8],
+        out: &mut [u8],
+    ) -> Result<(), IntegrityError<B::Error, Self::Error>> {
+        self.reset().map_err(IntegrityError::Crypto)?;
+        for_each_chunk(device, range, buf, |chunk| self.update(chunk))?;
+        self.finalize(out).map_err(IntegrityError::Crypto)
+    }
+
+    /// Computes the MAC of `range` of `device` and verifies it against `tag`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success, or an error from [`Mac::verify`].
+    fn verify_region_mac<B: ReadBlockDevice + ?Sized>(
+        &mut self,
+        device: &mut B,
+        range: Range<usize>,
+        buf: &mut [u8],
+        tag: &[u8],
+    ) -> Result<(), IntegrityError<B::Error, Self::Error>> {
+        self.reset().map_err(IntegrityError::Crypto)?;
+        for_each_chunk(device, range, buf, |chunk| self.update(chunk))?;
+        self.verify(tag).map_err(IntegrityError::Crypto)
+    }
+}
+
+impl<T: Mac + ?Sized> MacRegion for T {}
@@ -0,0 +1,281 @@
+use core::fmt::Debug;
+use core::marker::PhantomData;
+use core::num::NonZeroU32;
+
+use crate::common::Operation;
+use crate::digest::DigestMarker;
+use crate::key_vault::{KeyLocator, KeyUsage, KeyVault};
+
+/// Error kind.
+///
+/// This represents a common set of key derivation errors. Implementations are
+/// free to define more specific or additional error types. However, by providing
+/// a mapping to these common errors, generic code can still react to them.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The requested output length exceeds what the KDF can produce, e.g. 255 * HashLen for HKDF.
+    InvalidOutputLength,
+
+    /// The input key material, salt or label has an unsupported length.
+    InvalidInputLength,
+
+    /// The PBKDF2 iteration count is below the minimum accepted by the implementation.
+    InvalidIterationCount,
+
+    /// The underlying MAC or digest reported an error.
+    PrimitiveFailure,
+
+    /// Storing the derived key in the key vault failed.
+    KeyStorageFailure,
+
+    /// The hardware accelerator is busy and cannot process the request.
+    Busy,
+
+    /// General hardware failure.
+    HardwareFailure,
+}
+
+pub trait Error: core::fmt::Debug {
+    /// Convert error to a generic error kind
+    ///
+    /// By using this method, errors freely defined by Algo implementations
+    /// can be converted to a set of generic errors upon which generic
+    /// code can act.
+    fn kind(&self) -> ErrorKind;
+}
+
+impl Error for core::convert::Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// Pseudorandom function used by [`CounterModeKdf`] and [`Pbkdf2`].
+pub trait PrfMarker {
+    /// Output size of one PRF invocation, in bytes.
+    const OUTPUT_SIZE: usize;
+}
+
+/// HMAC over the digest `D`, e.g. `HmacPrf<digest::Sha384>`.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub struct HmacPrf<D>(PhantomData<D>);
+
+impl<D: DigestMarker> PrfMarker for HmacPrf<D> {
+    const OUTPUT_SIZE: usize = D::OUTPUT_SIZE;
+}
+
+/// AES-CMAC.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub struct CmacAes;
+
+impl PrfMarker for CmacAes {
+    const OUTPUT_SIZE: usize = 16;
+}
+
+/// HKDF (RFC 5869).
+///
+/// Software implementations are typically generic over a [`crate::mac::Mac`]
+/// providing HMAC for the chosen digest.
+pub trait Hkdf: ErrorType {
+    /// Digest HMAC is built on; HashLen is its output size.
+    type Digest: DigestMarker;
+
+    /// Pseudorandom key produced by the extract step, e.g. `[u8; 32]` for HKDF-SHA256
+    /// or a key-vault handle when the PRK must stay in hardware.
+    type Prk;
+
+    /// HKDF-Extract.
+    ///
+    /// # Parameters
+    ///
+    /// - `salt`: Optional salt. An empty slice selects a string of HashLen zeros.
+    /// - `ikm`: Input keying material.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the pseudorandom key, or an error of type `Self::Error`.
+    fn extract(&mut self, salt: &[u8], ikm: &[u8]) -> Result<Self::Prk, Self::Error>;
+
+    /// HKDF-Expand.
+    ///
+    /// # Parameters
+    ///
+    /// - `prk`: Pseudorandom key from [`Hkdf::extract`].
+    /// - `info`: Context and application specific information.
+    /// - `okm`: Output keying material. Its length selects the number of bytes derived.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or failure. Fails with [`ErrorKind::InvalidOutputLength`]
+    /// if `okm` is longer than 255 * HashLen.
+    fn expand(&mut self, prk: &Self::Prk, info: &[u8], okm: &mut [u8]) -> Result<(), Self::Error>;
+
+    /// HKDF-Expand into a fixed-size array.
+    fn expand_array<const N: usize>(
+        &mut self,
+        prk: &Self::Prk,
+        info: &[u8],
+    ) -> Result<[u8; N], Self::Error> {
+        let mut okm = [0u8; N];
+        self.expand(prk, info, &mut okm)?;
+        Ok(okm)
+    }
+}
+
+/// HKDF running on an interrupt-driven engine.
+pub trait HkdfStart: Hkdf {
+    type ExtractOperation<'a>: Operation<Output = Self::Prk, Error = Self::Error>
+    where
+        Self: 'a;
+    type ExpandOperation<'a>: Operation<Output = (), Error = Self::Error>
+    where
+        Self: 'a;
+
+    /// Starts HKDF-Extract. Completes like [`Hkdf::extract`].
+    fn start_extract<'a>(&'a mut self, salt: &'a [u8], ikm: &'a [u8]) -> Result<Self::ExtractOperation<'a>, Self::Error>;
+
+    /// Starts HKDF-Expand. Completes like [`Hkdf::expand`].
+    fn start_expand<'a>(
+        &'a mut self,
+        prk: &'a Self::Prk,
+        info: &'a [u8],
+        okm: &'a mut [u8],
+    ) -> Result<Self::ExpandOperation<'a>, Self::Error>;
+}
+
+/// KDF in counter mode (NIST SP 800-108).
+pub trait CounterModeKdf: ErrorType {
+    /// Pseudorandom function, typically [`HmacPrf`] or [`CmacAes`].
+    type Prf: PrfMarker;
+
+    /// Key-derivation key type.
+    type Key;
+
+    /// Derives `out.len()` bytes from `key`, `label` and `context`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or failure.
+    fn derive(
+        &mut self,
+        key: &Self::Key,
+        label: &[u8],
+        context: &[u8],
+        out: &mut [u8],
+    ) -> Result<(), Self::Error>;
+
+    /// Derives a fixed-size array.
+    fn derive_array<const N: usize>(
+        &mut self,
+        key: &Self::Key,
+        label: &[u8],
+        context: &[u8],
+    ) -> Result<[u8; N], Self::Error> {
+        let mut out = [0u8; N];
+        self.derive(key, label, context, &mut out)?;
+        Ok(out)
+    }
+}
+
+/// PBKDF2 (RFC 8018).
+pub trait Pbkdf2: ErrorType {
+    /// Pseudorandom function, typically [`HmacPrf`].
+    type Prf: PrfMarker;
+
+    /// Derives `out.len()` bytes from `password` and `salt`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or failure.
+    fn derive(
+        &mut self,
+        password: &[u8],
+        salt: &[u8],
+        iterations: NonZeroU32,
+        out: &mut [u8],
+    ) -> Result<(), Self::Error>;
+
+    /// Derives a fixed-size array.
+    fn derive_array<const N: usize>(
+        &mut self,
+        password: &[u8],
+        salt: &[u8],
+        iterations: NonZeroU32,
+    ) -> Result<[u8; N], Self::Error> {
+        let mut out = [0u8; N];
+        self.derive(password, salt, iterations, &mut out)?;
+        Ok(out)
+    }
+}
+
+/// HKDF-Expand directly into a key-vault slot, so the output key is never
+/// exposed to software.
+pub trait HkdfExpandToVault: Hkdf {
+    type Vault: KeyVault;
+
+    /// Expands `len` bytes and stores them at `locator`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the handle of the derived key, or an error of type `Self::Error`.
+    fn expand_to_vault(
+        &mut self,
+        vault: &mut Self::Vault,
+        prk: &Self::Prk,
+        info: &[u8],
+        len: usize,
+        locator: KeyLocator<'_>,
+        usage: KeyUsage,
+    ) -> Result<<Self::Vault as KeyVault>::KeyHandle, Self::Error>;
+}
+
+/// Counter-mode derivation directly into a key-vault slot.
+pub trait CounterModeKdfToVault: CounterModeKdf {
+    type Vault: KeyVault;
+
+    /// Derives `len` bytes from `key`, `label` and `context` and stores them at `locator`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the handle of the derived key, or an error of type `Self::Error`.
+    #[allow(clippy::too_many_arguments)]
+    fn derive_to_vault(
+        &mut self,
+        vault: &mut Self::Vault,
+        key: &Self::Key,
+        label: &[u8],
+        context: &[u8],
+        len: usize,
+        locator: KeyLocator<'_>,
+        usage: KeyUsage,
+    ) -> Result<<Self::Vault as KeyVault>::KeyHandle, Self::Error>;
+}
+
+/// PBKDF2 directly into a key-vault slot, e.g. to unwrap a key with a
+/// password-derived key that software never sees.
+pub trait Pbkdf2ToVault: Pbkdf2 {
+    type Vault: KeyVault;
+
+    /// Derives `len` bytes from `password` and `salt` and stores them at `locator`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the handle of the derived key, or an error of type `Self::Error`.
+    #[allow(clippy::too_many_arguments)]
+    fn derive_to_vault(
+        &mut self,
+        vault: &mut Self::Vault,
+        password: &[u8],
+        salt: &[u8],
+        iterations: NonZeroU32,
+        len: usize,
+        locator: KeyLocator<'_>,
+        usage: KeyUsage,
+    ) -> Result<<Self::Vault as KeyVault>::KeyHandle, Self::Error>;
+}
@@ -0,0 +1,218 @@
+use core::fmt::Debug;
+
+/// Error kind.
+///
+/// This represents a common set of key vault errors. Implementations are
+/// free to define more specific or additional error types. However, by providing
+/// a mapping to these common errors, generic code can still react to them.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// No key is stored at the requested slot or label.
+    KeyNotFound,
+
+    /// The requested slot already holds a key.
+    SlotOccupied,
+
+    /// The vault has no free slot left.
+    StorageFull,
+
+    /// The key exists but its usage policy forbids the requested operation.
+    UsageNotPermitted,
+
+    /// The supplied key material is malformed or has the wrong size.
+    InvalidKey,
+
+    /// A wrapped key failed its integrity check during unwrapping.
+    UnwrapFailed,
+
+    /// The key is locked and cannot be exported, wrapped or erased.
+    KeyLocked,
+
+    /// The hardware is busy and cannot process the request.
+    Busy,
+
+    /// General hardware failure.
+    HardwareFailure,
+}
+
+pub trait Error: core::fmt::Debug {
+    /// Convert error to a generic error kind
+    ///
+    /// By using this method, errors freely defined by HAL implementations
+    /// can be converted to a set of generic errors upon which generic
+    /// code can act.
+    fn kind(&self) -> ErrorKind;
+}
+
+impl Error for core::convert::Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// Locates a key inside the vault.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum KeyLocator<'a> {
+    /// Hardware key slot number.
+    Slot(u32),
+    /// Implementation-defined key label.
+    Label(&'a str),
+}
+
+/// Operations a stored key may be used for.
+///
+/// Values combine with `|`, e.g. `KeyUsage::SIGN | KeyUsage::DERIVE`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct KeyUsage(pub u32);
+
+impl KeyUsage {
+    pub const SIGN: Self = Self(1 << 0);
+    pub const VERIFY: Self = Self(1 << 1);
+    pub const ENCRYPT: Self = Self(1 << 2);
+    pub const DECRYPT: Self = Self(1 << 3);
+    pub const WRAP: Self = Self(1 << 4);
+    pub const DERIVE: Self = Self(1 << 5);
+    /// The key may leave the vault in plaintext.
+    pub const EXPORT: Self = Self(1 << 6);
+
+    /// Returns whether every usage in `other` is also set in `self`.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl core::ops::BitOr for KeyUsage {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// Storage of opaque keys.
+///
+/// Keys never leave the vault; callers refer to them through
+/// [`KeyVault::KeyHandle`]. Crypto traits whose key is an associated type,
+/// such as `EcdsaSign::PrivateKey`, can be implemented with the handle as that
+/// type to express "sign with key slot 3" without handling key material.
+pub trait KeyVault: ErrorType {
+    /// Opaque reference to a key held by the vault.
+    type KeyHandle: Copy;
+
+    /// Resolves a slot or label to a handle.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the handle, or an error of kind [`ErrorKind::KeyNotFound`].
+    fn lookup(&self, locator: KeyLocator<'_>) -> Result<Self::KeyHandle, Self::Error>;
+
+    /// Returns the usage policy attached to a key.
+    fn usage(&self, key: Self::KeyHandle) -> Result<KeyUsage, Self::Error>;
+
+    /// Erases a key and frees its slot. The handle must not be used afterwards.
+    fn erase(&mut self, key: Self::KeyHandle) -> Result<(), Self::Error>;
+}
+
+/// Import of plaintext key material into the vault.
+pub trait KeyImport: KeyVault {
+    /// Stores `key_material` at `locator` with the given usage policy.
+    ///
+    /// # Parameters
+    ///
+    /// - `locator`: Where to store the key.
+    /// - `usage`: Operations the key may be used for.
+    /// - `key_material`: Raw key bytes, in an implementation-defined encoding.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the handle of the stored key, or an error of type `Self::Error`.
+    fn import(
+        &mut self,
+        locator: KeyLocator<'_>,
+        usage: KeyUsage,
+        key_material: &[u8],
+    ) -> Result<Self::KeyHandle, Self::Error>;
+}
+
+/// Generation of keys inside the vault.
+pub trait KeyGenerate: KeyVault {
+    /// Describes the algorithm and size of the key to generate.
+    type KeyType;
+
+    /// Generates a new key at `locator`. The key material never leaves the vault.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the handle of the generated key, or an error of type `Self::Error`.
+    fn generate(
+        &mut self,
+        locator: KeyLocator<'_>,
+        key_type: &Self::KeyType,
+        usage: KeyUsage,
+    ) -> Result<Self::KeyHandle, Self::Error>;
+}
+
+/// Wrapping of keys for storage outside the vault.
+pub trait KeyWrap: KeyVault {
+    /// Encrypts `key` under `wrapping_key` into `out`.
+    ///
+    /// `wrapping_key` must carry [`KeyUsage::WRAP`].
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes written to `out`, or an error of type `Self::Error`.
+    fn wrap(
+        &mut self,
+        wrapping_key: Self::KeyHandle,
+        key: Self::KeyHandle,
+        out: &mut [u8],
+    ) -> Result<usize, Self::Error>;
+
+    /// Decrypts a blob produced by [`KeyWrap::wrap`] and stores the key at `locator`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the handle of the unwrapped key, or an error of kind
+    /// [`ErrorKind::UnwrapFailed`] when the blob fails its integrity check.
+    fn unwrap(
+        &mut self,
+        wrapping_key: Self::KeyHandle,
+        locator: KeyLocator<'_>,
+        usage: KeyUsage,
+        wrapped: &[u8],
+    ) -> Result<Self::KeyHandle, Self::Error>;
+}
+
+/// Where a key generator puts the private key.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum KeyDestination<'a> {
+    /// Return the key material to the caller.
+    Raw,
+    /// Keep the key in hardware at `locator` and return a handle.
+    Hardware {
+        locator: KeyLocator<'a>,
+        usage: KeyUsage,
+    },
+}
+
+/// Private key produced by a key generator, as requested by [`KeyDestination`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum GeneratedKey<K, H> {
+    /// Raw key material.
+    Raw(K),
+    /// Handle to a key kept in hardware.
+    Handle(H),
+}
+
+/// Key pair produced by a key generator.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct GeneratedKeyPair<K, H, P> {
+    pub private_key: GeneratedKey<K, H>,
+    pub public_key: P,
+}
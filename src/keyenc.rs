@@ -0,0 +1,144 @@
+//! DER encoding and decoding of RSA and elliptic-curve keys.
+//!
+//! Keys are read from and written to caller-provided buffers, so projects
+//! exchanging keys with provisioning tools share one interface regardless of
+//! the ASN.1 implementation behind it. Buffers holding encoded private keys
+//! should be zeroized by the caller once they are no longer needed.
+
+use core::fmt::Debug;
+
+use crate::ecdsa::EcdsaTypes;
+use crate::rsa::RsaKeys;
+
+/// Error kind.
+///
+/// This represents a common set of key encoding errors. Implementations are
+/// free to define more specific or additional error types. However, by providing
+/// a mapping to these common errors, generic code can still react to them.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The DER encoding is invalid.
+    Malformed,
+
+    /// The key algorithm or the format is not supported.
+    UnsupportedAlgorithm,
+
+    /// The key belongs to a different curve than the implementation's.
+    CurveMismatch,
+
+    /// The output buffer is too small.
+    BufferTooSmall,
+
+    /// A different error occurred.
+    Other,
+}
+
+pub trait Error: core::fmt::Debug {
+    /// Convert error to a generic error kind
+    ///
+    /// By using this method, errors freely defined by HAL implementations
+    /// can be converted to a set of generic errors upon which generic
+    /// code can act.
+    fn kind(&self) -> ErrorKind;
+}
+
+impl Error for core::convert::Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// DER container of a private key.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum PrivateKeyFormat {
+    /// `RSAPrivateKey` (RFC 8017).
+    Pkcs1,
+    /// `ECPrivateKey` (RFC 5915), with the curve OID in its parameters.
+    Sec1,
+    /// `PrivateKeyInfo` (RFC 5208), wrapping either of the above.
+    Pkcs8,
+}
+
+/// DER container of a public key.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum PublicKeyFormat {
+    /// `RSAPublicKey` (RFC 8017).
+    Pkcs1,
+    /// `SubjectPublicKeyInfo` (RFC 5280), as embedded in certificates.
+    Spki,
+}
+
+/// Decoding of RSA keys.
+pub trait RsaKeyDecode: ErrorType + RsaKeys {
+    /// Parses a [`PrivateKeyFormat::Pkcs1`] or [`PrivateKeyFormat::Pkcs8`] private key.
+    fn decode_private_key(&self, der: &[u8], format: PrivateKeyFormat) -> Result<Self::PrivateKey, Self::Error>;
+
+    /// Parses a public key.
+    fn decode_public_key(&self, der: &[u8], format: PublicKeyFormat) -> Result<Self::PublicKey, Self::Error>;
+}
+
+/// Encoding of RSA keys.
+pub trait RsaKeyEncode: ErrorType + RsaKeys {
+    /// Encodes `key` as [`PrivateKeyFormat::Pkcs1`] or [`PrivateKeyFormat::Pkcs8`] into `out`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes written, or an error of kind
+    /// [`ErrorKind::BufferTooSmall`].
+    fn encode_private_key(
+        &self,
+        key: &Self::PrivateKey,
+        format: PrivateKeyFormat,
+        out: &mut [u8],
+    ) -> Result<usize, Self::Error>;
+
+    /// Encodes `key` into `out`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes written.
+    fn encode_public_key(&self, key: &Self::PublicKey, format: PublicKeyFormat, out: &mut [u8]) -> Result<usize, Self::Error>;
+}
+
+/// Decoding of elliptic-curve keys for the implementation's curve.
+pub trait EcKeyDecode: ErrorType + EcdsaTypes {
+    /// Parses a [`PrivateKeyFormat::Sec1`] or [`PrivateKeyFormat::Pkcs8`] private key.
+    ///
+    /// Fails with [`ErrorKind::CurveMismatch`] if the encoded curve is not `Self::Curve`.
+    fn decode_private_key(&self, der: &[u8], format: PrivateKeyFormat) -> Result<Self::PrivateKey, Self::Error>;
+
+    /// Parses a [`PublicKeyFormat::Spki`] public key.
+    ///
+    /// Only checks the encoding; use [`crate::ecdsa::EcdsaPublicKeyValidate`]
+    /// before trusting the point.
+    fn decode_public_key(&self, der: &[u8], format: PublicKeyFormat) -> Result<Self::PublicKey, Self::Error>;
+}
+
+/// Encoding of elliptic-curve keys.
+pub trait EcKeyEncode: ErrorType + EcdsaTypes {
+    /// Encodes `key` as [`PrivateKeyFormat::Sec1`] or [`PrivateKeyFormat::Pkcs8`] into `out`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes written, or an error of kind
+    /// [`ErrorKind::BufferTooSmall`].
+    fn encode_private_key(
+        &self,
+        key: &Self::PrivateKey,
+        format: PrivateKeyFormat,
+        out: &mut [u8],
+    ) -> Result<usize, Self::Error>;
+
+    /// Encodes `key` as [`PublicKeyFormat::Spki`] into `out`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes written.
+    fn encode_public_key(&self, key: &Self::PublicKey, format: PublicKeyFormat, out: &mut [u8]) -> Result<usize, Self::Error>;
+}
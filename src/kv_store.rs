@@ -0,0 +1,93 @@
+//! Small persistent records, e.g. service configuration.
+//!
+//! The traits say nothing about layout or wear leveling; implementations
+//! typically keep a log of records on a [`crate::block_device::BlockDevice`]
+//! and garbage collect it behind the scenes.
+
+pub use crate::error::{Error, ErrorKind};
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// Result of the integrity check on a stored record.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum CrcStatus {
+    /// The record's CRC matches its contents.
+    Valid,
+    /// The record's CRC does not match; the value read may be damaged.
+    Mismatch,
+}
+
+/// A record returned by [`KvStore::get`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Record {
+    /// Length of the stored value in bytes.
+    pub len: usize,
+    pub crc: CrcStatus,
+}
+
+/// Get, put and delete of records identified by byte-string keys.
+pub trait KvStore: ErrorType {
+    /// Longest supported key, in bytes.
+    const MAX_KEY_LEN: usize;
+
+    /// Longest supported value, in bytes.
+    const MAX_VALUE_LEN: usize;
+
+    /// Reads the value stored under `key` into `buf`.
+    ///
+    /// If `buf` is shorter than the value, only the first `buf.len()` bytes
+    /// are copied; [`Record::len`] always reports the full length.
+    ///
+    /// A record whose CRC does not match is still returned, with
+    /// [`CrcStatus::Mismatch`], so the caller can choose to fall back to defaults.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the record, or an error of kind [`ErrorKind::NotFound`].
+    fn get(&mut self, key: &[u8], buf: &mut [u8]) -> Result<Record, Self::Error>;
+
+    /// Stores `value` under `key`, replacing any previous value.
+    ///
+    /// The replacement is atomic: after a power loss, `get` returns either
+    /// the old or the new value, never a mix.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success, or an error of kind [`ErrorKind::Full`].
+    fn put(&mut self, key: &[u8], value: &[u8]) -> Result<(), Self::Error>;
+
+    /// Removes the record stored under `key`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success, or an error of kind [`ErrorKind::NotFound`].
+    fn delete(&mut self, key: &[u8]) -> Result<(), Self::Error>;
+
+    /// Returns whether a record is stored under `key`.
+    fn contains(&mut self, key: &[u8]) -> Result<bool, Self::Error> {
+        match self.get(key, &mut []) {
+            Ok(_) => Ok(true),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Enumeration of stored keys.
+pub trait KvIterate: KvStore {
+    /// Iteration position. `Default` starts at the first key.
+    type Cursor: Default;
+
+    /// Copies the key at `cursor` into `key` and advances `cursor`.
+    ///
+    /// Keys are returned in an implementation-defined order. Modifying the
+    /// store during iteration may cause keys to be skipped or repeated.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the key length, or `None` once all keys were returned.
+    fn next_key(&mut self, cursor: &mut Self::Cursor, key: &mut [u8]) -> Result<Option<usize>, Self::Error>;
+}
@@ -1,10 +1,71 @@
 #![no_std]
 #![deny(unsafe_code)]
 
+pub mod error;
+
+pub mod common;
+pub mod bignum;
 pub mod rsa;
 pub mod ecdsa;
+pub mod ecdh;
+pub mod eddsa;
+pub mod pqc;
 pub mod mac;
 pub mod digest;
+pub mod aead;
+pub mod symm_cipher;
+pub mod key_vault;
+pub mod secure_element;
+pub mod kdf;
+pub mod rand;
+pub mod cert;
+pub mod csr;
+pub mod keyenc;
+pub mod measure;
+pub mod integrity;
+pub mod suite;
+
 
+pub mod dma;
+pub mod block_device;
+pub mod otp;
+pub mod otp_aspeed;
+pub mod monotonic_counter;
+pub mod flash_partition;
+pub mod kv_store;
+pub mod i3c_master;
+pub mod i3c_target;
+pub mod rx_grant;
+pub mod rtc;
+pub mod shared_bus;
+pub mod i2c_target;
+pub mod smbus_target;
+pub mod regmap_target;
+pub mod spi_master;
+pub mod spi_target;
+pub mod gpio;
+pub mod debug_port;
+pub mod adc;
+pub mod pwm;
+pub mod tach;
+pub mod thermal;
+pub mod can;
+pub mod serial;
+pub mod mailbox;
+pub mod espi_target;
+pub mod time;
+pub mod metrics;
+pub mod system_control;
+pub mod event_queue;
+pub mod hotplug;
+pub mod update;
 
-pub mod block_device;
\ No newline at end of file
+pub mod asynch;
+pub mod nb;
+#[cfg(feature = "fault-injection")]
+pub mod fault;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
+#[cfg(feature = "embedded-hal-compat")]
+pub mod embedded_hal_compat;
+pub mod messaging;
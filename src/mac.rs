@@ -1,3 +1,9 @@
+pub mod cmac;
+pub mod hmac;
+pub mod kmac;
+
+use crate::common::{ConstantTimeEq, Operation, Zeroize};
+
 /// Error kind.
 ///
 /// This represents a common set of digest operation errors. Implementations are
@@ -38,6 +44,9 @@ pub enum ErrorKind {
 
     /// The hash computation context has not been initialized.
     NotInitialized,
+
+    /// The computed MAC tag does not match the expected tag.
+    VerificationFailed,
 }
 
 pub trait Error: core::fmt::Debug {
@@ -68,6 +77,11 @@ pub trait ErrorType {
 /// Message Authentication algorithm
 pub trait Mac: ErrorType {
     type InitParams;
+
+    /// Tag returned by [`Mac::finalize_tag`]. Compare tags with
+    /// [`ConstantTimeEq`], never with `==`.
+    type Tag: AsRef<[u8]> + ConstantTimeEq + Zeroize;
+
     /// Init instance of the crypto function with the given context.
     ///
     /// # Parameters
@@ -119,8 +133,17 @@ pub trait Mac: ErrorType {
     /// A `Result` indicating success or failure. On success, returns `Ok(())`. On failure, returns a `CryptoError`.    
     fn finalize(&mut self, out: &mut [u8]) -> Result<(), Self::Error>;
 
+    /// Finalize the computation and return the tag.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the tag, or an error of type `Self::Error`.
+    fn finalize_tag(&mut self) -> Result<Self::Tag, Self::Error>;
+
     /// Verifies if the given MAC tag matches the expected result.
     ///
+    /// The comparison runs in constant time, as with [`ConstantTimeEq`].
+    ///
     /// # Parameters
     ///
     /// - `tag`: The MAC tag to be verified.
@@ -129,4 +152,51 @@ pub trait Mac: ErrorType {
     ///
     /// A `Result` indicating success or failure. On success, returns `Ok(())`. On failure, returns an error of type `Self::Error`.    
     fn verify(&mut self, tag: &[u8]) -> Result<(), Self::Error>;
+
+    /// Computes the MAC of a complete message in one call.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The key to be used.
+    /// - `data`: The complete message.
+    /// - `out`: A mutable slice to store the tag. The length of the slice must be at least `MAX_OUTPUT_SIZE`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or failure. On success, returns `Ok(())`. On failure, returns an error of type `Self::Error`.
+    fn mac(&mut self, key: &[u8], data: &mut [u8], out: &mut [u8]) -> Result<(), Self::Error> {
+        self.set_key(key)?;
+        self.update(data)?;
+        self.finalize(out)
+    }
+
+    /// Computes the MAC of a complete message and returns the tag as an array.
+    fn mac_array<const N: usize>(&mut self, key: &[u8], data: &mut [u8]) -> Result<[u8; N], Self::Error> {
+        let mut tag = [0u8; N];
+        self.mac(key, data, &mut tag)?;
+        Ok(tag)
+    }
+
+    /// Verifies the MAC tag of a complete message in one call.
+    fn mac_verify(&mut self, key: &[u8], data: &mut [u8], tag: &[u8]) -> Result<(), Self::Error> {
+        self.set_key(key)?;
+        self.update(data)?;
+        self.verify(tag)
+    }
+}
+
+/// MAC updates and finalization running on an interrupt-driven engine.
+pub trait MacStart: Mac {
+    type UpdateOperation<'a>: Operation<Output = (), Error = Self::Error>
+    where
+        Self: 'a;
+    type FinalizeOperation<'a>: Operation<Output = Self::Tag, Error = Self::Error>
+    where
+        Self: 'a;
+
+    /// Starts absorbing `input`. Completes like [`Mac::update`].
+    fn start_update<'a>(&'a mut self, input: &'a [u8]) -> Result<Self::UpdateOperation<'a>, Self::Error>;
+
+    /// Starts finalization. Completes like [`Mac::finalize_tag`].
+    fn start_finalize(&mut self) -> Result<Self::FinalizeOperation<'_>, Self::Error>;
 }
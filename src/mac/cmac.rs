@@ -0,0 +1,17 @@
+//! CMAC (NIST SP 800-38B) over a block cipher.
+
+use crate::symm_cipher::{BlockCipher, KeyOf};
+
+use super::Mac;
+
+/// CMAC over [`Cmac::Cipher`].
+///
+/// [`Mac::set_key`] takes raw key bytes; [`Cmac::set_cipher_key`] accepts the
+/// cipher's own key, which may be a handle to a hardware-held key.
+/// [`Mac::finalize`] may truncate the tag to the length of its output buffer.
+pub trait Cmac: Mac {
+    type Cipher: BlockCipher;
+
+    /// Sets the key and resets the computation.
+    fn set_cipher_key(&mut self, key: KeyOf<'_, Self::Cipher>) -> Result<(), Self::Error>;
+}
@@ -0,0 +1,366 @@
+//! HMAC (RFC 2104) built from any [`Digest`] implementation.
+//!
+//! A digest opts in by implementing [`HmacFromDigest`], which only declares
+//! its block and output sizes; [`Hmac`] then provides [`Mac`] on top of it.
+
+use crate::common::{ConstantTimeEq, Zeroize};
+use crate::digest::{self, Digest};
+
+use super::{ErrorKind, ErrorType, Mac};
+
+/// Largest digest block size supported by [`Hmac`], in bytes (SHA3-224).
+pub const MAX_BLOCK_SIZE: usize = 144;
+
+/// Largest digest output size supported by [`Hmac`], in bytes (SHA-512).
+pub const MAX_OUTPUT_SIZE: usize = 64;
+
+const IPAD: u8 = 0x36;
+const OPAD: u8 = 0x5c;
+
+/// A digest that HMAC can be constructed from.
+pub trait HmacFromDigest: Digest + Sized {
+    /// Input block size of the digest, in bytes.
+    const BLOCK_SIZE: usize;
+
+    /// Output size of the digest, in bytes.
+    const OUTPUT_SIZE: usize;
+
+    /// Wraps the digest into an HMAC instance.
+    fn into_hmac(self) -> Hmac<Self> {
+        Hmac::new(self)
+    }
+}
+
+/// Error returned by [`Hmac`].
+#[derive(Debug)]
+pub enum HmacError<E> {
+    /// The underlying digest failed.
+    Digest(E),
+    /// The output buffer is shorter than the digest output.
+    InvalidOutputSize,
+    /// The tag passed to `verify` does not match.
+    VerificationFailed,
+}
+
+impl<E: digest::Error> super::Error for HmacError<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            HmacError::InvalidOutputSize => ErrorKind::InvalidOutputSize,
+            HmacError::VerificationFailed => ErrorKind::VerificationFailed,
+            HmacError::Digest(e) => match e.kind() {
+                digest::ErrorKind::InvalidInputLength => ErrorKind::InvalidInputLength,
+                digest::ErrorKind::UnsupportedAlgorithm => ErrorKind::UnsupportedAlgorithm,
+                digest::ErrorKind::MemoryAllocationFailure => ErrorKind::MemoryAllocationFailure,
+                digest::ErrorKind::InitializationError => ErrorKind::InitializationError,
+                digest::ErrorKind::UpdateError => ErrorKind::UpdateError,
+                digest::ErrorKind::FinalizationError => ErrorKind::FinalizationError,
+                digest::ErrorKind::Busy => ErrorKind::HardwareAcceleratorBusy,
+                digest::ErrorKind::InvalidOutputSize => ErrorKind::InvalidOutputSize,
+                digest::ErrorKind::PermissionDenied => ErrorKind::PermissionDenied,
+                digest::ErrorKind::NotInitialized => ErrorKind::NotInitialized,
+                _ => ErrorKind::HardwareFailure,
+            },
+        }
+    }
+}
+
+/// Buffer of key material, cleared when dropped.
+struct KeyBlock([u8; MAX_BLOCK_SIZE]);
+
+impl Drop for KeyBlock {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+/// Tag computed by [`Hmac`], cleared when dropped.
+pub struct HmacTag {
+    bytes: [u8; MAX_OUTPUT_SIZE],
+    len: usize,
+}
+
+impl AsRef<[u8]> for HmacTag {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+}
+
+impl ConstantTimeEq for HmacTag {
+    fn ct_eq(&self, other: &Self) -> bool {
+        self.as_ref().ct_eq(other.as_ref())
+    }
+}
+
+impl Zeroize for HmacTag {
+    fn zeroize(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
+impl Drop for HmacTag {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+/// HMAC over the digest `D`.
+///
+/// The key block is cleared on drop. Zeroizing also clears the digest, which
+/// holds state derived from the key.
+pub struct Hmac<D> {
+    digest: D,
+    key_block: KeyBlock,
+}
+
+impl<D: HmacFromDigest> Hmac<D> {
+    pub fn new(digest: D) -> Self {
+        const {
+            assert!(D::BLOCK_SIZE <= MAX_BLOCK_SIZE);
+            assert!(D::OUTPUT_SIZE <= MAX_OUTPUT_SIZE);
+        }
+        Self {
+            digest,
+            key_block: KeyBlock([0; MAX_BLOCK_SIZE]),
+        }
+    }
+
+    /// Returns the underlying digest.
+    pub fn into_inner(self) -> D {
+        self.digest
+    }
+
+    /// Resets the digest and absorbs the key block XORed with `pad`.
+    fn start(&mut self, pad: u8) -> Result<(), D::Error> {
+        let mut block = KeyBlock([0; MAX_BLOCK_SIZE]);
+        for (b, k) in block.0.iter_mut().zip(&self.key_block.0[..D::BLOCK_SIZE]) {
+            *b = k ^ pad;
+        }
+        self.digest.reset()?;
+        self.digest.update(&mut block.0[..D::BLOCK_SIZE])
+    }
+
+    fn compute(&mut self) -> Result<HmacTag, D::Error> {
+        let mut inner = HmacTag {
+            bytes: [0; MAX_OUTPUT_SIZE],
+            len: 0,
+        };
+        inner.len = self.digest.finalize(&mut inner.bytes)?;
+        self.start(OPAD)?;
+        self.digest.update(&mut inner.bytes[..inner.len])?;
+        let mut tag = HmacTag {
+            bytes: [0; MAX_OUTPUT_SIZE],
+            len: 0,
+        };
+        tag.len = self.digest.finalize(&mut tag.bytes)?;
+        Ok(tag)
+    }
+}
+
+impl<D: Zeroize> Zeroize for Hmac<D> {
+    /// Clears the stored key and the digest state. A key must be set again
+    /// before further use.
+    fn zeroize(&mut self) {
+        self.key_block.0.zeroize();
+        self.digest.zeroize();
+    }
+}
+
+impl<D: HmacFromDigest> ErrorType for Hmac<D> {
+    type Error = HmacError<D::Error>;
+}
+
+impl<D: HmacFromDigest> Mac for Hmac<D> {
+    type InitParams = ();
+    type Tag = HmacTag;
+
+    fn init(_init_params: Self::InitParams) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_key(&mut self, key: &[u8]) -> Result<(), Self::Error> {
+        self.key_block.0.zeroize();
+        if key.len() > D::BLOCK_SIZE {
+            self.digest.reset().map_err(HmacError::Digest)?;
+            let mut chunk = KeyBlock([0; MAX_BLOCK_SIZE]);
+            for part in key.chunks(MAX_BLOCK_SIZE) {
+                chunk.0[..part.len()].copy_from_slice(part);
+                self.digest
+                    .update(&mut chunk.0[..part.len()])
+                    .map_err(HmacError::Digest)?;
+            }
+            let len = self
+                .digest
+                .finalize(&mut self.key_block.0)
+                .map_err(HmacError::Digest)?;
+            self.key_block.0[len..].fill(0);
+        } else {
+            self.key_block.0[..key.len()].copy_from_slice(key);
+        }
+        self.start(IPAD).map_err(HmacError::Digest)
+    }
+
+    fn update(&mut self, input: &mut [u8]) -> Result<(), Self::Error> {
+        self.digest.update(input).map_err(HmacError::Digest)
+    }
+
+    fn reset(&mut self) -> Result<(), Self::Error> {
+        self.start(IPAD).map_err(HmacError::Digest)
+    }
+
+    fn finalize(&mut self, out: &mut [u8]) -> Result<(), Self::Error> {
+        let tag = self.compute().map_err(HmacError::Digest)?;
+        out.get_mut(..tag.len)
+            .ok_or(HmacError::InvalidOutputSize)?
+            .copy_from_slice(tag.as_ref());
+        Ok(())
+    }
+
+    fn finalize_tag(&mut self) -> Result<Self::Tag, Self::Error> {
+        self.compute().map_err(HmacError::Digest)
+    }
+
+    fn verify(&mut self, tag: &[u8]) -> Result<(), Self::Error> {
+        let computed = self.compute().map_err(HmacError::Digest)?;
+        if computed.as_ref().ct_eq(tag) {
+            Ok(())
+        } else {
+            Err(HmacError::VerificationFailed)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::digest::{SoftSha256, SoftSha384, SoftSha512};
+
+    fn unhex<const N: usize>(hex: &str) -> [u8; N] {
+        assert_eq!(hex.len(), 2 * N);
+        let mut out = [0u8; N];
+        for (byte, pair) in out.iter_mut().zip(hex.as_bytes().chunks(2)) {
+            *byte = u8::from_str_radix(core::str::from_utf8(pair).unwrap(), 16).unwrap();
+        }
+        out
+    }
+
+    /// Computes the tag of `data` in one call, copying it into a mutable buffer.
+    fn tag<D: HmacFromDigest, const N: usize>(digest: D, key: &[u8], data: &[u8]) -> [u8; N] {
+        let mut buf = [0u8; 64];
+        buf[..data.len()].copy_from_slice(data);
+        digest.into_hmac().mac_array(key, &mut buf[..data.len()]).unwrap()
+    }
+
+    const HI_THERE: &[u8] = b"Hi There";
+    const LARGE_KEY_DATA: &[u8] = b"Test Using Larger Than Block-Size Key - Hash Key First";
+
+    #[test]
+    fn rfc4231_case_1() {
+        let key = [0x0b; 20];
+        assert_eq!(
+            tag::<_, 32>(SoftSha256::default(), &key, HI_THERE),
+            unhex("b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7")
+        );
+        assert_eq!(
+            tag::<_, 48>(SoftSha384::default(), &key, HI_THERE),
+            unhex(
+                "afd03944d84895626b0825f4ab46907f15f9dadbe4101ec682aa034c7cebc59c\
+                 faea9ea9076ede7f4af152e8b2fa9cb6"
+            )
+        );
+        assert_eq!(
+            tag::<_, 64>(SoftSha512::default(), &key, HI_THERE),
+            unhex(
+                "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cde\
+                 daa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854"
+            )
+        );
+    }
+
+    #[test]
+    fn rfc4231_case_2() {
+        assert_eq!(
+            tag::<_, 32>(SoftSha256::default(), b"Jefe", b"what do ya want for nothing?"),
+            unhex("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843")
+        );
+    }
+
+    #[test]
+    fn rfc4231_case_6_hashes_long_keys() {
+        let key = [0xaa; 131];
+        assert_eq!(
+            tag::<_, 32>(SoftSha256::default(), &key, LARGE_KEY_DATA),
+            unhex("60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54")
+        );
+        assert_eq!(
+            tag::<_, 64>(SoftSha512::default(), &key, LARGE_KEY_DATA),
+            unhex(
+                "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f352\
+                 6b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598"
+            )
+        );
+    }
+
+    #[test]
+    fn keys_longer_than_the_chunk_buffer() {
+        let key = [0xaa; 200];
+        assert_eq!(
+            tag::<_, 32>(SoftSha256::default(), &key, b"message"),
+            unhex("a149a5d170d99c8ef195d2816bdcd897716f95d7e7b5fdd7668e95d2cb765fc8")
+        );
+    }
+
+    #[test]
+    fn split_updates_match_one_shot() {
+        let mut hmac = SoftSha256::default().into_hmac();
+        hmac.set_key(b"Jefe").unwrap();
+        let mut data = *b"what do ya want for nothing?";
+        let (head, tail) = data.split_at_mut(7);
+        hmac.update(head).unwrap();
+        hmac.update(tail).unwrap();
+        let tag = hmac.finalize_tag().unwrap();
+        assert_eq!(
+            tag.as_ref(),
+            unhex::<32>("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843")
+        );
+    }
+
+    #[test]
+    fn reset_restarts_with_the_same_key() {
+        let mut hmac = SoftSha256::default().into_hmac();
+        hmac.set_key(&[0x0b; 20]).unwrap();
+        hmac.update(&mut [1, 2, 3]).unwrap();
+        hmac.reset().unwrap();
+        let mut data = *b"Hi There";
+        hmac.update(&mut data).unwrap();
+        let expected = unhex::<32>("b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7");
+        hmac.verify(&expected).unwrap();
+    }
+
+    #[test]
+    fn verify_rejects_wrong_and_truncated_tags() {
+        let mut expected = unhex::<32>("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843");
+        let mut hmac = SoftSha256::default().into_hmac();
+        let mut data = *b"what do ya want for nothing?";
+        hmac.mac_verify(b"Jefe", &mut data, &expected).unwrap();
+
+        assert!(matches!(
+            hmac.mac_verify(b"Jefe", &mut data, &expected[..16]),
+            Err(HmacError::VerificationFailed)
+        ));
+        expected[31] ^= 1;
+        assert!(matches!(
+            hmac.mac_verify(b"Jefe", &mut data, &expected),
+            Err(HmacError::VerificationFailed)
+        ));
+    }
+
+    #[test]
+    fn finalize_rejects_short_output() {
+        let mut hmac = SoftSha256::default().into_hmac();
+        let mut out = [0u8; 31];
+        assert!(matches!(
+            hmac.mac(b"Jefe", &mut [], &mut out),
+            Err(HmacError::InvalidOutputSize)
+        ));
+    }
+}
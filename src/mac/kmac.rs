@@ -0,0 +1,21 @@
+//! KMAC (NIST SP 800-185) over a Keccak XOF.
+
+use crate::digest::Xof;
+
+use super::Mac;
+
+/// KMAC128 or KMAC256 built on [`Kmac::Xof`].
+///
+/// The requested output length is part of the KMAC input, so
+/// [`Mac::finalize`] produces a tag of exactly the length of its output
+/// buffer, and tags of different lengths are unrelated.
+pub trait Kmac: Mac {
+    /// The underlying SHAKE implementation.
+    type Xof: Xof;
+
+    /// Sets the customization string `S`. Takes effect on the next `set_key` or `reset`.
+    fn set_customization(&mut self, customization: &[u8]) -> Result<(), Self::Error>;
+
+    /// Finalizes as KMACXOF and fills `out` with output of arbitrary length.
+    fn finalize_xof(&mut self, out: &mut [u8]) -> Result<(), Self::Error>;
+}
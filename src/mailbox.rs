@@ -0,0 +1,92 @@
+//! Hardware mailboxes between cores.
+//!
+//! A mailbox is a bank of shared registers plus a doorbell interrupt in each
+//! direction. The sender fills the registers and rings the doorbell; the
+//! receiver reads them and acknowledges, which frees the registers for the
+//! next message.
+
+pub use crate::error::{Error, ErrorKind};
+use crate::time::{Deadline, Monotonic};
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// One direction-agnostic mailbox channel.
+pub trait Mailbox: ErrorType {
+    /// Number of 32-bit data registers.
+    const REGISTER_COUNT: usize;
+
+    /// Writes outgoing data register `index`.
+    ///
+    /// Fails with `ErrorKind::OutOfBounds` if `index >= REGISTER_COUNT`.
+    fn write_register(&mut self, index: usize, value: u32) -> Result<(), Self::Error>;
+
+    /// Reads incoming data register `index`.
+    fn read_register(&mut self, index: usize) -> Result<u32, Self::Error>;
+
+    /// Rings the peer's doorbell.
+    fn ring_doorbell(&mut self) -> Result<(), Self::Error>;
+
+    /// Returns whether the peer has acknowledged the last doorbell.
+    fn is_acknowledged(&mut self) -> Result<bool, Self::Error>;
+
+    /// Blocks until the peer acknowledges the last doorbell.
+    ///
+    /// Fails with `ErrorKind::Timeout` if the peer does not respond in an
+    /// implementation-defined time.
+    fn wait_for_ack(&mut self) -> Result<(), Self::Error>;
+
+    /// Returns whether the peer has rung our doorbell.
+    fn is_doorbell_pending(&mut self) -> Result<bool, Self::Error>;
+
+    /// Acknowledges the peer's doorbell after its registers have been read.
+    fn acknowledge(&mut self) -> Result<(), Self::Error>;
+
+    /// Writes `words` to the data registers, rings the doorbell and waits for the acknowledgment.
+    fn send(&mut self, words: &[u32]) -> Result<(), Self::Error> {
+        for (index, &word) in words.iter().enumerate() {
+            self.write_register(index, word)?;
+        }
+        self.ring_doorbell()?;
+        self.wait_for_ack()
+    }
+
+    /// Polls for the acknowledgment of the last doorbell until `deadline` passes.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing `true` once acknowledged, or `false` if the
+    /// deadline passed first.
+    fn wait_for_ack_with_deadline(
+        &mut self,
+        clock: &impl Monotonic,
+        deadline: Deadline,
+    ) -> Result<bool, Self::Error> {
+        loop {
+            if self.is_acknowledged()? {
+                return Ok(true);
+            }
+            if deadline.has_expired(clock) {
+                return Ok(false);
+            }
+        }
+    }
+
+    /// Like [`Mailbox::send`], but gives up waiting for the acknowledgment at `deadline`.
+    ///
+    /// See [`Mailbox::wait_for_ack_with_deadline`] for the return value.
+    fn send_with_deadline(
+        &mut self,
+        words: &[u32],
+        clock: &impl Monotonic,
+        deadline: Deadline,
+    ) -> Result<bool, Self::Error> {
+        for (index, &word) in words.iter().enumerate() {
+            self.write_register(index, word)?;
+        }
+        self.ring_doorbell()?;
+        self.wait_for_ack_with_deadline(clock, deadline)
+    }
+}
@@ -0,0 +1,105 @@
+//! Measurement registers for measured boot.
+//!
+//! Registers can only be extended (`R = H(R || measurement)`) and locked,
+//! never written directly, so later boot stages cannot erase what earlier
+//! stages recorded.
+
+use core::fmt::Debug;
+
+use crate::digest::Digest;
+
+/// Error kind.
+///
+/// This represents a common set of measurement errors. Implementations are
+/// free to define more specific or additional error types. However, by providing
+/// a mapping to these common errors, generic code can still react to them.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The register index does not exist.
+    InvalidRegister,
+
+    /// The register is locked against further extends.
+    Locked,
+
+    /// The measurement or output buffer has the wrong length.
+    InvalidLength,
+
+    /// Hashing the measured data failed.
+    DigestFailure,
+
+    /// The hardware is busy and cannot process the operation.
+    Busy,
+
+    /// General hardware failure during the operation.
+    HardwareFailure,
+}
+
+pub trait Error: core::fmt::Debug {
+    /// Convert error to a generic error kind
+    ///
+    /// By using this method, errors freely defined by HAL implementations
+    /// can be converted to a set of generic errors upon which generic
+    /// code can act.
+    fn kind(&self) -> ErrorKind;
+}
+
+impl Error for core::convert::Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// A bank of extend-only measurement registers.
+pub trait MeasurementRegisters: ErrorType {
+    /// Identifies a register, e.g. a PCR number.
+    type RegisterIndex: Copy;
+
+    /// Size of a register value and of a measurement, in bytes.
+    const DIGEST_SIZE: usize;
+
+    /// Copies the value of register `index` into `out`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes written.
+    fn read(&self, index: Self::RegisterIndex, out: &mut [u8]) -> Result<usize, Self::Error>;
+
+    /// Extends register `index` with a precomputed `measurement`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success, or an error of kind [`ErrorKind::Locked`].
+    fn extend(&mut self, index: Self::RegisterIndex, measurement: &[u8]) -> Result<(), Self::Error>;
+
+    /// Locks register `index` against further extends until the next reset.
+    fn lock(&mut self, index: Self::RegisterIndex) -> Result<(), Self::Error>;
+
+    fn is_locked(&self, index: Self::RegisterIndex) -> Result<bool, Self::Error>;
+}
+
+/// Hashing a region and extending a register in one operation.
+pub trait Measure: MeasurementRegisters {
+    /// Digest used to hash measured regions.
+    type Digest: Digest;
+
+    /// Hashes `data` and extends register `index` with the result.
+    ///
+    /// The digest never leaves the implementation, so no code can run
+    /// between hashing and extending to substitute a different value.
+    ///
+    /// # Parameters
+    ///
+    /// - `index`: The register to extend.
+    /// - `data`: The region to measure, e.g. the next boot stage image.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or failure.
+    fn measure(&mut self, index: Self::RegisterIndex, data: &[u8]) -> Result<(), Self::Error>;
+}
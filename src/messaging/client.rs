@@ -0,0 +1,442 @@
+//! Requester side of request/response messaging.
+
+pub use super::common::{
+    Decode, Encode, Error, ErrorKind, ErrorType, IdempotencyKey, Priority, RequestHeader, ServiceId,
+};
+
+use core::time::Duration;
+
+use crate::time::{Deadline, DelayNs, Monotonic};
+
+/// Sends typed requests to services and waits for their responses.
+pub trait Client: ErrorType {
+    /// Sends `request` to `service` and blocks until the response arrives.
+    ///
+    /// # Parameters
+    ///
+    /// - `service`: The destination service.
+    /// - `request`: The request message.
+    /// - `response_buf`: Receive buffer. The decoded response may borrow from it.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the decoded response, or an error of type `Self::Error`.
+    fn request<'r, Req, Resp>(
+        &mut self,
+        service: ServiceId,
+        request: &Req,
+        response_buf: &'r mut [u8],
+    ) -> Result<Resp, Self::Error>
+    where
+        Req: Encode,
+        Resp: Decode<'r>;
+}
+
+/// Occupancy of an outbound message queue.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct QueueDepth {
+    /// Messages queued or reserved but not yet handed to the transport.
+    pub queued: usize,
+    /// Maximum number of messages the queue holds.
+    pub capacity: usize,
+}
+
+impl QueueDepth {
+    /// Returns the number of free slots.
+    pub fn available(&self) -> usize {
+        self.capacity.saturating_sub(self.queued)
+    }
+}
+
+/// A reserved slot in an outbound queue, obtained from [`FlowControl::reserve`].
+///
+/// Sending through a permit cannot fail for lack of queue space. Dropping the
+/// permit without sending returns the slot.
+pub trait SendPermit: ErrorType {
+    /// Encodes `message` for `service` into the reserved slot.
+    fn send<Req>(self, service: ServiceId, message: &Req) -> Result<(), Self::Error>
+    where
+        Req: Encode;
+}
+
+/// Credit-based flow control for one-way messages.
+///
+/// Lets a sender under memory pressure decide up front whether to wait,
+/// shed the message or retry later, instead of blocking indefinitely or
+/// having the transport drop it.
+pub trait FlowControl: ErrorType {
+    type Permit<'a>: SendPermit<Error = Self::Error>
+    where
+        Self: 'a;
+
+    /// Blocks until a queue slot is free and reserves it.
+    fn reserve(&mut self) -> Result<Self::Permit<'_>, Self::Error>;
+
+    /// Reserves a queue slot without blocking.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the permit, or `None` if the queue is full.
+    fn try_reserve(&mut self) -> Result<Option<Self::Permit<'_>>, Self::Error>;
+
+    /// Queues `message` for `service` if a slot is free.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success, or an error of kind [`ErrorKind::Busy`] if the queue is full.
+    fn try_send<Req>(&mut self, service: ServiceId, message: &Req) -> Result<(), Self::Error>
+    where
+        Req: Encode;
+
+    /// Returns the current occupancy of the outbound queue.
+    fn queue_depth(&self) -> QueueDepth;
+}
+
+/// Per-message priority on send.
+///
+/// Each [`Priority`] has its own outbound queue, so a full bulk queue does
+/// not hold up higher-priority messages.
+pub trait PrioritySend: ErrorType {
+    /// Sends `request` to `service` at `priority` and blocks until the response arrives.
+    ///
+    /// Behaves like [`Client::request`] otherwise.
+    fn request_with_priority<'r, Req, Resp>(
+        &mut self,
+        service: ServiceId,
+        request: &Req,
+        priority: Priority,
+        response_buf: &'r mut [u8],
+    ) -> Result<Resp, Self::Error>
+    where
+        Req: Encode,
+        Resp: Decode<'r>;
+
+    /// Queues `message` for `service` at `priority` if its queue has a free slot.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success, or an error of kind [`ErrorKind::Busy`] if the queue is full.
+    fn try_send_with_priority<Req>(
+        &mut self,
+        service: ServiceId,
+        message: &Req,
+        priority: Priority,
+    ) -> Result<(), Self::Error>
+    where
+        Req: Encode;
+
+    /// Returns the current occupancy of the outbound queue for `priority`.
+    fn queue_depth_for(&self, priority: Priority) -> QueueDepth;
+}
+
+/// State of a request started with [`CancellableClient::start_request`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum RequestStatus {
+    /// No response yet.
+    Pending,
+    /// The response arrived and can be taken with [`CancellableClient::take_response`].
+    Ready,
+    /// The request was cancelled before a response arrived.
+    Cancelled,
+    /// The deadline passed in [`CancellableClient::wait`] and the request was cancelled.
+    ///
+    /// Only returned by `wait`; the completion is reported as [`RequestStatus::Cancelled`].
+    TimedOut,
+    /// The request failed in the transport or was rejected by the service.
+    Failed(ErrorKind),
+}
+
+impl RequestStatus {
+    /// Returns whether the request has reached a final state.
+    pub fn is_terminal(&self) -> bool {
+        !matches!(self, RequestStatus::Pending)
+    }
+}
+
+/// Receives the final state of every request from [`CancellableClient::dispatch_completions`].
+pub trait CompletionHandler<T> {
+    fn on_complete(&mut self, token: T, status: RequestStatus);
+}
+
+/// Client whose requests can be polled, waited on with a deadline, or cancelled.
+///
+/// Every started request reaches exactly one terminal [`RequestStatus`], which
+/// is reported once through [`CancellableClient::dispatch_completions`], so a
+/// hung service cannot leave the caller waiting forever.
+pub trait CancellableClient: ErrorType {
+    /// Identifies an in-flight request.
+    type Token: Copy + Eq;
+
+    /// Sends `request` to `service` without waiting for the response.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the token of the request, or an error of type `Self::Error`.
+    fn start_request<Req>(&mut self, service: ServiceId, request: &Req) -> Result<Self::Token, Self::Error>
+    where
+        Req: Encode;
+
+    /// Returns the state of the request without blocking.
+    fn status(&mut self, token: Self::Token) -> Result<RequestStatus, Self::Error>;
+
+    /// Decodes the response of a [`RequestStatus::Ready`] request and frees its token.
+    ///
+    /// # Parameters
+    ///
+    /// - `token`: The request.
+    /// - `response_buf`: Receive buffer. The decoded response may borrow from it.
+    fn take_response<'r, Resp>(
+        &mut self,
+        token: Self::Token,
+        response_buf: &'r mut [u8],
+    ) -> Result<Resp, Self::Error>
+    where
+        Resp: Decode<'r>;
+
+    /// Cancels the request. A response arriving afterwards is discarded.
+    ///
+    /// Cancelling a request that already reached a terminal state has no effect.
+    fn cancel(&mut self, token: Self::Token) -> Result<(), Self::Error>;
+
+    /// Polls the request until it reaches a terminal state or `deadline` passes.
+    ///
+    /// The default implementation busy-spins on [`CancellableClient::status`];
+    /// implementations that can sleep until a completion should override it.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the terminal state, or [`RequestStatus::TimedOut`] after
+    /// cancelling the request once the deadline has passed. A request that
+    /// completes while it is being cancelled returns its own terminal state.
+    fn wait(
+        &mut self,
+        token: Self::Token,
+        clock: &impl Monotonic,
+        deadline: Deadline,
+    ) -> Result<RequestStatus, Self::Error> {
+        loop {
+            let status = self.status(token)?;
+            if status.is_terminal() {
+                return Ok(status);
+            }
+            if deadline.has_expired(clock) {
+                self.cancel(token)?;
+                return match self.status(token)? {
+                    RequestStatus::Cancelled | RequestStatus::Pending => Ok(RequestStatus::TimedOut),
+                    status => Ok(status),
+                };
+            }
+        }
+    }
+
+    /// Hands every request that reached a terminal state since the last call to `handler`.
+    fn dispatch_completions(
+        &mut self,
+        handler: &mut impl CompletionHandler<Self::Token>,
+    ) -> Result<(), Self::Error>;
+}
+
+/// Identifier carried by a request and echoed in its response, so responses
+/// can be matched to requests regardless of arrival order.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct CorrelationId(pub u16);
+
+/// A finished request returned by [`MultiplexClient::poll_any`] or [`MultiplexClient::poll`].
+#[derive(Debug)]
+pub struct Completed<H, C> {
+    /// Handle returned by [`MultiplexClient::submit`].
+    pub handle: H,
+    /// Context passed to [`MultiplexClient::submit`].
+    pub context: C,
+    /// Length of the encoded response copied into the response buffer,
+    /// or the reason the request failed.
+    pub outcome: Result<usize, ErrorKind>,
+}
+
+/// Completion type of the multiplexed client `C`.
+pub type CompletedOf<C> =
+    Completed<<C as MultiplexClient>::ResponseHandle, <C as MultiplexClient>::Context>;
+
+/// Client with several requests in flight at once.
+///
+/// The client allocates a [`CorrelationId`] per request and routes each
+/// response back to its request. Since in-flight requests may have different
+/// response types, responses are returned encoded; the per-request context
+/// tells the caller how to decode them.
+pub trait MultiplexClient: ErrorType {
+    /// Maximum number of requests in flight.
+    const MAX_IN_FLIGHT: usize;
+
+    /// Handle to an in-flight request.
+    type ResponseHandle: Copy + Eq;
+
+    /// Caller state kept with each request and handed back on completion.
+    type Context;
+
+    /// Sends `request` to `service` without waiting for the response.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the handle of the request, or an error of kind
+    /// [`ErrorKind::Busy`] if [`MultiplexClient::MAX_IN_FLIGHT`] requests are outstanding.
+    fn submit<Req>(
+        &mut self,
+        service: ServiceId,
+        request: &Req,
+        context: Self::Context,
+    ) -> Result<Self::ResponseHandle, Self::Error>
+    where
+        Req: Encode;
+
+    /// Returns the correlation ID assigned to an in-flight request.
+    fn correlation_id(&self, handle: Self::ResponseHandle) -> CorrelationId;
+
+    /// Number of requests in flight.
+    fn in_flight(&self) -> usize;
+
+    /// Returns the request `handle` if it has finished, copying its response into `response_buf`.
+    fn poll(
+        &mut self,
+        handle: Self::ResponseHandle,
+        response_buf: &mut [u8],
+    ) -> Result<Option<CompletedOf<Self>>, Self::Error>;
+
+    /// Returns any finished request, copying its response into `response_buf`.
+    fn poll_any(
+        &mut self,
+        response_buf: &mut [u8],
+    ) -> Result<Option<CompletedOf<Self>>, Self::Error>;
+}
+
+/// Requests whose encoded body is gathered from several fragments.
+///
+/// The fragments are concatenated on the wire, so a protocol header can be
+/// sent ahead of a payload without copying both into one staging buffer.
+pub trait VectoredClient: ErrorType {
+    /// Sends the concatenation of `request` to `service` and blocks until the response arrives.
+    ///
+    /// # Parameters
+    ///
+    /// - `service`: The destination service.
+    /// - `request`: Fragments of the encoded request, in wire order.
+    /// - `response_buf`: Receive buffer. The decoded response may borrow from it.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the decoded response, or an error of type `Self::Error`.
+    fn request_vectored<'r, Resp>(
+        &mut self,
+        service: ServiceId,
+        request: &[&[u8]],
+        response_buf: &'r mut [u8],
+    ) -> Result<Resp, Self::Error>
+    where
+        Resp: Decode<'r>;
+
+    /// Sends the concatenation of `message` to `service` without waiting for a response.
+    fn send_vectored(&mut self, service: ServiceId, message: &[&[u8]]) -> Result<(), Self::Error>;
+}
+
+/// Decides whether and when a failed request is retried.
+pub trait RetryPolicy {
+    /// Called after attempt number `attempt` (starting at 1) failed with `error`.
+    ///
+    /// # Returns
+    ///
+    /// The delay before the next attempt, or `None` to give up.
+    fn retry_after(&mut self, attempt: u32, error: ErrorKind) -> Option<Duration>;
+}
+
+/// Returns whether a request failing with `error` may succeed when retried.
+///
+/// Transient transport conditions are retryable; rejections and codec
+/// errors are not.
+pub fn is_transient(error: ErrorKind) -> bool {
+    matches!(
+        error,
+        ErrorKind::Timeout | ErrorKind::Busy | ErrorKind::Disconnected | ErrorKind::QuotaExceeded
+    )
+}
+
+/// Never retries.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub struct NoRetry;
+
+impl RetryPolicy for NoRetry {
+    fn retry_after(&mut self, _attempt: u32, _error: ErrorKind) -> Option<Duration> {
+        None
+    }
+}
+
+/// Retries transient failures with a doubling delay.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ExponentialBackoff {
+    /// Delay before the first retry.
+    pub initial: Duration,
+    /// Upper bound of the delay.
+    pub max: Duration,
+    /// Total number of attempts, including the first.
+    pub max_attempts: u32,
+}
+
+impl RetryPolicy for ExponentialBackoff {
+    fn retry_after(&mut self, attempt: u32, error: ErrorKind) -> Option<Duration> {
+        if attempt >= self.max_attempts || !is_transient(error) {
+            return None;
+        }
+        let factor = 1u32.checked_shl(attempt.saturating_sub(1)).unwrap_or(u32::MAX);
+        Some(self.initial.saturating_mul(factor).min(self.max))
+    }
+}
+
+/// A client whose requests carry an [`IdempotencyKey`] in their [`RequestHeader`].
+///
+/// Gives at-least-once delivery over unreliable transports, such as shared
+/// mailboxes or I3C with arbitration loss, without repeating side effects
+/// on a service that detects duplicates.
+pub trait IdempotentClient: ErrorType {
+    /// Returns a key not used by any other request still within the service's duplicate window.
+    fn next_idempotency_key(&mut self) -> IdempotencyKey;
+
+    /// Sends one attempt of `request` to `service` tagged with `key` and blocks until the response arrives.
+    fn request_idempotent<'r, Req, Resp>(
+        &mut self,
+        service: ServiceId,
+        key: IdempotencyKey,
+        request: &Req,
+        response_buf: &'r mut [u8],
+    ) -> Result<Resp, Self::Error>
+    where
+        Req: Encode,
+        Resp: Decode<'r>;
+
+    /// Sends `request` under a fresh key, retrying as `policy` allows.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the decoded response, or the error of the last attempt.
+    fn request_with_retry<Req, Resp>(
+        &mut self,
+        service: ServiceId,
+        request: &Req,
+        response_buf: &mut [u8],
+        policy: &mut impl RetryPolicy,
+        delay: &mut impl DelayNs,
+    ) -> Result<Resp, Self::Error>
+    where
+        Req: Encode,
+        Resp: for<'r> Decode<'r>,
+    {
+        let key = self.next_idempotency_key();
+        let mut attempt = 1;
+        loop {
+            let error = match self.request_idempotent(service, key, request, response_buf) {
+                Ok(response) => return Ok(response),
+                Err(error) => error,
+            };
+            let wait = policy.retry_after(attempt, error.kind()).ok_or(error)?;
+            delay.delay_us(u32::try_from(wait.as_micros()).unwrap_or(u32::MAX));
+            attempt += 1;
+        }
+    }
+}
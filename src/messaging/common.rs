@@ -0,0 +1,432 @@
+//! Types shared by the messaging client and service traits.
+
+use core::fmt::Debug;
+use core::num::NonZeroUsize;
+
+/// Error kind.
+///
+/// This represents a common set of messaging errors. Implementations are
+/// free to define more specific or additional error types. However, by providing
+/// a mapping to these common errors, generic code can still react to them.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// No service is registered under the requested ID.
+    UnknownService,
+
+    /// A service is already registered under the ID.
+    AlreadyRegistered,
+
+    /// A message could not be encoded.
+    Encode,
+
+    /// A received message could not be decoded.
+    Decode,
+
+    /// A buffer is too small for the message.
+    BufferTooSmall,
+
+    /// The peer did not answer in time.
+    Timeout,
+
+    /// The transport is not connected or the peer went away.
+    Disconnected,
+
+    /// The transport cannot accept the message right now.
+    Busy,
+
+    /// The service rejected the request.
+    Rejected,
+
+    /// The message exceeds the size the peer accepts.
+    MessageTooLarge,
+
+    /// The peer aborted a chunked transfer.
+    Aborted,
+
+    /// A secured message did not authenticate, or was replayed or reordered.
+    AuthenticationFailed,
+
+    /// A secure session used up its sequence numbers and must be rekeyed.
+    SessionExhausted,
+
+    /// The access policy does not allow the sender to send the message.
+    Forbidden,
+
+    /// The sender used up its quota; it may retry later.
+    QuotaExceeded,
+
+    /// A different error occurred.
+    Other,
+}
+
+pub trait Error: core::fmt::Debug {
+    /// Convert error to a generic error kind
+    ///
+    /// By using this method, errors freely defined by HAL implementations
+    /// can be converted to a set of generic errors upon which generic
+    /// code can act.
+    fn kind(&self) -> ErrorKind;
+}
+
+impl Error for core::convert::Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+impl Error for ErrorKind {
+    fn kind(&self) -> ErrorKind {
+        *self
+    }
+}
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// Well-known identifier of a service.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct ServiceId(pub u32);
+
+/// Delivery priority of a message.
+///
+/// Transports queue each priority separately and always deliver the highest
+/// non-empty one first, so e.g. a thermal shutdown notice overtakes queued
+/// firmware image chunks. Messages of equal priority keep their order.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Priority {
+    /// Bulk transfers that may be delayed indefinitely.
+    Background,
+    /// Default for ordinary requests.
+    #[default]
+    Normal,
+    /// Latency-sensitive control traffic.
+    High,
+    /// Messages that must not wait behind anything else.
+    Critical,
+}
+
+impl Priority {
+    /// Number of priority levels.
+    pub const COUNT: usize = 4;
+
+    /// Returns the level as an index in `0..COUNT`, lowest priority first.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Identifies one logical request across retries.
+///
+/// A client sends every retry of a request with the same key, so a service
+/// that already handled it can replay the response instead of repeating its
+/// side effects.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct IdempotencyKey(pub u32);
+
+/// Header preceding every request on the wire.
+///
+/// Encoded as the service ID (little-endian `u32`), the priority index, a
+/// flag byte (bit 0: key present) and the idempotency key (little-endian
+/// `u32`, zero if absent).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct RequestHeader {
+    pub service: ServiceId,
+    pub priority: Priority,
+    /// Set for requests that may be retried.
+    pub idempotency_key: Option<IdempotencyKey>,
+}
+
+impl MaxSize for RequestHeader {
+    const MAX_SIZE: usize = 10;
+}
+
+impl Encode for RequestHeader {
+    fn encode(&self, buf: &mut [u8]) -> Result<usize, CodecError> {
+        let buf = buf.get_mut(..Self::MAX_SIZE).ok_or(CodecError::BufferTooSmall)?;
+        buf[..4].copy_from_slice(&self.service.0.to_le_bytes());
+        buf[4] = self.priority.index() as u8;
+        buf[5] = u8::from(self.idempotency_key.is_some());
+        buf[6..].copy_from_slice(&self.idempotency_key.map_or(0, |key| key.0).to_le_bytes());
+        Ok(Self::MAX_SIZE)
+    }
+}
+
+impl<'a> Decode<'a> for RequestHeader {
+    fn decode(buf: &'a [u8]) -> Result<Self, CodecError> {
+        let buf: &[u8; 10] = buf
+            .get(..Self::MAX_SIZE)
+            .ok_or(CodecError::BufferTooSmall)?
+            .try_into()
+            .map_err(|_| CodecError::Malformed)?;
+        let priority = match buf[4] {
+            0 => Priority::Background,
+            1 => Priority::Normal,
+            2 => Priority::High,
+            3 => Priority::Critical,
+            _ => return Err(CodecError::Malformed),
+        };
+        let key = u32::from_le_bytes([buf[6], buf[7], buf[8], buf[9]]);
+        let idempotency_key = match buf[5] {
+            0 => None,
+            1 => Some(IdempotencyKey(key)),
+            _ => return Err(CodecError::Malformed),
+        };
+        Ok(Self {
+            service: ServiceId(u32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]])),
+            priority,
+            idempotency_key,
+        })
+    }
+}
+
+/// Failure of [`Encode::encode`] or [`Decode::decode`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum CodecError {
+    /// The output buffer is too small, or the input ended early.
+    BufferTooSmall,
+    /// The input is not a valid encoding of the type.
+    Malformed,
+}
+
+/// Upper bound of a type's encoded size.
+///
+/// Lets callers size stack buffers at compile time.
+pub trait MaxSize {
+    const MAX_SIZE: usize;
+}
+
+/// Serialization into a caller-provided buffer.
+///
+/// The wire format (postcard, CBOR, a fixed layout, ...) is chosen by the implementation.
+pub trait Encode: MaxSize {
+    /// Encodes `self` into `buf`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes written.
+    fn encode(&self, buf: &mut [u8]) -> Result<usize, CodecError>;
+}
+
+/// Deserialization from a received buffer.
+///
+/// The lifetime lets decoded values borrow from the buffer instead of copying.
+pub trait Decode<'a>: Sized {
+    fn decode(buf: &'a [u8]) -> Result<Self, CodecError>;
+}
+
+impl<const N: usize> MaxSize for [u8; N] {
+    const MAX_SIZE: usize = N;
+}
+
+impl<const N: usize> Encode for [u8; N] {
+    fn encode(&self, buf: &mut [u8]) -> Result<usize, CodecError> {
+        buf.get_mut(..N)
+            .ok_or(CodecError::BufferTooSmall)?
+            .copy_from_slice(self);
+        Ok(N)
+    }
+}
+
+impl<'a, const N: usize> Decode<'a> for [u8; N] {
+    fn decode(buf: &'a [u8]) -> Result<Self, CodecError> {
+        if buf.len() < N {
+            return Err(CodecError::BufferTooSmall);
+        }
+        buf.try_into().map_err(|_| CodecError::Malformed)
+    }
+}
+
+impl<'a> Decode<'a> for &'a [u8] {
+    fn decode(buf: &'a [u8]) -> Result<Self, CodecError> {
+        Ok(buf)
+    }
+}
+
+/// Parameters of a chunked transfer agreed by [`ChunkSender::negotiate`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct TransferParams {
+    /// Total length of the message in bytes.
+    pub total_len: usize,
+    /// Payload bytes per chunk; the last chunk may be shorter.
+    pub chunk_size: NonZeroUsize,
+}
+
+impl TransferParams {
+    /// Returns the number of chunks needed for the message.
+    pub fn chunk_count(&self) -> usize {
+        self.total_len.div_ceil(self.chunk_size.get())
+    }
+}
+
+/// Receives progress reports of a chunked transfer.
+pub trait TransferProgress {
+    /// Called after each chunk with the number of bytes transferred so far.
+    fn on_progress(&mut self, transferred: usize, total: usize);
+}
+
+impl TransferProgress for () {
+    fn on_progress(&mut self, _transferred: usize, _total: usize) {}
+}
+
+/// Sending side of messages larger than the transport MTU.
+///
+/// A transfer is opened with `negotiate`, carries its chunks in order and
+/// ends with `finish` or `abort`. Implementations frame chunks with whatever
+/// header their transport needs.
+pub trait ChunkSender: ErrorType {
+    /// Announces a message of `total_len` bytes to the peer.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the agreed parameters, or an error of kind
+    /// [`ErrorKind::MessageTooLarge`] if the peer cannot accept the message.
+    fn negotiate(&mut self, total_len: usize) -> Result<TransferParams, Self::Error>;
+
+    /// Sends the chunk starting at byte `offset` of the message.
+    ///
+    /// Fails with [`ErrorKind::Aborted`] if the peer aborted the transfer.
+    fn send_chunk(&mut self, offset: usize, chunk: &[u8]) -> Result<(), Self::Error>;
+
+    /// Completes the transfer once every chunk was sent.
+    fn finish(&mut self) -> Result<(), Self::Error>;
+
+    /// Abandons the transfer and tells the peer to discard the partial message.
+    fn abort(&mut self) -> Result<(), Self::Error>;
+
+    /// Sends `message` in chunks, reporting progress to `progress`.
+    ///
+    /// The transfer is aborted if a chunk fails.
+    fn send_message(&mut self, message: &[u8], progress: &mut impl TransferProgress) -> Result<(), Self::Error> {
+        let params = self.negotiate(message.len())?;
+        let mut offset = 0;
+        for chunk in message.chunks(params.chunk_size.get()) {
+            if let Err(e) = self.send_chunk(offset, chunk) {
+                // The chunk error is the one worth reporting.
+                let _ = self.abort();
+                return Err(e);
+            }
+            offset += chunk.len();
+            progress.on_progress(offset, message.len());
+        }
+        self.finish()
+    }
+}
+
+/// State of a message being reassembled.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Reassembly {
+    /// `received` of `total` bytes have arrived.
+    InProgress { received: usize, total: usize },
+    /// The message is complete and can be read with [`Reassembler::message`].
+    Complete,
+}
+
+/// Receiving side of chunked transfers.
+pub trait Reassembler: ErrorType {
+    /// Largest message the reassembly buffer holds, reported to senders during negotiation.
+    fn max_message_len(&self) -> usize;
+
+    /// Prepares to receive a message of `total_len` bytes, discarding any partial one.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the agreed parameters, or an error of kind
+    /// [`ErrorKind::MessageTooLarge`], or of kind [`ErrorKind::Decode`] if
+    /// `chunk_size` is 0.
+    fn begin(&mut self, total_len: usize, chunk_size: usize) -> Result<TransferParams, Self::Error>;
+
+    /// Stores the chunk that starts at byte `offset` of the message.
+    ///
+    /// Fails with [`ErrorKind::Decode`] for a chunk outside the message or
+    /// out of order.
+    fn push_chunk(&mut self, offset: usize, chunk: &[u8]) -> Result<Reassembly, Self::Error>;
+
+    /// Discards the partial message, e.g. after the sender aborted.
+    fn abort(&mut self);
+
+    /// Returns the complete message, or `None` while reassembly is in progress.
+    fn message(&self) -> Option<&[u8]>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_header_layout() {
+        let header = RequestHeader {
+            service: ServiceId(0x1234_5678),
+            priority: Priority::High,
+            idempotency_key: Some(IdempotencyKey(0xAABB_CCDD)),
+        };
+        let mut buf = [0u8; RequestHeader::MAX_SIZE];
+        assert_eq!(header.encode(&mut buf), Ok(10));
+        assert_eq!(buf, [0x78, 0x56, 0x34, 0x12, 2, 1, 0xDD, 0xCC, 0xBB, 0xAA]);
+        assert_eq!(RequestHeader::decode(&buf), Ok(header));
+    }
+
+    #[test]
+    fn request_header_round_trips() {
+        let priorities = [Priority::Background, Priority::Normal, Priority::High, Priority::Critical];
+        for priority in priorities {
+            for idempotency_key in [None, Some(IdempotencyKey(7))] {
+                let header = RequestHeader {
+                    service: ServiceId(3),
+                    priority,
+                    idempotency_key,
+                };
+                let mut buf = [0xFFu8; 12];
+                let len = header.encode(&mut buf).unwrap();
+                assert_eq!(RequestHeader::decode(&buf[..len]), Ok(header));
+                // Trailing payload bytes are left to the caller.
+                assert_eq!(RequestHeader::decode(&buf), Ok(header));
+            }
+        }
+    }
+
+    #[test]
+    fn request_header_without_key_encodes_zero() {
+        let header = RequestHeader {
+            service: ServiceId(1),
+            priority: Priority::Normal,
+            idempotency_key: None,
+        };
+        let mut buf = [0xFFu8; RequestHeader::MAX_SIZE];
+        header.encode(&mut buf).unwrap();
+        assert_eq!(buf, [1, 0, 0, 0, 1, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn request_header_rejects_bad_input() {
+        let header = RequestHeader {
+            service: ServiceId(1),
+            priority: Priority::Normal,
+            idempotency_key: None,
+        };
+        assert_eq!(header.encode(&mut [0u8; 9]), Err(CodecError::BufferTooSmall));
+
+        let good = [1, 0, 0, 0, 1, 0, 0, 0, 0, 0];
+        assert_eq!(RequestHeader::decode(&good[..9]), Err(CodecError::BufferTooSmall));
+
+        let mut bad_priority = good;
+        bad_priority[4] = 4;
+        assert_eq!(RequestHeader::decode(&bad_priority), Err(CodecError::Malformed));
+
+        let mut bad_flag = good;
+        bad_flag[5] = 2;
+        assert_eq!(RequestHeader::decode(&bad_flag), Err(CodecError::Malformed));
+    }
+
+    #[test]
+    fn array_codec() {
+        let mut buf = [0u8; 4];
+        assert_eq!([1u8, 2, 3].encode(&mut buf), Ok(3));
+        assert_eq!(buf, [1, 2, 3, 0]);
+        assert_eq!([1u8, 2, 3, 4, 5].encode(&mut buf), Err(CodecError::BufferTooSmall));
+        assert_eq!(<[u8; 3]>::decode(&buf[..3]), Ok([1, 2, 3]));
+        assert_eq!(<[u8; 3]>::decode(&buf[..2]), Err(CodecError::BufferTooSmall));
+    }
+}
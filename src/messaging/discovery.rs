@@ -0,0 +1,50 @@
+//! Service registration and message routing.
+//!
+//! Lets a firmware image made of several services wire them together
+//! without a hand-written dispatcher.
+
+pub use super::common::{Error, ErrorKind, ErrorType, ServiceId};
+
+/// Registry mapping well-known service IDs to the endpoints serving them.
+pub trait Registry: ErrorType {
+    /// Transport-specific endpoint, e.g. an MCTP EID or a task ID.
+    type Endpoint: Copy;
+
+    /// Registers `endpoint` as the provider of `id`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or failure. Fails with
+    /// [`ErrorKind::AlreadyRegistered`] if `id` already has a provider.
+    fn register(&mut self, id: ServiceId, endpoint: Self::Endpoint) -> Result<(), Self::Error>;
+
+    /// Removes the registration of `id`.
+    fn unregister(&mut self, id: ServiceId) -> Result<(), Self::Error>;
+
+    /// Returns the endpoint serving `id`, or an error of kind [`ErrorKind::UnknownService`].
+    fn lookup(&self, id: ServiceId) -> Result<Self::Endpoint, Self::Error>;
+
+    /// Calls `f` for every registered service.
+    fn for_each(&self, f: impl FnMut(ServiceId, Self::Endpoint));
+}
+
+/// Handles raw, still encoded requests for one service.
+pub trait MessageHandler: ErrorType {
+    /// Handles `request` and encodes the response into `response`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the length of the response.
+    fn handle(&mut self, request: &[u8], response: &mut [u8]) -> Result<usize, Self::Error>;
+}
+
+/// Dispatches incoming messages to the handler registered for their service.
+pub trait Router: ErrorType {
+    /// Routes `request` addressed to `id`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the length of the response, or an error of kind
+    /// [`ErrorKind::UnknownService`] if no handler serves `id`.
+    fn route(&mut self, id: ServiceId, request: &[u8], response: &mut [u8]) -> Result<usize, Self::Error>;
+}
@@ -0,0 +1,153 @@
+use core::fmt::Debug;
+
+/// Error kind.
+///
+/// This represents a common set of MCTP errors. Implementations are
+/// free to define more specific or additional error types. However, by providing
+/// a mapping to these common errors, generic code can still react to them.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The endpoint ID is reserved or otherwise not assignable.
+    InvalidEid,
+
+    /// No route or physical address is known for the destination EID.
+    NoRoute,
+
+    /// All message tags towards the destination are in use.
+    TagsExhausted,
+
+    /// A packet or message exceeds the size supported by the binding.
+    MessageTooLarge,
+
+    /// A packet arrived out of sequence and the message was dropped.
+    OutOfSequence,
+
+    /// Message assembly did not complete in time.
+    Timeout,
+
+    /// The underlying physical transport reported an error.
+    Transport,
+
+    /// A different error occurred.
+    Other,
+}
+
+pub trait Error: core::fmt::Debug {
+    /// Convert error to a generic error kind
+    ///
+    /// By using this method, errors freely defined by HAL implementations
+    /// can be converted to a set of generic errors upon which generic
+    /// code can act.
+    fn kind(&self) -> ErrorKind;
+}
+
+impl Error for core::convert::Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// MCTP endpoint ID.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Eid(pub u8);
+
+impl Eid {
+    /// Null EID, used before an EID has been assigned.
+    pub const NULL: Self = Self(0);
+    /// Broadcast EID.
+    pub const BROADCAST: Self = Self(0xff);
+}
+
+/// MCTP message tag.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct MsgTag {
+    /// Three-bit tag value.
+    pub value: u8,
+    /// Tag owner bit. Set by the endpoint that originated a request.
+    pub owner: bool,
+}
+
+/// MCTP message type, e.g. `0x01` for PLDM or `0x05` for SPDM.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct MessageType(pub u8);
+
+/// Endpoint ID management.
+pub trait MctpEndpoint: ErrorType {
+    /// Returns the EID currently assigned to this endpoint, or [`Eid::NULL`].
+    fn eid(&self) -> Eid;
+
+    /// Assigns a new EID, typically on a Set Endpoint ID control message.
+    fn set_eid(&mut self, eid: Eid) -> Result<(), Self::Error>;
+}
+
+/// Physical transport binding (SMBus/I2C, I3C, PCIe VDM, ...).
+///
+/// A binding only moves individual packets; splitting messages into packets
+/// and reassembly is done by [`MctpTransport`].
+pub trait MctpBinding: ErrorType {
+    /// Largest packet payload, excluding the transport header, carried by the binding.
+    const MTU: usize;
+
+    /// Binding-specific physical address, e.g. a 7-bit I2C address.
+    type PhysicalAddress: Copy;
+
+    /// Sends one packet to `dest`.
+    fn send_packet(&mut self, dest: Self::PhysicalAddress, packet: &[u8]) -> Result<(), Self::Error>;
+
+    /// Receives one packet into `buf` without blocking.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the packet length and source address, or `None` if no packet is pending.
+    fn receive_packet(
+        &mut self,
+        buf: &mut [u8],
+    ) -> Result<Option<(usize, Self::PhysicalAddress)>, Self::Error>;
+}
+
+/// Message tag ownership.
+pub trait MctpTagAllocator: ErrorType {
+    /// Allocates an owned tag for a new request to `dest`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the tag, or an error of kind [`ErrorKind::TagsExhausted`].
+    fn allocate_tag(&mut self, dest: Eid) -> Result<MsgTag, Self::Error>;
+
+    /// Releases a tag once its response has arrived or the request was abandoned.
+    fn release_tag(&mut self, dest: Eid, tag: MsgTag);
+}
+
+/// Receives fully assembled messages from [`MctpTransport::poll`].
+pub trait MctpMessageHandler {
+    /// Called once for each reassembled message.
+    ///
+    /// # Parameters
+    ///
+    /// - `src`: The EID that sent the message.
+    /// - `tag`: The message tag, with the owner bit as received.
+    /// - `msg_type`: The MCTP message type.
+    /// - `payload`: The message body following the message type byte.
+    fn on_message(&mut self, src: Eid, tag: MsgTag, msg_type: MessageType, payload: &[u8]);
+}
+
+/// Message-level MCTP transport.
+pub trait MctpTransport: MctpEndpoint {
+    /// Sends a message to `dest`, splitting it into packets of the binding MTU.
+    fn send_message(
+        &mut self,
+        dest: Eid,
+        tag: MsgTag,
+        msg_type: MessageType,
+        payload: &[u8],
+    ) -> Result<(), Self::Error>;
+
+    /// Processes received packets and hands any completed message to `handler`.
+    fn poll(&mut self, handler: &mut impl MctpMessageHandler) -> Result<(), Self::Error>;
+}
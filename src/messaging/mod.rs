@@ -0,0 +1,14 @@
+//! Messaging traits.
+//!
+//! Transport bindings and message-level abstractions used by protocol
+//! stacks such as PLDM and SPDM.
+
+pub mod client;
+pub mod common;
+pub mod discovery;
+pub mod mctp;
+pub mod policy;
+pub mod pubsub;
+pub mod secure_channel;
+pub mod service;
+pub mod trace;
@@ -0,0 +1,348 @@
+//! Access control and quotas for incoming messages.
+//!
+//! Firmware serving several requesters, such as the host, a BMC and a debug
+//! port, consults a [`Policy`] before handling each message. The policy sees
+//! who sent it, what it is and how large it is, and allows, denies or
+//! throttles it. [`QuotaTable`] provides per-sender rate accounting.
+
+pub use super::common::{ErrorKind, ServiceId};
+pub use super::service::{Service, ServiceHost};
+
+use crate::time::Instant;
+
+/// Identity of a sender as established by the transport, e.g. an MCTP
+/// endpoint ID or the ID of an authenticated secure session.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct SenderId(pub u32);
+
+/// What a policy knows about a message before it is decoded.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct MessageInfo {
+    pub sender: SenderId,
+    /// Destination service.
+    pub service: ServiceId,
+    /// Service-defined message type, e.g. a PLDM or SPDM command code.
+    pub message_type: u16,
+    /// Encoded length in bytes.
+    pub len: usize,
+}
+
+/// Outcome of a policy check.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Decision {
+    Allow,
+    /// Answered with [`ErrorKind::Forbidden`].
+    Deny,
+    /// Answered with [`ErrorKind::QuotaExceeded`]; the sender may retry at `until`.
+    Throttle { until: Instant },
+}
+
+impl Decision {
+    pub fn is_allowed(&self) -> bool {
+        matches!(self, Decision::Allow)
+    }
+
+    /// The error a denied or throttled message is answered with.
+    pub fn error(&self) -> Option<ErrorKind> {
+        match self {
+            Decision::Allow => None,
+            Decision::Deny => Some(ErrorKind::Forbidden),
+            Decision::Throttle { .. } => Some(ErrorKind::QuotaExceeded),
+        }
+    }
+}
+
+/// Authorization consulted for every incoming message.
+pub trait Policy {
+    /// Decides whether `message`, received at `now`, may be handled.
+    ///
+    /// An allowed message is charged against any quota the policy keeps,
+    /// even if a later policy of a chain then denies it.
+    fn check(&mut self, now: Instant, message: &MessageInfo) -> Decision;
+}
+
+/// Allows everything.
+impl Policy for () {
+    fn check(&mut self, _now: Instant, _message: &MessageInfo) -> Decision {
+        Decision::Allow
+    }
+}
+
+/// Checks `A` first; `B` only sees messages `A` allows.
+///
+/// `A` has already charged a message by the time `B` decides, so put
+/// stateless checks such as access lists first and quotas such as
+/// [`QuotaTable`] last; then a denied message uses up no quota.
+impl<A: Policy, B: Policy> Policy for (A, B) {
+    fn check(&mut self, now: Instant, message: &MessageInfo) -> Decision {
+        match self.0.check(now, message) {
+            Decision::Allow => self.1.check(now, message),
+            decision => decision,
+        }
+    }
+}
+
+/// Limits on what one sender may send per window.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Quota {
+    /// Messages per window.
+    pub messages: u32,
+    /// Encoded bytes per window.
+    pub bytes: u32,
+    /// Window length, in ticks of the host's [`crate::time::Monotonic`] clock.
+    pub window_ticks: u64,
+}
+
+/// Consumption of a [`Quota`] in the current window.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct QuotaUsage {
+    pub window_start: Instant,
+    pub messages: u32,
+    pub bytes: u32,
+}
+
+impl QuotaUsage {
+    /// Nothing consumed, in a window starting at tick 0.
+    pub const NONE: Self = Self {
+        window_start: Instant(0),
+        messages: 0,
+        bytes: 0,
+    };
+
+    /// Charges one message of `len` bytes at `now`, starting a new window if
+    /// the current one has passed.
+    ///
+    /// Nothing is charged if the message would exceed `quota`. A message
+    /// larger than the whole byte quota is denied, as no window could admit it.
+    pub fn charge(&mut self, quota: &Quota, now: Instant, len: usize) -> Decision {
+        if u32::try_from(len).map_or(true, |len| len > quota.bytes) {
+            return Decision::Deny;
+        }
+        if now.0.saturating_sub(self.window_start.0) >= quota.window_ticks {
+            *self = Self {
+                window_start: now,
+                messages: 0,
+                bytes: 0,
+            };
+        }
+        let bytes = u32::try_from(len).ok().and_then(|len| self.bytes.checked_add(len));
+        match bytes {
+            Some(bytes) if self.messages < quota.messages && bytes <= quota.bytes => {
+                self.messages += 1;
+                self.bytes = bytes;
+                Decision::Allow
+            }
+            _ => Decision::Throttle {
+                until: Instant(self.window_start.0.saturating_add(quota.window_ticks)),
+            },
+        }
+    }
+}
+
+/// Per-sender quotas for up to `N` senders.
+///
+/// Senders without an entry are denied, so the table doubles as an allow list.
+#[derive(Debug, Clone)]
+pub struct QuotaTable<const N: usize> {
+    entries: [Option<(SenderId, Quota, QuotaUsage)>; N],
+}
+
+impl<const N: usize> QuotaTable<N> {
+    pub const fn new() -> Self {
+        Self { entries: [None; N] }
+    }
+
+    /// Sets the quota of `sender`, resetting its usage.
+    ///
+    /// # Returns
+    ///
+    /// `false` if the table is full.
+    pub fn set_quota(&mut self, sender: SenderId, quota: Quota) -> bool {
+        let slot = self
+            .entries
+            .iter()
+            .position(|entry| matches!(entry, Some((id, ..)) if *id == sender))
+            .or_else(|| self.entries.iter().position(Option::is_none));
+        match slot {
+            Some(index) => {
+                self.entries[index] = Some((sender, quota, QuotaUsage::NONE));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes `sender`, which is denied from then on.
+    pub fn remove(&mut self, sender: SenderId) {
+        for entry in &mut self.entries {
+            if matches!(entry, Some((id, ..)) if *id == sender) {
+                *entry = None;
+            }
+        }
+    }
+
+    /// Returns the current usage of `sender`.
+    pub fn usage(&self, sender: SenderId) -> Option<QuotaUsage> {
+        self.entries.iter().flatten().find(|(id, ..)| *id == sender).map(|(_, _, usage)| *usage)
+    }
+}
+
+impl<const N: usize> Default for QuotaTable<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Policy for QuotaTable<N> {
+    fn check(&mut self, now: Instant, message: &MessageInfo) -> Decision {
+        match self.entries.iter_mut().flatten().find(|(id, ..)| *id == message.sender) {
+            Some((_, quota, usage)) => usage.charge(quota, now, message.len),
+            None => Decision::Deny,
+        }
+    }
+}
+
+/// A [`ServiceHost`] that checks every request against a [`Policy`].
+pub trait PolicyServiceHost: ServiceHost {
+    /// Like [`ServiceHost::serve_one`], but answers requests the policy
+    /// denies or throttles with the error from [`Decision::error`] instead of
+    /// handling them.
+    fn serve_one_checked<S: Service>(
+        &mut self,
+        service: &mut S,
+        policy: &mut impl Policy,
+        rx_buf: &mut [u8],
+        tx_buf: &mut [u8],
+    ) -> Result<(), Self::Error>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const QUOTA: Quota = Quota {
+        messages: 2,
+        bytes: 100,
+        window_ticks: 10,
+    };
+
+    #[test]
+    fn charge_allows_within_the_quota() {
+        let mut usage = QuotaUsage::NONE;
+        assert_eq!(usage.charge(&QUOTA, Instant(1), 40), Decision::Allow);
+        assert_eq!(usage.charge(&QUOTA, Instant(2), 60), Decision::Allow);
+        assert_eq!(
+            usage,
+            QuotaUsage {
+                window_start: Instant(0),
+                messages: 2,
+                bytes: 100,
+            }
+        );
+    }
+
+    #[test]
+    fn charge_throttles_until_the_window_ends() {
+        let mut usage = QuotaUsage::NONE;
+        assert!(usage.charge(&QUOTA, Instant(0), 1).is_allowed());
+        assert!(usage.charge(&QUOTA, Instant(1), 1).is_allowed());
+        let throttled = usage.charge(&QUOTA, Instant(2), 1);
+        assert_eq!(throttled, Decision::Throttle { until: Instant(10) });
+        assert_eq!(throttled.error(), Some(ErrorKind::QuotaExceeded));
+        // A throttled message is not charged.
+        assert_eq!(usage.messages, 2);
+    }
+
+    #[test]
+    fn charge_throttles_on_bytes() {
+        let mut usage = QuotaUsage::NONE;
+        assert!(usage.charge(&QUOTA, Instant(0), 90).is_allowed());
+        assert_eq!(usage.charge(&QUOTA, Instant(3), 11), Decision::Throttle { until: Instant(10) });
+        assert_eq!(usage.bytes, 90);
+        assert!(usage.charge(&QUOTA, Instant(3), 10).is_allowed());
+    }
+
+    #[test]
+    fn charge_starts_a_new_window() {
+        let mut usage = QuotaUsage::NONE;
+        assert!(usage.charge(&QUOTA, Instant(0), 100).is_allowed());
+        assert!(!usage.charge(&QUOTA, Instant(9), 1).is_allowed());
+        assert!(usage.charge(&QUOTA, Instant(10), 100).is_allowed());
+        assert_eq!(
+            usage,
+            QuotaUsage {
+                window_start: Instant(10),
+                messages: 1,
+                bytes: 100,
+            }
+        );
+    }
+
+    #[test]
+    fn charge_denies_messages_larger_than_the_byte_quota() {
+        let mut usage = QuotaUsage::NONE;
+        let denied = usage.charge(&QUOTA, Instant(0), 101);
+        assert_eq!(denied, Decision::Deny);
+        assert_eq!(denied.error(), Some(ErrorKind::Forbidden));
+        assert_eq!(usage.charge(&QUOTA, Instant(20), usize::MAX), Decision::Deny);
+        // A denied message neither charges nor starts a window.
+        assert_eq!(usage, QuotaUsage::NONE);
+    }
+
+    /// Denies every message to service 2.
+    struct DenyService2;
+
+    impl Policy for DenyService2 {
+        fn check(&mut self, _now: Instant, message: &MessageInfo) -> Decision {
+            if message.service == ServiceId(2) {
+                Decision::Deny
+            } else {
+                Decision::Allow
+            }
+        }
+    }
+
+    fn table() -> QuotaTable<1> {
+        let mut table = QuotaTable::new();
+        table.set_quota(SenderId(7), QUOTA);
+        table
+    }
+
+    #[test]
+    fn chained_quota_charges_only_after_earlier_checks() {
+        let message = MessageInfo {
+            sender: SenderId(7),
+            service: ServiceId(2),
+            message_type: 0,
+            len: 10,
+        };
+
+        let mut quota_last = (DenyService2, table());
+        assert_eq!(quota_last.check(Instant(0), &message), Decision::Deny);
+        assert_eq!(quota_last.1.usage(SenderId(7)), Some(QuotaUsage::NONE));
+
+        // With the quota first, the denied message is still charged.
+        let mut quota_first = (table(), DenyService2);
+        assert_eq!(quota_first.check(Instant(0), &message), Decision::Deny);
+        assert_eq!(quota_first.0.usage(SenderId(7)).map(|usage| usage.messages), Some(1));
+    }
+
+    #[test]
+    fn quota_table_denies_unknown_senders() {
+        let message = |sender| MessageInfo {
+            sender: SenderId(sender),
+            service: ServiceId(1),
+            message_type: 0,
+            len: 10,
+        };
+        let mut table = QuotaTable::<1>::new();
+        assert!(table.set_quota(SenderId(7), QUOTA));
+        assert!(!table.set_quota(SenderId(8), QUOTA));
+        assert!(table.check(Instant(0), &message(7)).is_allowed());
+        assert_eq!(table.check(Instant(0), &message(8)), Decision::Deny);
+        assert_eq!(table.usage(SenderId(7)).map(|usage| usage.bytes), Some(10));
+
+        table.remove(SenderId(7));
+        assert_eq!(table.check(Instant(0), &message(7)), Decision::Deny);
+    }
+}
@@ -0,0 +1,109 @@
+use core::fmt::Debug;
+
+/// Error kind.
+///
+/// This represents a common set of publish/subscribe errors. Implementations are
+/// free to define more specific or additional error types. However, by providing
+/// a mapping to these common errors, generic code can still react to them.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The topic is not known to the broker.
+    UnknownTopic,
+
+    /// No more subscriptions can be created.
+    TooManySubscriptions,
+
+    /// The subscription handle is stale or was never issued.
+    InvalidSubscription,
+
+    /// The message exceeds the maximum payload size of the topic.
+    MessageTooLarge,
+
+    /// The receive buffer is too small for the pending message.
+    BufferTooSmall,
+
+    /// A different error occurred.
+    Other,
+}
+
+pub trait Error: core::fmt::Debug {
+    /// Convert error to a generic error kind
+    ///
+    /// By using this method, errors freely defined by HAL implementations
+    /// can be converted to a set of generic errors upon which generic
+    /// code can act.
+    fn kind(&self) -> ErrorKind;
+}
+
+impl Error for core::convert::Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// Identifies a topic.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct TopicId(pub u32);
+
+/// A statically known topic.
+///
+/// Lets services refer to a topic by type instead of passing raw IDs around.
+pub trait Topic {
+    const ID: TopicId;
+
+    /// Largest payload published on the topic, in bytes.
+    const MAX_PAYLOAD: usize;
+}
+
+/// A message taken from a subscription queue.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Delivery {
+    /// Number of payload bytes written to the receive buffer.
+    pub len: usize,
+    /// Number of messages dropped on this subscription since the previous delivery
+    /// because its queue was full.
+    pub dropped: u32,
+}
+
+/// Publishing side.
+///
+/// Delivery is best effort: each subscription owns a bounded queue and a
+/// message is dropped for a subscriber whose queue is full. Publishing never
+/// blocks on slow subscribers.
+pub trait Publisher: ErrorType {
+    /// Publishes `payload` on `topic`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of subscriptions the message was queued on.
+    fn publish(&mut self, topic: TopicId, payload: &[u8]) -> Result<usize, Self::Error>;
+}
+
+/// Subscribing side.
+pub trait Subscriber: ErrorType {
+    /// Handle identifying one subscription.
+    type Subscription: Copy;
+
+    /// Subscribes to `topic`.
+    fn subscribe(&mut self, topic: TopicId) -> Result<Self::Subscription, Self::Error>;
+
+    /// Ends a subscription and discards its queued messages.
+    fn unsubscribe(&mut self, subscription: Self::Subscription) -> Result<(), Self::Error>;
+
+    /// Takes the oldest queued message of `subscription` without blocking.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the delivery, or `None` if the queue is empty.
+    fn try_receive(
+        &mut self,
+        subscription: Self::Subscription,
+        buf: &mut [u8],
+    ) -> Result<Option<Delivery>, Self::Error>;
+}
@@ -0,0 +1,251 @@
+//! Encrypted, authenticated sessions over a messaging transport.
+//!
+//! A [`Handshake`] exchanges messages with the peer until both sides share
+//! session keys, then yields a [`SecureSession`] that seals outgoing and
+//! opens incoming records. The algorithms come from a
+//! [`crate::suite::CipherSuite`]; the wire format of handshake messages and
+//! records (e.g. SPDM secured messages) is up to the implementation.
+
+pub use super::common::{Error, ErrorKind, ErrorType, ServiceId};
+
+use super::client::VectoredClient;
+use crate::suite::CipherSuite;
+
+/// Progress reported by [`Handshake::step`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum HandshakeStep {
+    /// Send the first `len` bytes of the output buffer to the peer.
+    Send(usize),
+    /// Wait for the next message from the peer.
+    Receive,
+    /// Keys are established; call [`Handshake::into_session`].
+    Complete,
+}
+
+/// Key establishment with a peer.
+pub trait Handshake: ErrorType + Sized {
+    type Suite: CipherSuite;
+    type Session: SecureSession<Suite = Self::Suite, Error = Self::Error>;
+
+    /// Advances the handshake.
+    ///
+    /// # Parameters
+    ///
+    /// - `received`: The message from the peer, or `None` when starting or
+    ///   after [`HandshakeStep::Send`].
+    /// - `out`: Buffer the next message to the peer is written into.
+    fn step(&mut self, received: Option<&[u8]>, out: &mut [u8]) -> Result<HandshakeStep, Self::Error>;
+
+    /// Completes the handshake.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the session, or an error if the handshake has
+    /// not reached [`HandshakeStep::Complete`].
+    fn into_session(self) -> Result<Self::Session, Self::Error>;
+}
+
+/// Failure of an operation involving both the session and the transport.
+#[derive(Debug)]
+pub enum ChannelError<S, T> {
+    Session(S),
+    Transport(T),
+}
+
+impl<S: Error, T: Error> Error for ChannelError<S, T> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            ChannelError::Session(e) => e.kind(),
+            ChannelError::Transport(e) => e.kind(),
+        }
+    }
+}
+
+/// Sequence numbers of one direction of a session.
+///
+/// Sending takes numbers in order until the limit; receiving accepts only
+/// numbers greater than the last one accepted, which rejects replays.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct SequenceNumbers {
+    next: u64,
+    limit: u64,
+}
+
+impl SequenceNumbers {
+    /// Starts at 0 and allows numbers below `limit`.
+    pub const fn new(limit: u64) -> Self {
+        Self { next: 0, limit }
+    }
+
+    /// Takes the next number to send, or `None` once the limit is reached.
+    pub fn take(&mut self) -> Option<u64> {
+        let seq = self.next;
+        (seq < self.limit).then(|| {
+            self.next += 1;
+            seq
+        })
+    }
+
+    /// Accepts the received number `seq` if it is newer than all previously
+    /// accepted ones and below the limit.
+    pub fn accept(&mut self, seq: u64) -> bool {
+        let fresh = seq >= self.next && seq < self.limit;
+        if fresh {
+            self.next = seq + 1;
+        }
+        fresh
+    }
+
+    /// Returns how many numbers are left before the limit.
+    pub fn remaining(&self) -> u64 {
+        self.limit.saturating_sub(self.next)
+    }
+
+    /// Restarts at 0, e.g. after a rekey.
+    pub fn reset(&mut self) {
+        self.next = 0;
+    }
+}
+
+/// Decides when a session should be rekeyed.
+pub trait RekeyPolicy {
+    /// Returns whether to rekey given the sequence numbers left in each direction.
+    fn should_rekey(&self, send_remaining: u64, receive_remaining: u64) -> bool;
+}
+
+/// Rekey once either direction has fewer than the given number of sequence numbers left.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct RekeyBelow(pub u64);
+
+impl RekeyPolicy for RekeyBelow {
+    fn should_rekey(&self, send_remaining: u64, receive_remaining: u64) -> bool {
+        send_remaining < self.0 || receive_remaining < self.0
+    }
+}
+
+/// An established session.
+pub trait SecureSession: ErrorType {
+    type Suite: CipherSuite;
+
+    /// Encrypts `plaintext` into a record with the next send sequence number.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the record length, or an error of kind
+    /// [`ErrorKind::SessionExhausted`] if the session must be rekeyed first.
+    fn seal(&mut self, plaintext: &[u8], record: &mut [u8]) -> Result<usize, Self::Error>;
+
+    /// Authenticates and decrypts `record` into `plaintext`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the plaintext length, or an error of kind
+    /// [`ErrorKind::AuthenticationFailed`] if the record is forged, replayed or reordered.
+    fn open(&mut self, record: &[u8], plaintext: &mut [u8]) -> Result<usize, Self::Error>;
+
+    /// Returns the send and receive sequence numbers left before rekeying is required.
+    fn remaining(&self) -> (u64, u64);
+
+    /// Derives the next generation of session keys and resets the sequence numbers.
+    ///
+    /// The peer must rekey at the same point in the message stream, e.g.
+    /// after a protocol-level key update message.
+    fn rekey(&mut self) -> Result<(), Self::Error>;
+
+    /// Destroys the session keys. Later calls fail.
+    fn close(&mut self);
+
+    /// Rekeys if `policy` asks for it.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating whether the session was rekeyed.
+    fn rekey_if_needed(&mut self, policy: &impl RekeyPolicy) -> Result<bool, Self::Error> {
+        let (send, receive) = self.remaining();
+        if policy.should_rekey(send, receive) {
+            self.rekey()?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Seals `request`, sends it to `service` over `client` and opens the response.
+    ///
+    /// # Parameters
+    ///
+    /// - `tx_buf`: Buffer the sealed request is written into.
+    /// - `rx_buf`: Receive buffer for the sealed response.
+    /// - `response`: Buffer the opened response is written into.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the response length.
+    fn request<C: VectoredClient>(
+        &mut self,
+        client: &mut C,
+        service: ServiceId,
+        request: &[u8],
+        tx_buf: &mut [u8],
+        rx_buf: &mut [u8],
+        response: &mut [u8],
+    ) -> Result<usize, ChannelError<Self::Error, C::Error>> {
+        let len = self.seal(request, tx_buf).map_err(ChannelError::Session)?;
+        let record: &[u8] = client
+            .request_vectored(service, &[&tx_buf[..len]], rx_buf)
+            .map_err(ChannelError::Transport)?;
+        self.open(record, response).map_err(ChannelError::Session)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn take_stops_at_the_limit() {
+        let mut seq = SequenceNumbers::new(3);
+        assert_eq!(seq.remaining(), 3);
+        assert_eq!(seq.take(), Some(0));
+        assert_eq!(seq.take(), Some(1));
+        assert_eq!(seq.take(), Some(2));
+        assert_eq!(seq.remaining(), 0);
+        assert_eq!(seq.take(), None);
+        assert_eq!(seq.take(), None);
+
+        seq.reset();
+        assert_eq!((seq.remaining(), seq.take()), (3, Some(0)));
+    }
+
+    #[test]
+    fn accept_rejects_replays_and_allows_gaps() {
+        let mut seq = SequenceNumbers::new(10);
+        assert!(seq.accept(0));
+        assert!(!seq.accept(0));
+        assert!(seq.accept(4));
+        assert_eq!(seq.remaining(), 5);
+        assert!(!seq.accept(2));
+        assert!(!seq.accept(4));
+        assert!(seq.accept(5));
+        assert!(!seq.accept(10));
+        assert!(seq.accept(9));
+        assert_eq!(seq.remaining(), 0);
+    }
+
+    #[test]
+    fn accept_at_u64_max_does_not_overflow() {
+        let mut seq = SequenceNumbers::new(u64::MAX);
+        assert!(!seq.accept(u64::MAX));
+        assert!(seq.accept(u64::MAX - 1));
+        assert_eq!(seq.remaining(), 0);
+        assert_eq!(seq.take(), None);
+    }
+
+    #[test]
+    fn rekey_below_checks_both_directions() {
+        let policy = RekeyBelow(4);
+        assert!(!policy.should_rekey(4, 4));
+        assert!(policy.should_rekey(3, 100));
+        assert!(policy.should_rekey(100, 3));
+    }
+}
@@ -0,0 +1,368 @@
+//! Responder side of request/response messaging.
+
+pub use super::common::{
+    Decode, Encode, Error, ErrorKind, ErrorType, IdempotencyKey, Priority, RequestHeader, ServiceId,
+};
+
+use super::client::QueueDepth;
+
+use crate::metrics::{MetricId, Metrics};
+use crate::time::{Instant, Monotonic};
+
+/// A service handling one type of request.
+pub trait Service: ErrorType {
+    /// Identifier clients use to reach the service.
+    const ID: ServiceId;
+
+    type Request<'a>: Decode<'a>;
+    type Response: Encode;
+
+    /// Handles a decoded request.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the response to send back, or an error of type `Self::Error`.
+    fn handle(&mut self, request: Self::Request<'_>) -> Result<Self::Response, Self::Error>;
+}
+
+/// Transport side that receives requests and delivers them to a [`Service`].
+pub trait ServiceHost: ErrorType {
+    /// Waits for one request for `service`, decodes it, handles it and sends the response.
+    ///
+    /// # Parameters
+    ///
+    /// - `service`: The service to dispatch to.
+    /// - `rx_buf`: Receive buffer. The decoded request may borrow from it.
+    /// - `tx_buf`: Buffer the response is encoded into.
+    fn serve_one<S: Service>(
+        &mut self,
+        service: &mut S,
+        rx_buf: &mut [u8],
+        tx_buf: &mut [u8],
+    ) -> Result<(), Self::Error>;
+}
+
+/// Per-priority inbound queues of a [`ServiceHost`].
+///
+/// `serve_one` takes the oldest request of the highest non-empty priority.
+pub trait PriorityQueues: ServiceHost {
+    /// Sets how many requests of `priority` can be queued for `service`.
+    ///
+    /// Requests arriving at a full queue are rejected with [`ErrorKind::Busy`]
+    /// without affecting the other priorities.
+    fn set_queue_depth(&mut self, service: ServiceId, priority: Priority, depth: usize) -> Result<(), Self::Error>;
+
+    /// Returns the current occupancy of the inbound queue of `service` for `priority`.
+    fn queue_depth(&self, service: ServiceId, priority: Priority) -> QueueDepth;
+
+    /// Sets the gauge `id` to the number of requests queued for `service` at all priorities.
+    fn report_queue_depth(&self, service: ServiceId, metrics: &impl Metrics, id: MetricId) {
+        let queued: usize = [Priority::Background, Priority::Normal, Priority::High, Priority::Critical]
+            .into_iter()
+            .map(|priority| self.queue_depth(service, priority).queued)
+            .sum();
+        metrics.set_gauge(id, i64::try_from(queued).unwrap_or(i64::MAX));
+    }
+}
+
+/// Lifecycle of a service managed by a supervisor.
+///
+/// A supervisor calls `init` once, then `start`; to shut down or restart it
+/// calls `drain` until it reports completion, then `stop`.
+pub trait Lifecycle: ErrorType {
+    /// Allocates resources and restores persistent state. Called once before `start`.
+    fn init(&mut self) -> Result<(), Self::Error>;
+
+    /// Starts accepting requests.
+    fn start(&mut self) -> Result<(), Self::Error>;
+
+    /// Stops accepting new requests and makes progress on those in flight.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing `true` once no requests are left in flight.
+    fn drain(&mut self) -> Result<bool, Self::Error>;
+
+    /// Stops the service. It may be started again with `start`.
+    fn stop(&mut self) -> Result<(), Self::Error>;
+}
+
+/// Health of a running service.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Health {
+    /// Initializing or recovering; not ready for requests yet.
+    Starting,
+    /// Serving requests normally.
+    Ready,
+    /// Serving requests with reduced capacity or functionality.
+    Degraded,
+    /// Not making progress; the supervisor should restart it.
+    Unhealthy,
+}
+
+/// Readiness and liveness queries.
+pub trait HealthCheck {
+    fn health(&self) -> Health;
+
+    /// Returns whether the service can accept requests.
+    fn is_ready(&self) -> bool {
+        matches!(self.health(), Health::Ready | Health::Degraded)
+    }
+
+    /// Returns whether the service is making progress.
+    fn is_alive(&self) -> bool {
+        self.health() != Health::Unhealthy
+    }
+}
+
+/// What a supervisor does when a service fails or becomes unhealthy.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum RestartPolicy {
+    /// Leave the service stopped.
+    Never,
+    /// Restart up to `max_restarts` times, waiting `backoff_ms` before each attempt.
+    OnFailure { max_restarts: u32, backoff_ms: u32 },
+    /// Restart every time, waiting `backoff_ms` before each attempt.
+    Always { backoff_ms: u32 },
+    /// Reset the whole system; the service is essential.
+    ResetSystem,
+}
+
+/// A service that a supervisor can manage generically.
+pub trait Supervised: Lifecycle + HealthCheck {
+    /// Restart policy the supervisor applies to the service.
+    fn restart_policy(&self) -> RestartPolicy;
+}
+
+/// Reply path handed to [`VectoredService::handle_vectored`].
+pub trait Reply {
+    /// Sends the concatenation of `fragments` as the response.
+    ///
+    /// Transport failures are reported by the host from
+    /// [`VectoredServiceHost::serve_one_vectored`].
+    fn send(&mut self, fragments: &[&[u8]]);
+}
+
+/// A service that sends its response as fragments, e.g. a fixed header
+/// followed by a payload borrowed from elsewhere.
+pub trait VectoredService: ErrorType {
+    /// Identifier clients use to reach the service.
+    const ID: ServiceId;
+
+    type Request<'a>: Decode<'a>;
+
+    /// Handles a decoded request and sends the response through `reply`.
+    ///
+    /// `reply` must be used exactly once when the request succeeds.
+    fn handle_vectored(
+        &mut self,
+        request: Self::Request<'_>,
+        reply: &mut dyn Reply,
+    ) -> Result<(), Self::Error>;
+}
+
+/// Transport side for [`VectoredService`]s.
+pub trait VectoredServiceHost: ServiceHost {
+    /// Waits for one request for `service`, decodes it and lets the service reply.
+    ///
+    /// No transmit buffer is needed; the fragments go to the transport directly.
+    fn serve_one_vectored<S: VectoredService>(
+        &mut self,
+        service: &mut S,
+        rx_buf: &mut [u8],
+    ) -> Result<(), Self::Error>;
+}
+
+/// Hooks run around [`Service::handle`], e.g. authorization, payload
+/// validation, logging or metrics.
+///
+/// Interceptors compose as tuples: `(A, B)` runs `A::before` then
+/// `B::before`, and the `after` hooks in reverse order.
+pub trait Interceptor<S: Service> {
+    /// Called with the decoded request before it is handled.
+    ///
+    /// Returning an error rejects the request without calling the service;
+    /// the `after` hooks of earlier interceptors in the chain are still called.
+    fn before(&mut self, request: &S::Request<'_>) -> Result<(), ErrorKind>;
+
+    /// Called with the outcome of the request.
+    fn after(&mut self, outcome: Result<&S::Response, ErrorKind>);
+}
+
+impl<S: Service> Interceptor<S> for () {
+    fn before(&mut self, _request: &S::Request<'_>) -> Result<(), ErrorKind> {
+        Ok(())
+    }
+
+    fn after(&mut self, _outcome: Result<&S::Response, ErrorKind>) {}
+}
+
+impl<S: Service, A: Interceptor<S>, B: Interceptor<S>> Interceptor<S> for (A, B) {
+    fn before(&mut self, request: &S::Request<'_>) -> Result<(), ErrorKind> {
+        self.0.before(request)?;
+        if let Err(kind) = self.1.before(request) {
+            self.0.after(Err(kind));
+            return Err(kind);
+        }
+        Ok(())
+    }
+
+    fn after(&mut self, outcome: Result<&S::Response, ErrorKind>) {
+        self.1.after(outcome);
+        self.0.after(outcome);
+    }
+}
+
+/// Error returned by [`Intercepted`].
+#[derive(Debug)]
+pub enum InterceptError<E> {
+    /// An interceptor rejected the request.
+    Rejected(ErrorKind),
+    /// The service failed.
+    Service(E),
+}
+
+impl<E: Error> Error for InterceptError<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            InterceptError::Rejected(kind) => *kind,
+            InterceptError::Service(e) => e.kind(),
+        }
+    }
+}
+
+/// A service wrapped in an interceptor chain.
+///
+/// Implements [`Service`] itself, so it can be handed to any [`ServiceHost`].
+pub struct Intercepted<S, I> {
+    service: S,
+    interceptor: I,
+}
+
+impl<S: Service, I: Interceptor<S>> Intercepted<S, I> {
+    pub fn new(service: S, interceptor: I) -> Self {
+        Self {
+            service,
+            interceptor,
+        }
+    }
+
+    /// Returns the service and the interceptor chain.
+    pub fn into_parts(self) -> (S, I) {
+        (self.service, self.interceptor)
+    }
+}
+
+impl<S: Service, I: Interceptor<S>> ErrorType for Intercepted<S, I> {
+    type Error = InterceptError<S::Error>;
+}
+
+impl<S: Service, I: Interceptor<S>> Service for Intercepted<S, I> {
+    const ID: ServiceId = S::ID;
+
+    type Request<'a> = S::Request<'a>;
+    type Response = S::Response;
+
+    fn handle(&mut self, request: Self::Request<'_>) -> Result<Self::Response, Self::Error> {
+        self.interceptor
+            .before(&request)
+            .map_err(InterceptError::Rejected)?;
+        let result = self.service.handle(request);
+        self.interceptor
+            .after(result.as_ref().map_err(|e| e.kind()));
+        result.map_err(InterceptError::Service)
+    }
+}
+
+/// Metrics reported by [`Metered`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ServiceMetricIds {
+    /// Counter of requests handled successfully.
+    pub handled: MetricId,
+    /// Counter of requests that failed or were rejected.
+    pub failed: MetricId,
+    /// Histogram of handling latency, in microseconds.
+    pub latency_us: MetricId,
+}
+
+/// Interceptor reporting request counts and handling latency.
+///
+/// Latency is measured from [`Interceptor::before`] to [`Interceptor::after`],
+/// so it covers the service and any interceptors placed after this one.
+pub struct Metered<'a, M, C> {
+    metrics: &'a M,
+    clock: &'a C,
+    ids: ServiceMetricIds,
+    started: Option<Instant>,
+}
+
+impl<'a, M: Metrics, C: Monotonic> Metered<'a, M, C> {
+    pub fn new(metrics: &'a M, clock: &'a C, ids: ServiceMetricIds) -> Self {
+        Self {
+            metrics,
+            clock,
+            ids,
+            started: None,
+        }
+    }
+}
+
+impl<S: Service, M: Metrics, C: Monotonic> Interceptor<S> for Metered<'_, M, C> {
+    fn before(&mut self, _request: &S::Request<'_>) -> Result<(), ErrorKind> {
+        self.started = Some(self.clock.now());
+        Ok(())
+    }
+
+    fn after(&mut self, outcome: Result<&S::Response, ErrorKind>) {
+        let counter = if outcome.is_ok() { self.ids.handled } else { self.ids.failed };
+        self.metrics.increment(counter, 1);
+        if let Some(started) = self.started.take() {
+            let ticks = self.clock.now().0.saturating_sub(started.0);
+            let frequency = u64::from(self.clock.frequency_hz()).max(1);
+            let micros = u128::from(ticks) * 1_000_000 / u128::from(frequency);
+            self.metrics
+                .record(self.ids.latency_us, u64::try_from(micros).unwrap_or(u64::MAX));
+        }
+    }
+}
+
+/// Outcome of [`DuplicateDetector::check`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum DuplicateCheck<'a> {
+    /// First time the key is seen; handle the request.
+    New,
+    /// An earlier attempt is still being handled; drop this one.
+    InProgress,
+    /// An earlier attempt completed; send this encoded response again.
+    Replay(&'a [u8]),
+}
+
+/// Remembers recently handled idempotency keys and their responses.
+///
+/// The window is implementation-defined, typically a fixed number of keys
+/// per service. Keys evicted from the window are treated as new.
+pub trait DuplicateDetector {
+    /// Looks up `key` for `service`, marking it in progress if it is new.
+    fn check(&mut self, service: ServiceId, key: IdempotencyKey) -> DuplicateCheck<'_>;
+
+    /// Records the encoded response of the request tagged `key`.
+    fn complete(&mut self, service: ServiceId, key: IdempotencyKey, response: &[u8]);
+
+    /// Forgets `key`, e.g. after handling failed, so a retry is handled again.
+    fn forget(&mut self, service: ServiceId, key: IdempotencyKey);
+}
+
+/// A [`ServiceHost`] that filters retried requests through a [`DuplicateDetector`].
+///
+/// Requests without an idempotency key are always handled.
+pub trait IdempotentServiceHost: ServiceHost {
+    /// Like [`ServiceHost::serve_one`], but answers duplicates of completed
+    /// requests with the recorded response instead of handling them again.
+    fn serve_one_idempotent<S: Service>(
+        &mut self,
+        service: &mut S,
+        detector: &mut impl DuplicateDetector,
+        rx_buf: &mut [u8],
+        tx_buf: &mut [u8],
+    ) -> Result<(), Self::Error>;
+}
@@ -0,0 +1,118 @@
+//! Tracing hooks for clients and service hosts.
+//!
+//! Transports that implement [`Tracing`] report every message they send or
+//! receive to a [`Tracer`], tagged with the [`CorrelationId`] of its request.
+//! With the `replay-capture` feature, [`replay`] records those events in a
+//! byte format that a bench build of the same services can read back.
+
+pub use super::client::CorrelationId;
+pub use super::common::{ErrorKind, ServiceId};
+
+use core::time::Duration;
+
+use crate::time::Instant;
+
+#[cfg(feature = "replay-capture")]
+pub mod replay;
+
+/// One step in the life of a request.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TraceEvent<'a> {
+    /// An encoded request was handed to the transport (client) or a response
+    /// was sent (host).
+    Send {
+        service: ServiceId,
+        correlation: CorrelationId,
+        message: &'a [u8],
+    },
+    /// An encoded response (client) or request (host) arrived.
+    Receive {
+        service: ServiceId,
+        correlation: CorrelationId,
+        message: &'a [u8],
+    },
+    /// A message was discarded, e.g. because its queue was full or it failed to decode.
+    Drop {
+        service: ServiceId,
+        correlation: CorrelationId,
+        reason: ErrorKind,
+    },
+    /// A failed request is sent again after `delay`.
+    Retry {
+        service: ServiceId,
+        correlation: CorrelationId,
+        /// Number of the upcoming attempt; the first retry is attempt 2.
+        attempt: u32,
+        delay: Duration,
+    },
+    /// A request completed `elapsed` after it was sent (client) or received (host).
+    Latency {
+        service: ServiceId,
+        correlation: CorrelationId,
+        elapsed: Duration,
+    },
+}
+
+impl TraceEvent<'_> {
+    pub fn service(&self) -> ServiceId {
+        match *self {
+            Self::Send { service, .. }
+            | Self::Receive { service, .. }
+            | Self::Drop { service, .. }
+            | Self::Retry { service, .. }
+            | Self::Latency { service, .. } => service,
+        }
+    }
+
+    pub fn correlation(&self) -> CorrelationId {
+        match *self {
+            Self::Send { correlation, .. }
+            | Self::Receive { correlation, .. }
+            | Self::Drop { correlation, .. }
+            | Self::Retry { correlation, .. }
+            | Self::Latency { correlation, .. } => correlation,
+        }
+    }
+}
+
+/// Destination of trace events.
+///
+/// Like [`crate::metrics::Metrics`], methods take `&self` so one tracer can
+/// be shared as a `static`. Tracing runs in the message path and should not
+/// block; a tracer that falls behind should drop events rather than stall.
+pub trait Tracer {
+    /// Records `event`, which happened at `at` on the transport's clock.
+    ///
+    /// Message bytes are only valid for the duration of the call.
+    fn trace(&self, at: Instant, event: &TraceEvent<'_>);
+}
+
+/// Discards every event.
+impl Tracer for () {
+    fn trace(&self, _at: Instant, _event: &TraceEvent<'_>) {}
+}
+
+impl<T: Tracer + ?Sized> Tracer for &T {
+    fn trace(&self, at: Instant, event: &TraceEvent<'_>) {
+        T::trace(self, at, event)
+    }
+}
+
+/// Forwards every event to both tracers.
+impl<A: Tracer, B: Tracer> Tracer for (A, B) {
+    fn trace(&self, at: Instant, event: &TraceEvent<'_>) {
+        self.0.trace(at, event);
+        self.1.trace(at, event);
+    }
+}
+
+/// A client or service host that can report to a [`Tracer`].
+///
+/// Tracing is off until a tracer is installed. Clients allocate a
+/// [`CorrelationId`] per request, including requests that do not need one
+/// on the wire, and keep it across retries; hosts use the ID carried by the
+/// request, or one of their own if the transport has none.
+pub trait Tracing {
+    /// Installs `tracer`, or turns tracing off with `None`.
+    fn set_tracer(&mut self, tracer: Option<&'static dyn Tracer>);
+}
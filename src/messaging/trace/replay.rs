@@ -0,0 +1,374 @@
+//! Replay capture of traced messages.
+//!
+//! [`ReplayCapture`] turns the `Send` and `Receive` events of a [`Tracer`]
+//! into a flat stream of records written to a [`CaptureSink`], e.g. a RAM
+//! ring dumped over a debug port or a flash partition. [`ReplayReader`]
+//! parses the stream back so a bench build can feed the recorded requests
+//! to the same services and compare the responses.
+//!
+//! Each record is a 17-byte header followed by the message bytes:
+//!
+//! | Offset | Size | Field                                     |
+//! |--------|------|-------------------------------------------|
+//! | 0      | 1    | Direction: 0 sent, 1 received             |
+//! | 1      | 8    | Timestamp in clock ticks, little-endian   |
+//! | 9      | 4    | Service ID, little-endian                 |
+//! | 13     | 2    | Correlation ID, little-endian             |
+//! | 15     | 2    | Message length, little-endian             |
+
+use super::{CorrelationId, ServiceId, TraceEvent, Tracer};
+use crate::messaging::common::CodecError;
+use crate::time::Instant;
+
+/// Length of a record header, in bytes.
+pub const HEADER_LEN: usize = 17;
+
+/// Whether a recorded message was sent or received by the traced side.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Direction {
+    Sent,
+    Received,
+}
+
+/// One captured message.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct CaptureRecord<'a> {
+    pub direction: Direction,
+    pub at: Instant,
+    pub service: ServiceId,
+    pub correlation: CorrelationId,
+    pub message: &'a [u8],
+}
+
+impl<'a> CaptureRecord<'a> {
+    /// Returns the record for a `Send` or `Receive` event.
+    pub fn from_event(at: Instant, event: &TraceEvent<'a>) -> Option<Self> {
+        let (direction, service, correlation, message) = match *event {
+            TraceEvent::Send {
+                service,
+                correlation,
+                message,
+            } => (Direction::Sent, service, correlation, message),
+            TraceEvent::Receive {
+                service,
+                correlation,
+                message,
+            } => (Direction::Received, service, correlation, message),
+            _ => return None,
+        };
+        Some(Self {
+            direction,
+            at,
+            service,
+            correlation,
+            message,
+        })
+    }
+
+    /// Encodes the record header.
+    ///
+    /// Returns `None` if the message is longer than `u16::MAX` bytes.
+    pub fn header(&self) -> Option<[u8; HEADER_LEN]> {
+        let len = u16::try_from(self.message.len()).ok()?;
+        let mut header = [0u8; HEADER_LEN];
+        header[0] = match self.direction {
+            Direction::Sent => 0,
+            Direction::Received => 1,
+        };
+        header[1..9].copy_from_slice(&self.at.0.to_le_bytes());
+        header[9..13].copy_from_slice(&self.service.0.to_le_bytes());
+        header[13..15].copy_from_slice(&self.correlation.0.to_le_bytes());
+        header[15..17].copy_from_slice(&len.to_le_bytes());
+        Some(header)
+    }
+
+    /// Parses the record at the start of `buf`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the record and the bytes following it.
+    pub fn parse(buf: &'a [u8]) -> Result<(Self, &'a [u8]), CodecError> {
+        if buf.len() < HEADER_LEN {
+            return Err(CodecError::BufferTooSmall);
+        }
+        let (header, rest) = buf.split_at(HEADER_LEN);
+        let direction = match header[0] {
+            0 => Direction::Sent,
+            1 => Direction::Received,
+            _ => return Err(CodecError::Malformed),
+        };
+        let mut at = [0u8; 8];
+        at.copy_from_slice(&header[1..9]);
+        let service = u32::from_le_bytes([header[9], header[10], header[11], header[12]]);
+        let correlation = u16::from_le_bytes([header[13], header[14]]);
+        let len = usize::from(u16::from_le_bytes([header[15], header[16]]));
+        if rest.len() < len {
+            return Err(CodecError::BufferTooSmall);
+        }
+        let (message, rest) = rest.split_at(len);
+        let record = Self {
+            direction,
+            at: Instant(u64::from_le_bytes(at)),
+            service: ServiceId(service),
+            correlation: CorrelationId(correlation),
+            message,
+        };
+        Ok((record, rest))
+    }
+}
+
+/// Storage for capture records.
+///
+/// Records arrive through a shared reference, possibly from interrupt
+/// handlers, so the sink does its own synchronization, e.g. by reserving
+/// space in a RAM ring with an atomic write index.
+pub trait CaptureSink: Sync {
+    /// Appends one record. A sink that is full or busy drops the record.
+    fn write_record(&self, header: &[u8; HEADER_LEN], message: &[u8]);
+}
+
+/// A [`Tracer`] writing `Send` and `Receive` events to a [`CaptureSink`].
+///
+/// Other events are ignored; combine with another tracer in a tuple to keep them.
+/// Messages longer than `u16::MAX` bytes are dropped. The capture is `Sync`,
+/// so it can be a `static` handed to [`super::Tracing::set_tracer`].
+pub struct ReplayCapture<S> {
+    sink: S,
+}
+
+impl<S: CaptureSink> ReplayCapture<S> {
+    pub const fn new(sink: S) -> Self {
+        Self { sink }
+    }
+
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    pub fn into_inner(self) -> S {
+        self.sink
+    }
+}
+
+impl<S: CaptureSink> Tracer for ReplayCapture<S> {
+    fn trace(&self, at: Instant, event: &TraceEvent<'_>) {
+        let Some(record) = CaptureRecord::from_event(at, event) else {
+            return;
+        };
+        if let Some(header) = record.header() {
+            self.sink.write_record(&header, record.message);
+        }
+    }
+}
+
+/// Iterator over the records of a capture stream.
+///
+/// Stops after the first malformed or truncated record, which it yields as an error.
+pub struct ReplayReader<'a> {
+    remaining: &'a [u8],
+}
+
+impl<'a> ReplayReader<'a> {
+    pub const fn new(capture: &'a [u8]) -> Self {
+        Self { remaining: capture }
+    }
+}
+
+impl<'a> Iterator for ReplayReader<'a> {
+    type Item = Result<CaptureRecord<'a>, CodecError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining.is_empty() {
+            return None;
+        }
+        match CaptureRecord::parse(self.remaining) {
+            Ok((record, rest)) => {
+                self.remaining = rest;
+                Some(Ok(record))
+            }
+            Err(error) => {
+                self.remaining = &[];
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+
+    use super::super::Tracing;
+    use super::*;
+    use crate::messaging::common::ErrorKind;
+
+    /// Concatenates records into a fixed buffer, like a RAM ring that never
+    /// wraps. Writers reserve their space with the atomic length first.
+    struct Buffer {
+        bytes: [AtomicU8; 64],
+        len: AtomicUsize,
+    }
+
+    impl Buffer {
+        const fn new() -> Self {
+            Self {
+                bytes: [const { AtomicU8::new(0) }; 64],
+                len: AtomicUsize::new(0),
+            }
+        }
+
+        fn contents(&self, out: &mut [u8; 64]) -> usize {
+            let len = self.len.load(Ordering::Acquire);
+            for (byte, cell) in out.iter_mut().zip(&self.bytes[..len]) {
+                *byte = cell.load(Ordering::Relaxed);
+            }
+            len
+        }
+    }
+
+    impl CaptureSink for Buffer {
+        fn write_record(&self, header: &[u8; HEADER_LEN], message: &[u8]) {
+            let size = HEADER_LEN + message.len();
+            let reserved = self.len.fetch_update(Ordering::AcqRel, Ordering::Acquire, |len| {
+                (len + size <= self.bytes.len()).then_some(len + size)
+            });
+            let Ok(start) = reserved else {
+                return;
+            };
+            let cells = self.bytes[start..start + size].iter();
+            for (cell, &byte) in cells.zip(header.iter().chain(message)) {
+                cell.store(byte, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Stands in for a transport that keeps a `&'static dyn Tracer`.
+    struct Transport(Option<&'static dyn Tracer>);
+
+    impl Tracing for Transport {
+        fn set_tracer(&mut self, tracer: Option<&'static dyn Tracer>) {
+            self.0 = tracer;
+        }
+    }
+
+    fn record(direction: Direction, message: &[u8]) -> CaptureRecord<'_> {
+        CaptureRecord {
+            direction,
+            at: Instant(0x0102_0304_0506_0708),
+            service: ServiceId(0x0A0B_0C0D),
+            correlation: CorrelationId(0x1122),
+            message,
+        }
+    }
+
+    #[test]
+    fn header_layout() {
+        let header = record(Direction::Received, &[0; 3]).header().unwrap();
+        assert_eq!(
+            header,
+            [1, 8, 7, 6, 5, 4, 3, 2, 1, 0x0D, 0x0C, 0x0B, 0x0A, 0x22, 0x11, 3, 0]
+        );
+    }
+
+    #[test]
+    fn parse_round_trips_and_returns_the_rest() {
+        let original = record(Direction::Sent, b"ping");
+        let mut buf = [0u8; HEADER_LEN + 6];
+        buf[..HEADER_LEN].copy_from_slice(&original.header().unwrap());
+        buf[HEADER_LEN..HEADER_LEN + 4].copy_from_slice(b"ping");
+        buf[HEADER_LEN + 4..].copy_from_slice(&[0xEE, 0xFF]);
+
+        let (parsed, rest) = CaptureRecord::parse(&buf).unwrap();
+        assert_eq!(parsed, original);
+        assert_eq!(rest, &[0xEE, 0xFF]);
+    }
+
+    #[test]
+    fn parse_rejects_truncated_and_malformed_records() {
+        let header = record(Direction::Sent, b"ping").header().unwrap();
+        assert_eq!(CaptureRecord::parse(&header[..HEADER_LEN - 1]), Err(CodecError::BufferTooSmall));
+        // The header announces four message bytes that are missing.
+        assert_eq!(CaptureRecord::parse(&header), Err(CodecError::BufferTooSmall));
+
+        let mut bad = [0u8; HEADER_LEN];
+        bad[0] = 2;
+        assert_eq!(CaptureRecord::parse(&bad), Err(CodecError::Malformed));
+    }
+
+    #[test]
+    fn oversized_message_has_no_header() {
+        static LARGE: [u8; 0x1_0000] = [0; 0x1_0000];
+        assert_eq!(record(Direction::Sent, &LARGE).header(), None);
+        assert!(record(Direction::Sent, &LARGE[1..]).header().is_some());
+    }
+
+    #[test]
+    fn capture_and_read_back() {
+        let capture = ReplayCapture::new(Buffer::new());
+        let service = ServiceId(5);
+        let correlation = CorrelationId(9);
+        capture.trace(
+            Instant(1),
+            &TraceEvent::Send {
+                service,
+                correlation,
+                message: b"req",
+            },
+        );
+        capture.trace(
+            Instant(2),
+            &TraceEvent::Drop {
+                service,
+                correlation,
+                reason: ErrorKind::Other,
+            },
+        );
+        capture.trace(
+            Instant(3),
+            &TraceEvent::Receive {
+                service,
+                correlation,
+                message: b"resp",
+            },
+        );
+        let mut bytes = [0u8; 64];
+        let len = capture.sink().contents(&mut bytes);
+        assert_eq!(len, 2 * HEADER_LEN + 7);
+
+        let mut reader = ReplayReader::new(&bytes[..len]);
+        let sent = reader.next().unwrap().unwrap();
+        assert_eq!((sent.direction, sent.at, sent.message), (Direction::Sent, Instant(1), &b"req"[..]));
+        let received = reader.next().unwrap().unwrap();
+        assert_eq!(
+            (received.direction, received.at, received.message),
+            (Direction::Received, Instant(3), &b"resp"[..])
+        );
+        assert_eq!(reader.next(), None);
+    }
+
+    #[test]
+    fn reader_stops_after_an_error() {
+        let header = record(Direction::Sent, b"ping").header().unwrap();
+        let mut reader = ReplayReader::new(&header);
+        assert_eq!(reader.next(), Some(Err(CodecError::BufferTooSmall)));
+        assert_eq!(reader.next(), None);
+    }
+
+    #[test]
+    fn static_capture_is_installable() {
+        static CAPTURE: ReplayCapture<Buffer> = ReplayCapture::new(Buffer::new());
+        let mut transport = Transport(None);
+        transport.set_tracer(Some(&CAPTURE));
+        let tracer = transport.0.unwrap();
+        let event = TraceEvent::Send {
+            service: ServiceId(1),
+            correlation: CorrelationId(2),
+            message: &[0; 40],
+        };
+        tracer.trace(Instant(0), &event);
+        tracer.trace(Instant(1), &event);
+        // The second record does not fit and is dropped.
+        let mut bytes = [0u8; 64];
+        assert_eq!(CAPTURE.sink().contents(&mut bytes), HEADER_LEN + 40);
+    }
+}
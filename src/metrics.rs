@@ -0,0 +1,58 @@
+//! Counters, gauges and histograms for drivers and services.
+//!
+//! Metrics are identified by a [`MetricId`] chosen at compile time; the
+//! recorder decides how values are stored and exported, e.g. as fixed
+//! tables read over a debug channel. Nothing here allocates.
+
+/// Identifier of a metric.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct MetricId {
+    /// Numeric ID, unique within the firmware image.
+    pub id: u16,
+    /// Name for exporters that report by name.
+    pub name: &'static str,
+}
+
+impl MetricId {
+    pub const fn new(id: u16, name: &'static str) -> Self {
+        Self { id, name }
+    }
+}
+
+/// Destination of metric updates.
+///
+/// Methods take `&self` so a single recorder can be shared between drivers,
+/// typically as a `static` with interior mutability. Unknown IDs are ignored.
+pub trait Metrics {
+    /// Adds `by` to the counter `id`.
+    fn increment(&self, id: MetricId, by: u64);
+
+    /// Sets the gauge `id` to `value`.
+    fn set_gauge(&self, id: MetricId, value: i64);
+
+    /// Records one sample of `value` in the histogram `id`.
+    fn record(&self, id: MetricId, value: u64);
+}
+
+/// Discards every update.
+impl Metrics for () {
+    fn increment(&self, _id: MetricId, _by: u64) {}
+
+    fn set_gauge(&self, _id: MetricId, _value: i64) {}
+
+    fn record(&self, _id: MetricId, _value: u64) {}
+}
+
+impl<T: Metrics + ?Sized> Metrics for &T {
+    fn increment(&self, id: MetricId, by: u64) {
+        T::increment(self, id, by)
+    }
+
+    fn set_gauge(&self, id: MetricId, value: i64) {
+        T::set_gauge(self, id, value)
+    }
+
+    fn record(&self, id: MetricId, value: u64) {
+        T::record(self, id, value)
+    }
+}
@@ -0,0 +1,53 @@
+//! In-memory and loopback reference implementations.
+//!
+//! Lets downstream crates unit-test drivers and services against the traits
+//! without hardware or hand-rolled fakes: a RAM-backed block device,
+//! loopback I2C and I3C controller/target pairs, software SHA-2 digests
+//! and an in-process messaging transport.
+//!
+//! Enabled by the `mock` feature. These types favour simplicity and
+//! predictability over speed and are not meant for production firmware.
+
+pub mod block_device;
+pub mod digest;
+pub mod i2c;
+pub mod i3c;
+pub mod messaging;
+
+/// Fixed-capacity FIFO of bus events shared by the loopback pairs.
+#[derive(Debug)]
+struct EventRing<E, const N: usize> {
+    events: [Option<E>; N],
+    head: usize,
+    len: usize,
+}
+
+impl<E: Copy, const N: usize> EventRing<E, N> {
+    const fn new() -> Self {
+        Self {
+            events: [None; N],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns `false` if the ring is full.
+    fn push(&mut self, event: E) -> bool {
+        if self.len == N {
+            return false;
+        }
+        self.events[(self.head + self.len) % N] = Some(event);
+        self.len += 1;
+        true
+    }
+
+    fn pop(&mut self) -> Option<E> {
+        if self.len == 0 {
+            return None;
+        }
+        let event = self.events[self.head].take();
+        self.head = (self.head + 1) % N;
+        self.len -= 1;
+        event
+    }
+}
@@ -0,0 +1,91 @@
+//! RAM-backed block device with NOR flash semantics.
+
+use crate::block_device::{BlockDevice, ErrorType, ReadBlockDevice};
+use crate::error::ErrorKind;
+
+/// A block device of `N` bytes kept in memory.
+///
+/// Behaves like NOR flash: erasing sets bytes to `0xFF` and programming can
+/// only clear bits, so a missing erase shows up as corrupted data just as on
+/// real hardware. Reads have a granularity of one byte.
+#[derive(Debug, Clone)]
+pub struct RamBlockDevice<const N: usize> {
+    data: [u8; N],
+    erase_size: usize,
+    program_size: usize,
+}
+
+impl<const N: usize> RamBlockDevice<N> {
+    /// Creates an erased device.
+    ///
+    /// Returns `None` unless both sizes are non-zero, `program_size` divides
+    /// `erase_size` and `erase_size` divides `N`.
+    pub fn new(erase_size: usize, program_size: usize) -> Option<Self> {
+        let valid = program_size != 0
+            && erase_size != 0
+            && erase_size.is_multiple_of(program_size)
+            && N.is_multiple_of(erase_size);
+        valid.then_some(Self {
+            data: [0xFF; N],
+            erase_size,
+            program_size,
+        })
+    }
+
+    /// Returns the whole contents, for assertions in tests.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    fn range(&self, addr: usize, len: usize, align: usize) -> Result<core::ops::Range<usize>, ErrorKind> {
+        if !addr.is_multiple_of(align) || !len.is_multiple_of(align) {
+            return Err(ErrorKind::InvalidParameter);
+        }
+        let end = addr.checked_add(len).filter(|&end| end <= N).ok_or(ErrorKind::OutOfBounds)?;
+        Ok(addr..end)
+    }
+}
+
+impl<const N: usize> ErrorType for RamBlockDevice<N> {
+    type Error = ErrorKind;
+}
+
+impl<const N: usize> ReadBlockDevice for RamBlockDevice<N> {
+    fn read_size(&self) -> usize {
+        1
+    }
+
+    fn read(&mut self, block_addr: usize, data: &mut [u8]) -> Result<(), Self::Error> {
+        let range = self.range(block_addr, data.len(), 1)?;
+        data.copy_from_slice(&self.data[range]);
+        Ok(())
+    }
+
+    fn capacity(&self) -> usize {
+        N
+    }
+}
+
+impl<const N: usize> BlockDevice for RamBlockDevice<N> {
+    fn erase_size(&self) -> usize {
+        self.erase_size
+    }
+
+    fn erase(&mut self, block_addr: usize, size_in_bytes: usize) -> Result<(), Self::Error> {
+        let range = self.range(block_addr, size_in_bytes, self.erase_size)?;
+        self.data[range].fill(0xFF);
+        Ok(())
+    }
+
+    fn program_size(&self) -> usize {
+        self.program_size
+    }
+
+    fn program(&mut self, block_addr: usize, data: &[u8]) -> Result<(), Self::Error> {
+        let range = self.range(block_addr, data.len(), self.program_size)?;
+        for (cell, byte) in self.data[range].iter_mut().zip(data) {
+            *cell &= byte;
+        }
+        Ok(())
+    }
+}
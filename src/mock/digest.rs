@@ -0,0 +1,81 @@
+//! Software SHA-2 digests backed by the `sha2` crate.
+//!
+//! Each type also implements [`HmacFromDigest`], so e.g.
+//! `SoftSha256::default().into_hmac()` gives a software HMAC-SHA256.
+
+use sha2::Digest as _;
+
+use crate::common::Zeroize;
+use crate::digest::{self, Digest, DigestMarker, DigestOf, ErrorKind, ErrorType};
+use crate::mac::hmac::HmacFromDigest;
+
+macro_rules! soft_digest {
+    ($(#[$doc:meta])* $name:ident, $inner:ty, $marker:ty) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Default)]
+        pub struct $name($inner);
+
+        impl ErrorType for $name {
+            type Error = ErrorKind;
+        }
+
+        impl Digest for $name {
+            type InitParams = ();
+
+            fn init(_init_params: Self::InitParams) -> Result<(), Self::Error> {
+                Ok(())
+            }
+
+            fn update(&mut self, input: &mut [u8]) -> Result<(), Self::Error> {
+                self.0.update(&*input);
+                Ok(())
+            }
+
+            fn reset(&mut self) -> Result<(), Self::Error> {
+                self.0 = <$inner>::new();
+                Ok(())
+            }
+
+            fn finalize(&mut self, out: &mut [u8]) -> Result<usize, Self::Error> {
+                let len = <$marker as DigestMarker>::OUTPUT_SIZE;
+                let out = out.get_mut(..len).ok_or(ErrorKind::InvalidOutputSize)?;
+                out.copy_from_slice(&self.0.finalize_reset());
+                Ok(len)
+            }
+        }
+
+        impl Zeroize for $name {
+            fn zeroize(&mut self) {
+                self.0 = <$inner>::new();
+            }
+        }
+
+        impl DigestOf for $name {
+            type Algorithm = $marker;
+        }
+
+        impl HmacFromDigest for $name {
+            const BLOCK_SIZE: usize = <$marker as DigestMarker>::BLOCK_SIZE;
+            const OUTPUT_SIZE: usize = <$marker as DigestMarker>::OUTPUT_SIZE;
+        }
+    };
+}
+
+soft_digest!(
+    /// Software SHA-256.
+    SoftSha256,
+    sha2::Sha256,
+    digest::Sha256
+);
+soft_digest!(
+    /// Software SHA-384.
+    SoftSha384,
+    sha2::Sha384,
+    digest::Sha384
+);
+soft_digest!(
+    /// Software SHA-512.
+    SoftSha512,
+    sha2::Sha512,
+    digest::Sha512
+);
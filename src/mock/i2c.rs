@@ -0,0 +1,189 @@
+//! Loopback I2C controller and target.
+//!
+//! An [`I2cLoopback`] models one bus with a single target. The
+//! [`LoopbackI2cTarget`] half implements [`I2cTarget`] for the code under
+//! test; the [`LoopbackI2cController`] half plays the controller from the
+//! test and produces the events the target would see on real hardware.
+
+use core::cell::RefCell;
+
+use crate::error::ErrorKind;
+use crate::i2c_target::{ErrorType, Event, I2cTarget};
+
+use super::EventRing;
+
+/// Events the target can queue before the controller gets [`ErrorKind::Overrun`].
+const EVENT_DEPTH: usize = 8;
+
+#[derive(Debug)]
+struct Bus<const N: usize> {
+    address: Option<u8>,
+    enabled: bool,
+    events: EventRing<Event, EVENT_DEPTH>,
+    received: [u8; N],
+    received_len: usize,
+    response: [u8; N],
+    response_len: usize,
+}
+
+/// Shared state of a loopback bus with buffers of `N` bytes.
+#[derive(Debug)]
+pub struct I2cLoopback<const N: usize> {
+    bus: RefCell<Bus<N>>,
+}
+
+impl<const N: usize> I2cLoopback<N> {
+    pub const fn new() -> Self {
+        Self {
+            bus: RefCell::new(Bus {
+                address: None,
+                enabled: false,
+                events: EventRing::new(),
+                received: [0; N],
+                received_len: 0,
+                response: [0; N],
+                response_len: 0,
+            }),
+        }
+    }
+
+    pub fn target(&self) -> LoopbackI2cTarget<'_, N> {
+        LoopbackI2cTarget { loopback: self }
+    }
+
+    pub fn controller(&self) -> LoopbackI2cController<'_, N> {
+        LoopbackI2cController { loopback: self }
+    }
+}
+
+impl<const N: usize> Default for I2cLoopback<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Target half of an [`I2cLoopback`].
+#[derive(Debug)]
+pub struct LoopbackI2cTarget<'a, const N: usize> {
+    loopback: &'a I2cLoopback<N>,
+}
+
+impl<const N: usize> ErrorType for LoopbackI2cTarget<'_, N> {
+    type Error = ErrorKind;
+}
+
+impl<const N: usize> I2cTarget for LoopbackI2cTarget<'_, N> {
+    fn set_address(&mut self, addr: u8) -> Result<(), Self::Error> {
+        if addr > 0x7F {
+            return Err(ErrorKind::InvalidAddress);
+        }
+        self.loopback.bus.borrow_mut().address = Some(addr);
+        Ok(())
+    }
+
+    fn enable(&mut self) -> Result<(), Self::Error> {
+        self.loopback.bus.borrow_mut().enabled = true;
+        Ok(())
+    }
+
+    fn disable(&mut self) -> Result<(), Self::Error> {
+        self.loopback.bus.borrow_mut().enabled = false;
+        Ok(())
+    }
+
+    fn poll_event(&mut self) -> Result<Option<Event>, Self::Error> {
+        Ok(self.loopback.bus.borrow_mut().events.pop())
+    }
+
+    fn read_received(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let bus = self.loopback.bus.borrow();
+        let len = bus.received_len.min(buf.len());
+        buf[..len].copy_from_slice(&bus.received[..len]);
+        Ok(len)
+    }
+
+    fn set_response(&mut self, data: &[u8]) -> Result<usize, Self::Error> {
+        let mut bus = self.loopback.bus.borrow_mut();
+        let len = data.len().min(N);
+        bus.response[..len].copy_from_slice(&data[..len]);
+        bus.response_len = len;
+        Ok(len)
+    }
+}
+
+/// Controller half of an [`I2cLoopback`], driven by the test.
+#[derive(Debug)]
+pub struct LoopbackI2cController<'a, const N: usize> {
+    loopback: &'a I2cLoopback<N>,
+}
+
+impl<const N: usize> LoopbackI2cController<'_, N> {
+    fn address(bus: &Bus<N>, addr: u8) -> Result<(), ErrorKind> {
+        if bus.enabled && bus.address == Some(addr) {
+            Ok(())
+        } else {
+            Err(ErrorKind::Nack)
+        }
+    }
+
+    fn push(bus: &mut Bus<N>, event: Event) -> Result<(), ErrorKind> {
+        if bus.events.push(event) {
+            Ok(())
+        } else {
+            Err(ErrorKind::Overrun)
+        }
+    }
+
+    fn write_part(bus: &mut Bus<N>, data: &[u8]) -> Result<(), ErrorKind> {
+        if data.len() > N {
+            return Err(ErrorKind::Overrun);
+        }
+        Self::push(bus, Event::WriteRequest)?;
+        bus.received[..data.len()].copy_from_slice(data);
+        bus.received_len = data.len();
+        Self::push(bus, Event::WriteComplete { len: data.len() })
+    }
+
+    fn read_part(bus: &mut Bus<N>, buf: &mut [u8]) -> Result<usize, ErrorKind> {
+        Self::push(bus, Event::ReadRequest)?;
+        let len = bus.response_len.min(buf.len());
+        buf[..len].copy_from_slice(&bus.response[..len]);
+        Self::push(bus, Event::ReadComplete { sent: len })?;
+        Ok(len)
+    }
+
+    /// Writes `data` to the target at `addr`, followed by a STOP.
+    ///
+    /// Fails with [`ErrorKind::Nack`] if no enabled target has the address.
+    pub fn write(&mut self, addr: u8, data: &[u8]) -> Result<(), ErrorKind> {
+        let mut bus = self.loopback.bus.borrow_mut();
+        Self::address(&bus, addr)?;
+        Self::write_part(&mut bus, data)?;
+        Self::push(&mut bus, Event::Stop)
+    }
+
+    /// Reads from the target at `addr`, followed by a STOP.
+    ///
+    /// Returns the number of staged bytes read; the rest of `buf` is untouched.
+    pub fn read(&mut self, addr: u8, buf: &mut [u8]) -> Result<usize, ErrorKind> {
+        let mut bus = self.loopback.bus.borrow_mut();
+        Self::address(&bus, addr)?;
+        let len = Self::read_part(&mut bus, buf)?;
+        Self::push(&mut bus, Event::Stop)?;
+        Ok(len)
+    }
+
+    /// Writes `data`, then reads after a repeated START.
+    ///
+    /// The target's handler must stage its response before the read, so the
+    /// test calls its serving loop between [`Self::write`] and [`Self::read`]
+    /// instead when the response depends on the written data.
+    pub fn write_read(&mut self, addr: u8, data: &[u8], buf: &mut [u8]) -> Result<usize, ErrorKind> {
+        let mut bus = self.loopback.bus.borrow_mut();
+        Self::address(&bus, addr)?;
+        Self::write_part(&mut bus, data)?;
+        let len = Self::read_part(&mut bus, buf)?;
+        Self::push(&mut bus, Event::Stop)?;
+        Ok(len)
+    }
+}
@@ -0,0 +1,156 @@
+//! Loopback I3C controller and target.
+//!
+//! Like [`super::i2c`], but the controller half implements [`I3cMaster`], so
+//! controller-side drivers and target-side firmware can be tested against
+//! each other. The target starts with the dynamic address given to
+//! [`I3cLoopback::new`]; address assignment is not modelled.
+
+use core::cell::RefCell;
+
+use crate::error::ErrorKind;
+use crate::i3c_master::{self, I3cMaster};
+use crate::i3c_target::{self, DeviceCharacteristics, Event, I3cTarget};
+
+use super::EventRing;
+
+/// Events the target can queue before the controller gets [`ErrorKind::Overrun`].
+const EVENT_DEPTH: usize = 8;
+
+#[derive(Debug)]
+struct Bus<const N: usize> {
+    events: EventRing<Event, EVENT_DEPTH>,
+    received: [u8; N],
+    received_len: usize,
+    response: [u8; N],
+    response_len: usize,
+}
+
+/// Shared state of a loopback bus with buffers of `N` bytes.
+#[derive(Debug)]
+pub struct I3cLoopback<const N: usize> {
+    characteristics: DeviceCharacteristics,
+    address: u8,
+    bus: RefCell<Bus<N>>,
+}
+
+impl<const N: usize> I3cLoopback<N> {
+    /// Creates a bus with one target at dynamic address `address`.
+    pub const fn new(characteristics: DeviceCharacteristics, address: u8) -> Self {
+        Self {
+            characteristics,
+            address,
+            bus: RefCell::new(Bus {
+                events: EventRing::new(),
+                received: [0; N],
+                received_len: 0,
+                response: [0; N],
+                response_len: 0,
+            }),
+        }
+    }
+
+    pub fn target(&self) -> LoopbackI3cTarget<'_, N> {
+        LoopbackI3cTarget { loopback: self }
+    }
+
+    pub fn controller(&self) -> LoopbackI3cController<'_, N> {
+        LoopbackI3cController { loopback: self }
+    }
+}
+
+/// Target half of an [`I3cLoopback`].
+#[derive(Debug)]
+pub struct LoopbackI3cTarget<'a, const N: usize> {
+    loopback: &'a I3cLoopback<N>,
+}
+
+impl<const N: usize> i3c_target::ErrorType for LoopbackI3cTarget<'_, N> {
+    type Error = ErrorKind;
+}
+
+impl<const N: usize> I3cTarget for LoopbackI3cTarget<'_, N> {
+    fn characteristics(&self) -> DeviceCharacteristics {
+        self.loopback.characteristics
+    }
+
+    fn dynamic_address(&self) -> Option<u8> {
+        Some(self.loopback.address)
+    }
+
+    fn poll_event(&mut self) -> Result<Option<Event>, Self::Error> {
+        Ok(self.loopback.bus.borrow_mut().events.pop())
+    }
+
+    fn read_received(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let bus = self.loopback.bus.borrow();
+        let len = bus.received_len.min(buf.len());
+        buf[..len].copy_from_slice(&bus.received[..len]);
+        Ok(len)
+    }
+
+    fn set_response(&mut self, data: &[u8]) -> Result<usize, Self::Error> {
+        let mut bus = self.loopback.bus.borrow_mut();
+        let len = data.len().min(N);
+        bus.response[..len].copy_from_slice(&data[..len]);
+        bus.response_len = len;
+        Ok(len)
+    }
+}
+
+/// Controller half of an [`I3cLoopback`].
+#[derive(Debug)]
+pub struct LoopbackI3cController<'a, const N: usize> {
+    loopback: &'a I3cLoopback<N>,
+}
+
+impl<const N: usize> LoopbackI3cController<'_, N> {
+    fn bus(&self, addr: u8) -> Result<core::cell::RefMut<'_, Bus<N>>, ErrorKind> {
+        if addr == self.loopback.address {
+            Ok(self.loopback.bus.borrow_mut())
+        } else {
+            Err(ErrorKind::Nack)
+        }
+    }
+
+    fn write_part(bus: &mut Bus<N>, data: &[u8]) -> Result<(), ErrorKind> {
+        if data.len() > N {
+            return Err(ErrorKind::Overrun);
+        }
+        bus.received[..data.len()].copy_from_slice(data);
+        bus.received_len = data.len();
+        if bus.events.push(Event::PrivateWrite { len: data.len() }) {
+            Ok(())
+        } else {
+            Err(ErrorKind::Overrun)
+        }
+    }
+
+    fn read_part(bus: &mut Bus<N>, buf: &mut [u8]) -> Result<usize, ErrorKind> {
+        if !bus.events.push(Event::PrivateRead) {
+            return Err(ErrorKind::Overrun);
+        }
+        let len = bus.response_len.min(buf.len());
+        buf[..len].copy_from_slice(&bus.response[..len]);
+        Ok(len)
+    }
+}
+
+impl<const N: usize> i3c_master::ErrorType for LoopbackI3cController<'_, N> {
+    type Error = ErrorKind;
+}
+
+impl<const N: usize> I3cMaster for LoopbackI3cController<'_, N> {
+    fn write(&mut self, addr: u8, data: &[u8]) -> Result<(), Self::Error> {
+        Self::write_part(&mut *self.bus(addr)?, data)
+    }
+
+    fn read(&mut self, addr: u8, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        Self::read_part(&mut *self.bus(addr)?, buf)
+    }
+
+    fn write_read(&mut self, addr: u8, data: &[u8], buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let mut bus = self.bus(addr)?;
+        Self::write_part(&mut bus, data)?;
+        Self::read_part(&mut bus, buf)
+    }
+}
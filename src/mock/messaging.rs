@@ -0,0 +1,140 @@
+//! In-process messaging transport.
+//!
+//! [`InProcessClient`] delivers requests straight to a [`Service`] and
+//! [`InProcessHost`] serves requests injected by the test. Both run every
+//! message through its [`Encode`] and [`Decode`] implementations, so codec
+//! bugs show up as they would on a real transport.
+
+use crate::messaging::client::Client;
+use crate::messaging::common::{Decode, Encode, Error, ErrorKind, ErrorType, ServiceId};
+use crate::messaging::service::{Service, ServiceHost};
+
+fn codec_error(_: crate::messaging::common::CodecError) -> ErrorKind {
+    ErrorKind::Encode
+}
+
+/// A [`Client`] connected directly to one service, with an `N`-byte request buffer.
+#[derive(Debug)]
+pub struct InProcessClient<S, const N: usize> {
+    service: S,
+    request_buf: [u8; N],
+}
+
+impl<S: Service, const N: usize> InProcessClient<S, N> {
+    pub fn new(service: S) -> Self {
+        Self {
+            service,
+            request_buf: [0; N],
+        }
+    }
+
+    pub fn service(&mut self) -> &mut S {
+        &mut self.service
+    }
+
+    pub fn into_inner(self) -> S {
+        self.service
+    }
+}
+
+impl<S: Service, const N: usize> ErrorType for InProcessClient<S, N> {
+    type Error = ErrorKind;
+}
+
+impl<S: Service, const N: usize> Client for InProcessClient<S, N> {
+    /// Fails with [`ErrorKind::UnknownService`] unless `service` is `S::ID`,
+    /// and with the kind of the service's error if it fails.
+    fn request<'r, Req, Resp>(
+        &mut self,
+        service: ServiceId,
+        request: &Req,
+        response_buf: &'r mut [u8],
+    ) -> Result<Resp, Self::Error>
+    where
+        Req: Encode,
+        Resp: Decode<'r>,
+    {
+        if service != S::ID {
+            return Err(ErrorKind::UnknownService);
+        }
+        let len = request.encode(&mut self.request_buf).map_err(codec_error)?;
+        let decoded = S::Request::decode(&self.request_buf[..len]).map_err(|_| ErrorKind::Decode)?;
+        let response = self.service.handle(decoded).map_err(|e| e.kind())?;
+        let len = response.encode(response_buf).map_err(codec_error)?;
+        Resp::decode(&response_buf[..len]).map_err(|_| ErrorKind::Decode)
+    }
+}
+
+/// A [`ServiceHost`] fed by the test, holding one pending request and the
+/// last response, each of up to `N` bytes.
+#[derive(Debug)]
+pub struct InProcessHost<const N: usize> {
+    request: [u8; N],
+    request_len: Option<usize>,
+    response: [u8; N],
+    response_len: Option<usize>,
+}
+
+impl<const N: usize> InProcessHost<N> {
+    pub const fn new() -> Self {
+        Self {
+            request: [0; N],
+            request_len: None,
+            response: [0; N],
+            response_len: None,
+        }
+    }
+
+    /// Queues an encoded request for the next `serve_one`.
+    ///
+    /// Fails with [`ErrorKind::Busy`] if a request is already pending.
+    pub fn push_request(&mut self, request: &[u8]) -> Result<(), ErrorKind> {
+        if self.request_len.is_some() {
+            return Err(ErrorKind::Busy);
+        }
+        self.request
+            .get_mut(..request.len())
+            .ok_or(ErrorKind::MessageTooLarge)?
+            .copy_from_slice(request);
+        self.request_len = Some(request.len());
+        Ok(())
+    }
+
+    /// Takes the encoded response sent by the last `serve_one`.
+    pub fn take_response(&mut self) -> Option<&[u8]> {
+        self.response_len.take().map(|len| &self.response[..len])
+    }
+}
+
+impl<const N: usize> Default for InProcessHost<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> ErrorType for InProcessHost<N> {
+    type Error = ErrorKind;
+}
+
+impl<const N: usize> ServiceHost for InProcessHost<N> {
+    /// Fails with [`ErrorKind::Timeout`] if no request was pushed.
+    fn serve_one<S: Service>(
+        &mut self,
+        service: &mut S,
+        rx_buf: &mut [u8],
+        tx_buf: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        let len = self.request_len.take().ok_or(ErrorKind::Timeout)?;
+        let rx = rx_buf.get_mut(..len).ok_or(ErrorKind::BufferTooSmall)?;
+        rx.copy_from_slice(&self.request[..len]);
+        let request = S::Request::decode(rx).map_err(|_| ErrorKind::Decode)?;
+        let response = service.handle(request).map_err(|e| e.kind())?;
+        let len = response.encode(tx_buf).map_err(codec_error)?;
+        self.response
+            .get_mut(..len)
+            .ok_or(ErrorKind::MessageTooLarge)?
+            .copy_from_slice(&tx_buf[..len]);
+        self.response_len = Some(len);
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+//! Monotonic counters and anti-rollback protection.
+//!
+//! Counters are typically backed by OTP bits, where each increment burns one
+//! more fuse, or by dedicated counter hardware.
+
+pub use crate::error::{Error, ErrorKind};
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// Integrity status of a counter.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TamperStatus {
+    /// The stored value is consistent.
+    Intact,
+    /// The hardware detected an inconsistent or manipulated value.
+    Tampered,
+}
+
+/// A counter that can only count up.
+pub trait MonotonicCounter: ErrorType {
+    /// Returns the current value.
+    fn read(&self) -> Result<u64, Self::Error>;
+
+    /// Increments the counter by one.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the new value, or an error of kind [`ErrorKind::OutOfBounds`]
+    /// once [`MonotonicCounter::max_value`] is reached.
+    fn increment(&mut self) -> Result<u64, Self::Error>;
+
+    /// Largest value the counter can reach.
+    fn max_value(&self) -> u64;
+
+    fn tamper_status(&self) -> Result<TamperStatus, Self::Error>;
+
+    /// Increments the counter until it is at least `value`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success, or [`AdvanceError::OutOfBounds`] without
+    /// incrementing if `value` exceeds [`MonotonicCounter::max_value`], so a
+    /// corrupt target value cannot burn the remaining increments.
+    fn advance_to(&mut self, value: u64) -> Result<(), AdvanceError<Self::Error>> {
+        if value > self.max_value() {
+            return Err(AdvanceError::OutOfBounds);
+        }
+        let mut current = self.read().map_err(AdvanceError::Counter)?;
+        while current < value {
+            current = self.increment().map_err(AdvanceError::Counter)?;
+        }
+        Ok(())
+    }
+}
+
+/// Failure of [`MonotonicCounter::advance_to`].
+#[derive(Debug)]
+pub enum AdvanceError<E> {
+    /// The target value is above the counter's maximum; nothing was incremented.
+    OutOfBounds,
+    /// The counter failed.
+    Counter(E),
+}
+
+impl<E: Error> Error for AdvanceError<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            AdvanceError::OutOfBounds => ErrorKind::OutOfBounds,
+            AdvanceError::Counter(e) => e.kind(),
+        }
+    }
+}
+
+/// Outcome of [`AntiRollback::check_version`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum RollbackCheck {
+    /// The image is at or above the minimum security version.
+    Accepted,
+    /// The image is older than the minimum security version.
+    Rollback,
+    /// The counter holding the minimum security version was tampered with.
+    Tampered,
+}
+
+/// Firmware security version policy on top of a [`MonotonicCounter`].
+///
+/// The counter value is the minimum security version (SVN) an image must
+/// carry to be booted.
+pub trait AntiRollback: MonotonicCounter {
+    /// Checks an image's security version against the stored minimum.
+    fn check_version(&self, image_svn: u64) -> Result<RollbackCheck, Self::Error> {
+        if self.tamper_status()? == TamperStatus::Tampered {
+            return Ok(RollbackCheck::Tampered);
+        }
+        if image_svn >= self.read()? {
+            Ok(RollbackCheck::Accepted)
+        } else {
+            Ok(RollbackCheck::Rollback)
+        }
+    }
+
+    /// Raises the minimum security version to `image_svn`, after the image was
+    /// booted and validated. Lower versions are ignored, and versions above
+    /// [`MonotonicCounter::max_value`] are rejected before any increment.
+    fn commit_version(&mut self, image_svn: u64) -> Result<(), AdvanceError<Self::Error>> {
+        self.advance_to(image_svn)
+    }
+}
+
+impl<T: MonotonicCounter> AntiRollback for T {}
@@ -0,0 +1,22 @@
+pub use crate::block_device::{Error, ErrorKind, ErrorType};
+
+/// Non-blocking block device.
+///
+/// Mirrors [`crate::block_device::BlockDevice`]. The first call of `read`,
+/// `erase` or `program` starts the operation; later calls with the same
+/// arguments poll it until it completes. Starting a different operation
+/// while one is in progress is an implementation-defined error.
+pub trait BlockDevice: ErrorType {
+    /// Get size of a readable block
+    fn read_size(&self) -> usize;
+    fn read(&mut self, block_addr: usize, data: &mut [u8]) -> nb::Result<(), Self::Error>;
+
+    fn erase_size(&self) -> usize;
+    fn erase(&mut self, block_addr: usize, size_in_bytes: usize) -> nb::Result<(), Self::Error>;
+
+    fn program_size(&self) -> usize;
+    fn program(&mut self, block_addr: usize, data: &[u8]) -> nb::Result<(), Self::Error>;
+
+    /// Size of the underlying device in bytes
+    fn capacity(&self) -> usize;
+}
@@ -0,0 +1,26 @@
+pub use crate::i3c_master::{Error, ErrorKind, ErrorType};
+
+/// Non-blocking I3C controller private transfers.
+///
+/// Mirrors [`crate::i3c_master::I3cMaster`]. The first call of a transfer
+/// starts it; later calls with the same arguments poll it until it
+/// completes. Starting a different transfer while one is in progress is an
+/// implementation-defined error.
+pub trait I3cMaster: ErrorType {
+    /// Private write to `addr`.
+    fn write(&mut self, addr: u8, data: &[u8]) -> nb::Result<(), Self::Error>;
+
+    /// Private read from `addr`. The target may end the read early.
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes read once the transfer completes.
+    fn read(&mut self, addr: u8, buf: &mut [u8]) -> nb::Result<usize, Self::Error>;
+
+    /// Private write followed by a read, separated by a repeated start.
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes read once the transfer completes.
+    fn write_read(&mut self, addr: u8, data: &[u8], buf: &mut [u8]) -> nb::Result<usize, Self::Error>;
+}
@@ -0,0 +1,15 @@
+//! Non-blocking variants of the peripheral traits.
+//!
+//! Methods return [`nb::Result`]: `Err(nb::Error::WouldBlock)` means the
+//! operation is still in progress and the method must be called again with
+//! the same arguments. This lets cooperative schedulers interleave long
+//! operations, such as block erases, with other work.
+//!
+//! I2C controllers have no mirror here, as the crate defines no blocking
+//! I2C controller trait to mirror.
+
+pub use ::nb::{Error, Result};
+
+pub mod block_device;
+pub mod i3c_master;
+pub mod spi_master;
@@ -0,0 +1,44 @@
+pub use crate::spi_master::{Config, Error, ErrorKind, ErrorType, Operation};
+
+/// Non-blocking SPI controller.
+///
+/// Mirrors [`crate::spi_master::SpiMaster`]. The first call of
+/// `transaction` starts it; later calls with the same arguments poll it
+/// until it completes. Configuration stays blocking as it only touches
+/// controller registers.
+pub trait SpiMaster: ErrorType {
+    /// Identifies a chip-select line driven by the controller.
+    type ChipSelect: Copy;
+
+    /// Applies `config` to all subsequent transactions on `cs`.
+    fn configure(&mut self, cs: Self::ChipSelect, config: &Config) -> Result<(), Self::Error>;
+
+    /// Performs `operations` in order with `cs` asserted throughout.
+    ///
+    /// Chip select is released when the transaction completes or fails.
+    fn transaction(
+        &mut self,
+        cs: Self::ChipSelect,
+        operations: &mut [Operation<'_>],
+    ) -> nb::Result<(), Self::Error>;
+
+    /// Full-duplex transfer in a transaction of its own.
+    fn transfer(
+        &mut self,
+        cs: Self::ChipSelect,
+        read: &mut [u8],
+        write: &[u8],
+    ) -> nb::Result<(), Self::Error> {
+        self.transaction(cs, &mut [Operation::Transfer(read, write)])
+    }
+
+    /// Write in a transaction of its own.
+    fn write(&mut self, cs: Self::ChipSelect, data: &[u8]) -> nb::Result<(), Self::Error> {
+        self.transaction(cs, &mut [Operation::Write(data)])
+    }
+
+    /// Read in a transaction of its own.
+    fn read(&mut self, cs: Self::ChipSelect, data: &mut [u8]) -> nb::Result<(), Self::Error> {
+        self.transaction(cs, &mut [Operation::Read(data)])
+    }
+}
@@ -0,0 +1,191 @@
+use core::fmt::Debug;
+
+pub use crate::error::{Error, ErrorKind};
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// Raw word access to the OTP array.
+pub trait OtpMemory: ErrorType {
+    /// Reads the word at `word_addr`.
+    fn read_word(&self, word_addr: usize) -> Result<u32, Self::Error>;
+
+    /// Programs the word at `word_addr`. Bits can only move from their erased
+    /// state to their programmed state.
+    fn program_word(&mut self, word_addr: usize, value: u32) -> Result<(), Self::Error>;
+
+    /// Size of the OTP array in words.
+    fn capacity_words(&self) -> usize;
+}
+
+/// Multi-word reads of the OTP array.
+pub trait OtpBurstRead: OtpMemory {
+    /// Number of words the controller reads most efficiently in one burst.
+    fn preferred_burst_words(&self) -> usize;
+
+    /// Reads `out.len()` consecutive words starting at `word_addr`.
+    fn read_words(&self, word_addr: usize, out: &mut [u32]) -> Result<(), Self::Error> {
+        for (addr, word) in (word_addr..).zip(out.iter_mut()) {
+            *word = self.read_word(addr)?;
+        }
+        Ok(())
+    }
+}
+
+/// Device lifecycle state recorded in OTP.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum LifecycleState {
+    /// Blank part, nothing provisioned.
+    Raw,
+    /// Manufacturing and test, debug typically open.
+    Manufacturing,
+    /// Deployed part.
+    Production,
+    /// Returned for failure analysis.
+    Rma,
+    /// End of life; the part no longer operates.
+    Scrapped,
+}
+
+/// Result of the ECC check of a region or word.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum EccStatus {
+    /// No error detected.
+    Clean,
+    /// Errors were detected and corrected; the data is valid.
+    Corrected {
+        /// Number of corrected bits.
+        bits: u32,
+    },
+    /// Errors were detected that could not be corrected.
+    Uncorrectable,
+}
+
+/// Location and state of an OTP region.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct RegionInfo {
+    /// First word of the region.
+    pub word_offset: usize,
+    /// Size of the region in words.
+    pub size_words: usize,
+    /// Whether the region is locked against programming.
+    pub locked: bool,
+}
+
+/// Partitioning of the OTP array into lockable regions.
+pub trait OtpRegions: OtpMemory {
+    /// Identifies a region, typically a platform-specific enum.
+    type RegionId: Copy;
+
+    /// Returns the layout and lock state of `region`.
+    fn region_info(&self, region: Self::RegionId) -> Result<RegionInfo, Self::Error>;
+
+    /// Permanently locks `region` against further programming.
+    fn lock_region(&mut self, region: Self::RegionId) -> Result<(), Self::Error>;
+
+    /// Returns the ECC status of `region`.
+    fn ecc_status(&self, region: Self::RegionId) -> Result<EccStatus, Self::Error>;
+}
+
+/// Lifecycle state query.
+pub trait OtpLifecycle: ErrorType {
+    /// Returns the current lifecycle state.
+    fn lifecycle_state(&self) -> Result<LifecycleState, Self::Error>;
+}
+
+/// A typed field stored in OTP.
+///
+/// Platform crates declare one marker type per field, e.g. a secure-boot
+/// enable bit or a key hash, and implement [`OtpField`] for their device.
+pub trait Field {
+    /// Decoded value of the field.
+    type Value;
+}
+
+/// Typed accessor for the field `F`.
+pub trait OtpField<F: Field>: ErrorType {
+    /// Reads and decodes the field.
+    fn read_field(&self) -> Result<F::Value, Self::Error>;
+
+    /// Encodes and programs the field.
+    fn program_field(&mut self, value: &F::Value) -> Result<(), Self::Error>;
+}
+
+/// State of the shadow copy of the OTP array.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ShadowState {
+    /// The shadow registers have not been loaded since reset.
+    Unloaded,
+    /// The shadow registers match the OTP array.
+    Current,
+    /// The OTP array was programmed after the shadow registers were loaded.
+    Stale,
+}
+
+/// Shadow registers holding a copy of the OTP array.
+///
+/// Controllers typically load OTP into shadow registers once at boot and
+/// serve reads from there, since sensing the fuse array is slow.
+pub trait OtpShadow: OtpMemory {
+    /// Loads the OTP array into the shadow registers.
+    fn load_shadow(&mut self) -> Result<(), Self::Error>;
+
+    /// Returns the state of the shadow registers.
+    fn shadow_state(&self) -> ShadowState;
+
+    /// Reads the word at `word_addr` from the shadow registers.
+    ///
+    /// Fails with [`ErrorKind::NotPermitted`] while the shadow is [`ShadowState::Unloaded`].
+    fn read_shadow_word(&self, word_addr: usize) -> Result<u32, Self::Error>;
+}
+
+/// Outcome of [`OtpStagedWrite::dry_run`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum DryRun {
+    /// Every staged word can be programmed.
+    Ok {
+        /// Number of staged words.
+        words: usize,
+    },
+    /// The staged word at `word_addr` cannot be programmed over its current value,
+    /// e.g. because it would need a programmed bit to return to its erased state,
+    /// or because its region is locked.
+    Conflict {
+        word_addr: usize,
+        current: u32,
+        requested: u32,
+    },
+}
+
+/// Buffered programming committed in one pass.
+///
+/// Writes are staged in RAM, checked against the current OTP contents with
+/// a dry run, then programmed together so a rejected change leaves the array
+/// untouched.
+pub trait OtpStagedWrite: OtpMemory {
+    /// Stages `value` for `word_addr`, replacing any value staged for it before.
+    ///
+    /// Fails with [`ErrorKind::Full`] when the staging buffer is full.
+    fn stage_word(&mut self, word_addr: usize, value: u32) -> Result<(), Self::Error>;
+
+    /// Returns the number of staged words.
+    fn staged_words(&self) -> usize;
+
+    /// Drops all staged words.
+    fn discard_staged(&mut self);
+
+    /// Checks every staged word against the current OTP contents without programming.
+    fn dry_run(&self) -> Result<DryRun, Self::Error>;
+
+    /// Runs a dry run and, if it passes, programs every staged word and
+    /// clears the staging buffer.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success, or an error of kind [`ErrorKind::ProgramError`]
+    /// if the dry run found a conflict; nothing is programmed in that case.
+    fn commit(&mut self) -> Result<(), Self::Error>;
+}
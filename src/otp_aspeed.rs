@@ -0,0 +1,240 @@
+//! ASPEED-style OTP: strap bits, configuration words and guarded programming.
+//!
+//! Shares its error types with [`crate::otp`]. Programming is only reachable
+//! through [`ProgrammingSession`], which walks the unlock → program → verify →
+//! lock sequence as a typestate, so skipping a step does not compile.
+
+use core::marker::PhantomData;
+
+pub use crate::otp::{Error, ErrorKind, ErrorType};
+
+/// A strap bit stored in OTP, typically a variant of a platform enum.
+pub trait StrapBit: Copy {
+    /// Bit position in the strap region.
+    fn bit(self) -> u8;
+
+    /// Name of the strap as used in the datasheet, e.g. `"EnableSecureBoot"`.
+    fn name(self) -> &'static str;
+}
+
+/// Strap region access.
+pub trait OtpStraps: ErrorType {
+    type Strap: StrapBit;
+
+    /// Returns the programmed value of `strap`.
+    fn read_strap(&self, strap: Self::Strap) -> Result<bool, Self::Error>;
+
+    /// Returns whether `strap` is protected against further changes.
+    fn is_strap_protected(&self, strap: Self::Strap) -> Result<bool, Self::Error>;
+
+    /// Returns how many more times `strap` can be reprogrammed.
+    fn remaining_strap_writes(&self, strap: Self::Strap) -> Result<u8, Self::Error>;
+
+    /// Overrides the value of `strap` until the next reset, without programming OTP.
+    ///
+    /// Fails with [`ErrorKind::Locked`] if the strap is protected.
+    fn override_strap(&mut self, strap: Self::Strap, value: bool) -> Result<(), Self::Error>;
+}
+
+/// Configuration word access.
+pub trait OtpConfig: ErrorType {
+    /// Number of configuration words.
+    const CONFIG_WORDS: usize;
+
+    /// Reads configuration word `index`.
+    fn read_config_word(&self, index: usize) -> Result<u32, Self::Error>;
+}
+
+/// Proof that the OTP controller is unlocked for programming.
+///
+/// Only [`ProgrammingSession`] can create one, so the programming methods of
+/// [`OtpProgrammer`] cannot be called outside a session.
+pub struct Unlocked(());
+
+/// Permission to unlock or relock the controller.
+///
+/// Like [`Unlocked`], only [`ProgrammingSession`] can create one, so the
+/// lock state cannot be changed outside a session.
+pub struct SessionKey(());
+
+/// Programming primitives, used through [`ProgrammingSession`].
+///
+/// Every method that changes the controller takes a token only a session
+/// can create, so implementors provide the primitives but callers cannot
+/// reach them directly. Implementations should not also implement
+/// [`crate::otp::OtpMemory`], whose `program_word` would bypass the session.
+pub trait OtpProgrammer: OtpStraps + OtpConfig {
+    /// Reads the data word at `word_addr`.
+    fn read_data_word(&self, word_addr: usize) -> Result<u32, Self::Error>;
+
+    /// Unlocks the controller for programming.
+    fn unlock(&mut self, key: &SessionKey) -> Result<(), Self::Error>;
+
+    /// Relocks the controller.
+    fn lock(&mut self, key: &SessionKey) -> Result<(), Self::Error>;
+
+    /// Programs the set bits of `value` into configuration word `index`.
+    fn program_config_word(
+        &mut self,
+        unlocked: &Unlocked,
+        index: usize,
+        value: u32,
+    ) -> Result<(), Self::Error>;
+
+    /// Programs `strap` to `value`, consuming one of its remaining writes.
+    fn program_strap(
+        &mut self,
+        unlocked: &Unlocked,
+        strap: Self::Strap,
+        value: bool,
+    ) -> Result<(), Self::Error>;
+
+    /// Permanently protects `strap`.
+    fn protect_strap(&mut self, unlocked: &Unlocked, strap: Self::Strap)
+        -> Result<(), Self::Error>;
+
+    /// Programs `value` into the data word at `word_addr`.
+    fn program_data_word(
+        &mut self,
+        unlocked: &Unlocked,
+        word_addr: usize,
+        value: u32,
+    ) -> Result<(), Self::Error>;
+}
+
+/// One programming operation of a [`ProgrammingSession`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Operation<S> {
+    ConfigWord { index: usize, value: u32 },
+    Strap { strap: S, value: bool },
+    ProtectStrap(S),
+    DataWord { word_addr: usize, value: u32 },
+}
+
+/// Failure of a [`ProgrammingSession`] step.
+#[derive(Debug)]
+pub enum SessionError<E> {
+    /// The OTP controller reported an error.
+    Otp(E),
+    /// Operation `index` did not read back as programmed.
+    VerifyFailed { index: usize },
+}
+
+impl<E: Error> Error for SessionError<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            SessionError::Otp(e) => e.kind(),
+            SessionError::VerifyFailed { .. } => ErrorKind::ProgramError,
+        }
+    }
+}
+
+/// Session state: unlocked, nothing programmed yet.
+pub struct Open;
+
+/// Session state: operations programmed, not yet verified.
+pub struct Programmed;
+
+/// Session state: operations programmed and read back successfully.
+pub struct Verified;
+
+/// Typestate wrapper enforcing unlock → program → verify → lock.
+///
+/// Dropping a session before [`ProgrammingSession::lock`] relocks the
+/// controller on a best-effort basis.
+pub struct ProgrammingSession<'a, P: OtpProgrammer, S> {
+    otp: Option<&'a mut P>,
+    operations: &'a [Operation<P::Strap>],
+    _state: PhantomData<S>,
+}
+
+impl<'a, P: OtpProgrammer, S> ProgrammingSession<'a, P, S> {
+    fn into_state<T>(mut self) -> ProgrammingSession<'a, P, T> {
+        ProgrammingSession {
+            otp: self.otp.take(),
+            operations: self.operations,
+            _state: PhantomData,
+        }
+    }
+
+    fn otp(&mut self) -> &mut P {
+        self.otp.as_mut().expect("session already closed")
+    }
+}
+
+impl<'a, P: OtpProgrammer> ProgrammingSession<'a, P, Open> {
+    /// Unlocks `otp` and opens a session.
+    pub fn unlock(otp: &'a mut P) -> Result<Self, SessionError<P::Error>> {
+        otp.unlock(&SessionKey(())).map_err(SessionError::Otp)?;
+        Ok(Self {
+            otp: Some(otp),
+            operations: &[],
+            _state: PhantomData,
+        })
+    }
+
+    /// Programs `operations` in order.
+    pub fn program(
+        mut self,
+        operations: &'a [Operation<P::Strap>],
+    ) -> Result<ProgrammingSession<'a, P, Programmed>, SessionError<P::Error>> {
+        let unlocked = Unlocked(());
+        let otp = self.otp();
+        for op in operations {
+            match *op {
+                Operation::ConfigWord { index, value } => {
+                    otp.program_config_word(&unlocked, index, value)
+                }
+                Operation::Strap { strap, value } => otp.program_strap(&unlocked, strap, value),
+                Operation::ProtectStrap(strap) => otp.protect_strap(&unlocked, strap),
+                Operation::DataWord { word_addr, value } => {
+                    otp.program_data_word(&unlocked, word_addr, value)
+                }
+            }
+            .map_err(SessionError::Otp)?;
+        }
+        self.operations = operations;
+        Ok(self.into_state())
+    }
+}
+
+impl<'a, P: OtpProgrammer> ProgrammingSession<'a, P, Programmed> {
+    /// Reads back every programmed operation.
+    pub fn verify(mut self) -> Result<ProgrammingSession<'a, P, Verified>, SessionError<P::Error>> {
+        let operations = self.operations;
+        let otp = self.otp();
+        for (index, op) in operations.iter().enumerate() {
+            let ok = match *op {
+                Operation::ConfigWord { index: word, value } => {
+                    otp.read_config_word(word).map(|w| w & value == value)
+                }
+                Operation::Strap { strap, value } => otp.read_strap(strap).map(|v| v == value),
+                Operation::ProtectStrap(strap) => otp.is_strap_protected(strap),
+                Operation::DataWord { word_addr, value } => {
+                    otp.read_data_word(word_addr).map(|w| w & value == value)
+                }
+            }
+            .map_err(SessionError::Otp)?;
+            if !ok {
+                return Err(SessionError::VerifyFailed { index });
+            }
+        }
+        Ok(self.into_state())
+    }
+}
+
+impl<P: OtpProgrammer> ProgrammingSession<'_, P, Verified> {
+    /// Relocks the controller and ends the session.
+    pub fn lock(mut self) -> Result<(), SessionError<P::Error>> {
+        let otp = self.otp.take().expect("session already closed");
+        otp.lock(&SessionKey(())).map_err(SessionError::Otp)
+    }
+}
+
+impl<P: OtpProgrammer, S> Drop for ProgrammingSession<'_, P, S> {
+    fn drop(&mut self) {
+        if let Some(otp) = self.otp.take() {
+            let _ = otp.lock(&SessionKey(()));
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use core::fmt::Debug;
+
+use crate::common::Operation;
+
+pub trait Error: core::fmt::Debug {
+    /// Convert error to a generic error kind
+    ///
+    /// By using this method, errors freely defined by HAL implementations
+    /// can be converted to a set of generic errors upon which generic
+    /// code can act.
+    fn kind(&self) -> ErrorKind;
+}
+
+impl Error for core::convert::Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// Error kind.
+///
+/// This represents a common set of post-quantum signature errors. Implementations are
+/// free to define more specific or additional error types. However, by providing
+/// a mapping to these common errors, generic code can still react to them.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    Busy,
+    InvalidSignature,
+    InvalidPublicKey,
+    /// The context string is longer than 255 bytes.
+    InvalidContext,
+    UnsupportedParameterSet,
+    SigningError,
+    /// A stateful key has no one-time signatures left.
+    KeyExhausted,
+    /// The updated key state could not be persisted; no signature was released.
+    StateCommitFailed,
+    Other,
+}
+
+/// Key and signature types of a post-quantum signature scheme.
+pub trait PqcTypes {
+    type PrivateKey;
+    type PublicKey;
+    type Signature;
+}
+
+/// ML-DSA parameter sets (FIPS 204).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum MlDsaParameterSet {
+    MlDsa44,
+    MlDsa65,
+    MlDsa87,
+}
+
+/// Trait for ML-DSA signing (FIPS 204).
+pub trait MlDsaSign: ErrorType + PqcTypes {
+    /// Signs a message.
+    ///
+    /// # Parameters
+    /// - `private_key`: The private key to use for signing.
+    /// - `context`: Domain-separation context string, at most 255 bytes. May be empty.
+    /// - `message`: The message to sign.
+    ///
+    /// # Returns
+    /// A result containing the generated signature, or an error.
+    fn sign(
+        &mut self,
+        private_key: &Self::PrivateKey,
+        context: &[u8],
+        message: &[u8],
+    ) -> Result<Self::Signature, Self::Error>;
+}
+
+/// Trait for ML-DSA verification (FIPS 204).
+pub trait MlDsaVerify: ErrorType + PqcTypes {
+    /// Verifies an ML-DSA signature.
+    ///
+    /// # Returns
+    /// A result indicating whether the signature is valid, or an error.
+    fn verify(
+        &mut self,
+        public_key: &Self::PublicKey,
+        context: &[u8],
+        message: &[u8],
+        signature: &Self::Signature,
+    ) -> Result<(), Self::Error>;
+}
+
+/// Trait for ML-DSA signing on an interrupt-driven engine.
+pub trait MlDsaSignStart: ErrorType + PqcTypes {
+    type SignOperation<'a>: Operation<Output = Self::Signature, Error = Self::Error>
+    where
+        Self: 'a;
+
+    /// Starts signing a message. Completes like [`MlDsaSign::sign`].
+    fn start_sign<'a>(
+        &'a mut self,
+        private_key: &'a Self::PrivateKey,
+        context: &'a [u8],
+        message: &'a [u8],
+    ) -> Result<Self::SignOperation<'a>, Self::Error>;
+}
+
+/// Trait for ML-DSA verification on an interrupt-driven engine.
+pub trait MlDsaVerifyStart: ErrorType + PqcTypes {
+    type VerifyOperation<'a>: Operation<Output = (), Error = Self::Error>
+    where
+        Self: 'a;
+
+    /// Starts verifying a signature. Completes like [`MlDsaVerify::verify`].
+    fn start_verify<'a>(
+        &'a mut self,
+        public_key: &'a Self::PublicKey,
+        context: &'a [u8],
+        message: &'a [u8],
+        signature: &'a Self::Signature,
+    ) -> Result<Self::VerifyOperation<'a>, Self::Error>;
+}
+
+/// Trait for SLH-DSA (SPHINCS+) signing (FIPS 205).
+pub trait SlhDsaSign: ErrorType + PqcTypes {
+    fn sign(
+        &mut self,
+        private_key: &Self::PrivateKey,
+        context: &[u8],
+        message: &[u8],
+    ) -> Result<Self::Signature, Self::Error>;
+}
+
+/// Trait for SLH-DSA (SPHINCS+) verification (FIPS 205).
+pub trait SlhDsaVerify: ErrorType + PqcTypes {
+    fn verify(
+        &mut self,
+        public_key: &Self::PublicKey,
+        context: &[u8],
+        message: &[u8],
+        signature: &Self::Signature,
+    ) -> Result<(), Self::Error>;
+}
+
+/// Durable storage of a stateful signing key's next one-time-signature index.
+///
+/// Reusing an index breaks the security of LMS/HSS, so the index must be
+/// persisted, e.g. to OTP or flash, before a signature is released.
+pub trait SignatureStateStore: ErrorType {
+    /// Returns the next unused one-time-signature index.
+    fn load_next_index(&mut self) -> Result<u64, Self::Error>;
+
+    /// Durably records `next_index` as the next unused index.
+    ///
+    /// Must not return until the value survives a power loss.
+    fn commit_next_index(&mut self, next_index: u64) -> Result<(), Self::Error>;
+}
+
+/// Trait for LMS/HSS signing (RFC 8554, NIST SP 800-208).
+pub trait LmsSign: ErrorType + PqcTypes {
+    /// Returns how many signatures `private_key` can still produce.
+    fn remaining_signatures(
+        &mut self,
+        private_key: &Self::PrivateKey,
+        state: &mut impl SignatureStateStore,
+    ) -> Result<u64, Self::Error>;
+
+    /// Signs a message.
+    ///
+    /// The implementation reserves the next index through `state`, commits the
+    /// incremented index and only then computes and returns the signature.
+    ///
+    /// # Returns
+    /// A result containing the signature, or an error of kind [`ErrorKind::KeyExhausted`]
+    /// or [`ErrorKind::StateCommitFailed`].
+    fn sign(
+        &mut self,
+        private_key: &Self::PrivateKey,
+        state: &mut impl SignatureStateStore,
+        message: &[u8],
+    ) -> Result<Self::Signature, Self::Error>;
+}
+
+/// Trait for LMS/HSS verification (RFC 8554).
+pub trait LmsVerify: ErrorType + PqcTypes {
+    fn verify(
+        &mut self,
+        public_key: &Self::PublicKey,
+        message: &[u8],
+        signature: &Self::Signature,
+    ) -> Result<(), Self::Error>;
+}
@@ -0,0 +1,80 @@
+pub use crate::error::{Error, ErrorKind};
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// PWM duty cycle as an unsigned Q1.15 fixed-point fraction.
+///
+/// `0x0000` is always low and `0x8000` is always high, so both ends are exact.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct DutyCycle(u16);
+
+impl DutyCycle {
+    /// Raw value of a 100% duty cycle.
+    pub const ONE_RAW: u16 = 0x8000;
+
+    /// Output always low.
+    pub const ZERO: Self = Self(0);
+
+    /// Output always high.
+    pub const FULL: Self = Self(Self::ONE_RAW);
+
+    /// Creates a duty cycle from its Q1.15 representation.
+    ///
+    /// Returns `None` if `raw` exceeds [`DutyCycle::ONE_RAW`].
+    pub const fn from_raw(raw: u16) -> Option<Self> {
+        if raw <= Self::ONE_RAW {
+            Some(Self(raw))
+        } else {
+            None
+        }
+    }
+
+    /// Creates a duty cycle from a percentage, rounding down.
+    ///
+    /// Returns `None` if `percent` exceeds 100.
+    pub const fn from_percent(percent: u8) -> Option<Self> {
+        if percent <= 100 {
+            Some(Self((percent as u32 * Self::ONE_RAW as u32 / 100) as u16))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the Q1.15 representation.
+    pub const fn raw(self) -> u16 {
+        self.0
+    }
+
+    /// Scales `max` by the duty cycle, rounding down, e.g. to compute a compare value
+    /// from a counter period.
+    pub const fn scale(self, max: u32) -> u32 {
+        ((max as u64 * self.0 as u64) >> 15) as u32
+    }
+}
+
+/// A single PWM output channel.
+pub trait PwmChannel: ErrorType {
+    /// Starts driving the output.
+    fn enable(&mut self) -> Result<(), Self::Error>;
+
+    /// Stops driving the output and leaves it at its inactive level.
+    fn disable(&mut self) -> Result<(), Self::Error>;
+
+    /// Sets the duty cycle, taking effect at the next period boundary.
+    fn set_duty_cycle(&mut self, duty: DutyCycle) -> Result<(), Self::Error>;
+
+    /// Returns the duty cycle currently applied, after hardware rounding.
+    fn duty_cycle(&self) -> DutyCycle;
+
+    /// Sets the period in nanoseconds.
+    ///
+    /// The duty cycle fraction is kept. Fails with [`ErrorKind::InvalidParameter`]
+    /// if the period cannot be generated from the channel clock.
+    fn set_period_ns(&mut self, period_ns: u32) -> Result<(), Self::Error>;
+
+    /// Returns the period currently applied in nanoseconds, after hardware rounding.
+    fn period_ns(&self) -> u32;
+}
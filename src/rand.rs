@@ -0,0 +1,110 @@
+use core::fmt::Debug;
+
+/// Error kind.
+///
+/// This represents a common set of random number generation errors. Implementations are
+/// free to define more specific or additional error types. However, by providing
+/// a mapping to these common errors, generic code can still react to them.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// A continuous or start-up health test of the entropy source failed.
+    HealthTestFailure,
+
+    /// The entropy source could not deliver enough entropy in time.
+    EntropyUnavailable,
+
+    /// The DRBG must be reseeded before more output can be generated.
+    ReseedRequired,
+
+    /// The DRBG has not been instantiated.
+    NotInstantiated,
+
+    /// The request exceeds the maximum number of bytes per generate call.
+    RequestTooLarge,
+
+    /// The requested security strength is not supported.
+    UnsupportedStrength,
+
+    /// General hardware failure.
+    HardwareFailure,
+}
+
+pub trait Error: core::fmt::Debug {
+    /// Convert error to a generic error kind
+    ///
+    /// By using this method, errors freely defined by HAL implementations
+    /// can be converted to a set of generic errors upon which generic
+    /// code can act.
+    fn kind(&self) -> ErrorKind;
+}
+
+impl Error for core::convert::Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// State of the entropy source health tests (NIST SP 800-90B).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum HealthStatus {
+    /// Start-up tests have not completed yet.
+    Starting,
+    /// All tests pass.
+    Healthy,
+    /// A test failed; the source delivers no output until it recovers or is reset.
+    Failed,
+}
+
+/// Hardware entropy source (TRNG).
+pub trait EntropySource: ErrorType {
+    /// Fills `dest` with conditioned entropy.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or failure. Fails with [`ErrorKind::HealthTestFailure`]
+    /// rather than returning unhealthy output.
+    fn fill_entropy(&mut self, dest: &mut [u8]) -> Result<(), Self::Error>;
+
+    /// Returns the current health-test status.
+    fn health_status(&mut self) -> Result<HealthStatus, Self::Error>;
+}
+
+/// Deterministic random bit generator (NIST SP 800-90A).
+pub trait Drbg: ErrorType {
+    /// Instantiates the DRBG from its entropy source.
+    ///
+    /// # Parameters
+    ///
+    /// - `security_strength`: Requested security strength in bits.
+    /// - `personalization`: Optional personalization string. May be empty.
+    fn instantiate(&mut self, security_strength: u32, personalization: &[u8]) -> Result<(), Self::Error>;
+
+    /// Reseeds the DRBG with fresh entropy.
+    ///
+    /// # Parameters
+    ///
+    /// - `additional_input`: Optional additional input. May be empty.
+    fn reseed(&mut self, additional_input: &[u8]) -> Result<(), Self::Error>;
+
+    /// Fills `dest` with random bytes.
+    ///
+    /// # Parameters
+    ///
+    /// - `additional_input`: Optional additional input. May be empty.
+    /// - `dest`: Output buffer.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or failure. Fails with [`ErrorKind::ReseedRequired`]
+    /// once the reseed interval is exhausted, unless the implementation reseeds automatically.
+    fn generate(&mut self, additional_input: &[u8], dest: &mut [u8]) -> Result<(), Self::Error>;
+
+    /// Destroys the internal state. The DRBG must be instantiated again before use.
+    fn uninstantiate(&mut self) -> Result<(), Self::Error>;
+}
@@ -0,0 +1,268 @@
+//! Emulated register-mapped devices on a target bus.
+//!
+//! Many I2C and I3C devices, such as sensors and EEPROMs, expose a bank of
+//! byte registers: a write sets the register pointer with its first byte and
+//! stores any further bytes from there, and a read returns registers from
+//! the pointer on. Firmware emulating such a device implements
+//! [`RegisterMap`]; [`I2cRegisterTarget`] and [`I3cRegisterTarget`] handle
+//! the bus side.
+//!
+//! Staging a read only peeks at the registers. Read side effects happen
+//! once the transfer completes, for the bytes the controller actually
+//! clocked out, and the pointer advances by the same count.
+
+use crate::i2c_target::{self, I2cTarget};
+use crate::i3c_target::{TransactionEvent, Transactions};
+
+/// Who may access a register.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Access {
+    ReadWrite,
+    /// Writes are ignored.
+    ReadOnly,
+    /// Reads return 0.
+    WriteOnly,
+}
+
+/// Attributes of one register.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Attributes {
+    pub access: Access,
+    /// Reading has side effects, such as clear-on-read status bits.
+    ///
+    /// Staging for a burst read stops before a volatile register unless the
+    /// read starts at it, so a burst never sends a stale value for it.
+    pub volatile: bool,
+}
+
+impl Attributes {
+    pub const READ_WRITE: Self = Self {
+        access: Access::ReadWrite,
+        volatile: false,
+    };
+
+    pub const READ_ONLY: Self = Self {
+        access: Access::ReadOnly,
+        volatile: false,
+    };
+
+    pub const fn is_readable(&self) -> bool {
+        !matches!(self.access, Access::WriteOnly)
+    }
+
+    pub const fn is_writable(&self) -> bool {
+        !matches!(self.access, Access::ReadOnly)
+    }
+}
+
+/// A bank of byte registers with read and write handlers.
+pub trait RegisterMap {
+    /// Number of registers; valid indices are `0..REGISTER_COUNT`.
+    const REGISTER_COUNT: usize;
+
+    /// Returns the attributes of register `index`.
+    fn attributes(&self, index: u8) -> Attributes;
+
+    /// Returns the value of readable register `index` without side effects.
+    fn peek(&self, index: u8) -> u8;
+
+    /// Called once for each readable register the controller actually read,
+    /// in order. Clear-on-read and similar side effects belong here.
+    fn on_read(&mut self, _index: u8) {}
+
+    /// Stores `value` in writable register `index`.
+    fn write(&mut self, index: u8, value: u8);
+}
+
+/// What the register pointer does after each byte.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum AutoIncrement {
+    /// Stay on the same register.
+    Off,
+    /// Move to the next register, wrapping to 0 after the last.
+    Wrap,
+    /// Move to the next register, stopping at the last.
+    Saturate,
+}
+
+/// Register pointer of an emulated device.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct RegisterPointer {
+    index: u8,
+    increment: AutoIncrement,
+}
+
+impl RegisterPointer {
+    pub const fn new(increment: AutoIncrement) -> Self {
+        Self {
+            index: 0,
+            increment,
+        }
+    }
+
+    pub const fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// Returns the index following `index` for a map with `count` registers.
+    fn next(&self, index: u8, count: usize) -> u8 {
+        let last = count.saturating_sub(1);
+        match self.increment {
+            AutoIncrement::Off => index,
+            AutoIncrement::Wrap if usize::from(index) >= last => 0,
+            AutoIncrement::Saturate if usize::from(index) >= last => index,
+            _ => index.wrapping_add(1),
+        }
+    }
+
+    /// Applies a write transfer: the first byte selects the register, the
+    /// rest are stored from there. Writes to read-only or out-of-range
+    /// registers are ignored.
+    pub fn apply_write<M: RegisterMap + ?Sized>(&mut self, map: &mut M, data: &[u8]) {
+        let Some((&index, values)) = data.split_first() else {
+            return;
+        };
+        self.index = index;
+        for &value in values {
+            if usize::from(self.index) < M::REGISTER_COUNT && map.attributes(self.index).is_writable() {
+                map.write(self.index, value);
+            }
+            self.index = self.next(self.index, M::REGISTER_COUNT);
+        }
+    }
+
+    /// Fills `buf` with register values from the pointer on, for the next read.
+    ///
+    /// Only [`RegisterMap::peek`] is called, so staging data the controller
+    /// never reads has no side effects.
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes staged.
+    pub fn stage_read<M: RegisterMap + ?Sized>(&self, map: &M, buf: &mut [u8]) -> usize {
+        let mut index = self.index;
+        for (staged, byte) in buf.iter_mut().enumerate() {
+            if usize::from(index) >= M::REGISTER_COUNT {
+                return staged;
+            }
+            let attributes = map.attributes(index);
+            if attributes.volatile && staged > 0 {
+                return staged;
+            }
+            *byte = if attributes.is_readable() { map.peek(index) } else { 0 };
+            index = self.next(index, M::REGISTER_COUNT);
+        }
+        buf.len()
+    }
+
+    /// Completes a read in which the controller clocked out `sent` staged
+    /// bytes: reports each register to [`RegisterMap::on_read`] and advances
+    /// the pointer past them.
+    pub fn complete_read<M: RegisterMap + ?Sized>(&mut self, map: &mut M, sent: usize) {
+        for _ in 0..sent {
+            if usize::from(self.index) >= M::REGISTER_COUNT {
+                return;
+            }
+            if map.attributes(self.index).is_readable() {
+                map.on_read(self.index);
+            }
+            self.index = self.next(self.index, M::REGISTER_COUNT);
+        }
+    }
+}
+
+/// Serves a [`RegisterMap`] as an I2C target.
+///
+/// Implemented for every [`I2cTarget`].
+pub trait I2cRegisterTarget: I2cTarget {
+    /// Stages the registers at the pointer for the next read.
+    ///
+    /// [`Self::serve_i2c_registers`] calls this on each read request, while
+    /// the hardware stretches the clock. Targets that cannot stretch the
+    /// clock call it after each transfer instead, so data is ready in time.
+    fn stage_i2c_registers<M: RegisterMap + ?Sized>(
+        &mut self,
+        map: &M,
+        pointer: &RegisterPointer,
+        buf: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        let len = pointer.stage_read(map, buf);
+        self.set_response(&buf[..len]).map(|_| ())
+    }
+
+    /// Handles every pending bus event against `map`.
+    ///
+    /// # Parameters
+    ///
+    /// - `map`: The emulated registers.
+    /// - `pointer`: The register pointer, kept across calls.
+    /// - `buf`: Scratch buffer for received writes and staged reads. Its length
+    ///   bounds both the write burst and the read burst length.
+    fn serve_i2c_registers<M: RegisterMap + ?Sized>(
+        &mut self,
+        map: &mut M,
+        pointer: &mut RegisterPointer,
+        buf: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        while let Some(event) = self.poll_event()? {
+            match event {
+                i2c_target::Event::WriteComplete { .. } => {
+                    let len = self.read_received(buf)?;
+                    pointer.apply_write(map, &buf[..len]);
+                }
+                i2c_target::Event::ReadRequest => self.stage_i2c_registers(map, pointer, buf)?,
+                i2c_target::Event::ReadComplete { sent } => pointer.complete_read(map, sent),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: I2cTarget + ?Sized> I2cRegisterTarget for T {}
+
+/// Serves a [`RegisterMap`] as an I3C target over private transfers.
+///
+/// I3C targets cannot stretch the clock, so the next read is staged after
+/// every completed transfer rather than on the read request.
+///
+/// Implemented for every [`Transactions`] target.
+pub trait I3cRegisterTarget: Transactions {
+    /// Stages the registers at the pointer for the next private read.
+    ///
+    /// Call once after enabling the target, before any controller read.
+    fn stage_i3c_registers<M: RegisterMap + ?Sized>(
+        &mut self,
+        map: &M,
+        pointer: &RegisterPointer,
+        buf: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        let len = pointer.stage_read(map, buf);
+        self.set_response(&buf[..len]).map(|_| ())
+    }
+
+    /// Handles every pending private transfer against `map`.
+    ///
+    /// See [`I2cRegisterTarget::serve_i2c_registers`] for the parameters.
+    fn serve_i3c_registers<M: RegisterMap + ?Sized>(
+        &mut self,
+        map: &mut M,
+        pointer: &mut RegisterPointer,
+        buf: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        while let Some(event) = self.poll_transaction()? {
+            match event {
+                TransactionEvent::WriteComplete { .. } => {
+                    let len = self.read_received(buf)?;
+                    pointer.apply_write(map, &buf[..len]);
+                }
+                TransactionEvent::ReadComplete { sent, .. } => pointer.complete_read(map, sent),
+                _ => continue,
+            }
+            self.stage_i3c_registers(map, pointer, buf)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Transactions + ?Sized> I3cRegisterTarget for T {}
@@ -1,5 +1,9 @@
 use core::num::NonZeroU32;
 
+use crate::common::{AlgorithmPolicy, Operation, Permits, Zeroize};
+use crate::digest::Digest;
+use crate::key_vault::{GeneratedKeyPair, KeyDestination};
+
 pub enum PaddingMode {
     Pkcs1v15,
     Pss,
@@ -12,10 +16,15 @@ pub enum RsaSize {
     Other(NonZeroU32),
 }
 
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
 pub enum ErrorKind {
     InvalidLength,
     SignError,
     VerifyError,
+    EncryptError,
+    DecryptError,
+    KeyGenError,
 }
 
 pub trait Error: core::fmt::Debug {
@@ -36,7 +45,7 @@ pub trait ErrorType {
 }
 
 pub trait RsaKeys {
-    type PrivateKey;
+    type PrivateKey: Zeroize;
     type PublicKey;
 }
 
@@ -45,8 +54,18 @@ pub trait RsaSignature {
 }    
 
 
+/// RSA key generation.
+///
+/// The modulus size is a type, so the implementation's policy decides at
+/// compile time which sizes can be generated.
 pub trait RsaKeyGen: ErrorType + RsaKeys {
-    fn generate_keys(bits: RsaSize) -> Result<(Self::PrivateKey, Self::PublicKey), Self::Error>;
+    /// Policy restricting the modulus sizes, e.g. [`crate::common::Unrestricted`].
+    type Policy: AlgorithmPolicy;
+
+    /// Generates keys with modulus `M`, which [`RsaKeyGen::Policy`] must permit.
+    fn generate_keys<M: RsaModulus>() -> Result<(Self::PrivateKey, Self::PublicKey), Self::Error>
+    where
+        Self::Policy: Permits<M>;
 }
 
 pub trait RsaSign: ErrorType + RsaKeys + RsaSignature {
@@ -69,3 +88,261 @@ pub trait RsaVerify: ErrorType + RsaKeys + RsaSignature {
         signature: &Self::Signature,
     ) -> Result<Self::Signature, Self::Error>;
 }
+
+/// RSA signing on an interrupt-driven engine.
+pub trait RsaSignStart: ErrorType + RsaKeys + RsaSignature {
+    type SignOperation<'a>: Operation<Output = Self::Signature, Error = Self::Error>
+    where
+        Self: 'a;
+
+    /// Starts a signature. Completes like [`RsaSign::sign`].
+    fn start_sign<'a>(
+        &'a mut self,
+        private_key: &'a Self::PrivateKey,
+        message_digest: &'a [u8],
+        padding_mode: PaddingMode,
+    ) -> Result<Self::SignOperation<'a>, Self::Error>;
+}
+
+/// RSA verification on an interrupt-driven engine.
+pub trait RsaVerifyStart: ErrorType + RsaKeys + RsaSignature {
+    type VerifyOperation<'a>: Operation<Output = (), Error = Self::Error>
+    where
+        Self: 'a;
+
+    /// Starts a verification.
+    ///
+    /// The operation completes with `()` if `signature` is valid for
+    /// `message_digest`, and fails with an error of kind
+    /// [`ErrorKind::VerifyError`] if it is not.
+    fn start_verify<'a>(
+        &'a mut self,
+        public_key: &'a Self::PublicKey,
+        message_digest: &'a [u8],
+        padding_mode: PaddingMode,
+        signature: &'a Self::Signature,
+    ) -> Result<Self::VerifyOperation<'a>, Self::Error>;
+}
+
+/// Salt length used by RSASSA-PSS.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum SaltLength {
+    /// Salt as long as the message digest.
+    Digest,
+    /// Longest salt that fits the modulus.
+    Max,
+    /// Salt of exactly the given number of bytes.
+    Explicit(usize),
+}
+
+/// RSASSA-PSS signing with explicit salt-length control.
+pub trait RsaPssSign: ErrorType + RsaKeys + RsaSignature {
+    fn sign_pss(
+        &self,
+        private_key: &Self::PrivateKey,
+        message_digest: impl AsRef<[u8]>,
+        salt_len: SaltLength,
+    ) -> Result<Self::Signature, Self::Error>;
+}
+
+/// RSASSA-PSS signing with a caller-chosen salt instead of a random one.
+///
+/// Only meant for running known-answer tests such as CAVP vectors.
+#[cfg(feature = "test-hooks")]
+pub trait RsaPssSignWithSalt: RsaPssSign {
+    fn sign_pss_with_salt(
+        &self,
+        private_key: &Self::PrivateKey,
+        message_digest: impl AsRef<[u8]>,
+        salt: &[u8],
+    ) -> Result<Self::Signature, Self::Error>;
+}
+
+/// RSASSA-PSS verification with explicit salt-length control.
+pub trait RsaPssVerify: ErrorType + RsaKeys + RsaSignature {
+    fn verify_pss(
+        &self,
+        public_key: &Self::PublicKey,
+        message_digest: impl AsRef<[u8]>,
+        salt_len: SaltLength,
+        signature: &Self::Signature,
+    ) -> Result<(), Self::Error>;
+}
+
+/// RSAES-OAEP encryption.
+///
+/// `digest` hashes the label and drives MGF1, so any [`Digest`]
+/// implementation can be plugged in.
+pub trait RsaOaepEncrypt: ErrorType + RsaKeys {
+    /// Encrypts `message` into `ciphertext`.
+    ///
+    /// # Returns
+    /// A result containing the number of bytes written to `ciphertext`, or an error.
+    fn encrypt_oaep<D: Digest>(
+        &self,
+        public_key: &Self::PublicKey,
+        digest: &mut D,
+        label: &[u8],
+        message: &[u8],
+        ciphertext: &mut [u8],
+    ) -> Result<usize, Self::Error>;
+}
+
+/// RSAES-OAEP decryption.
+pub trait RsaOaepDecrypt: ErrorType + RsaKeys {
+    /// Decrypts `ciphertext` into `plaintext`.
+    ///
+    /// # Returns
+    /// A result containing the number of bytes written to `plaintext`, or an error.
+    fn decrypt_oaep<D: Digest>(
+        &self,
+        private_key: &Self::PrivateKey,
+        digest: &mut D,
+        label: &[u8],
+        ciphertext: &[u8],
+        plaintext: &mut [u8],
+    ) -> Result<usize, Self::Error>;
+}
+
+/// Key pair returned by [`RsaGenerateKeyPair::generate_key_pair`].
+pub type GeneratedKeyPairOf<G> = GeneratedKeyPair<
+    <G as RsaKeys>::PrivateKey,
+    <G as RsaGenerateKeyPair>::KeyHandle,
+    <G as RsaKeys>::PublicKey,
+>;
+
+/// RSA key generation into raw material or a hardware key slot.
+///
+/// Extends [`RsaKeyGen`] and shares its policy, so the permitted modulus
+/// sizes are declared once.
+pub trait RsaGenerateKeyPair: RsaKeyGen {
+    /// Handle to a private key kept in hardware.
+    type KeyHandle;
+
+    /// Generates an RSA key pair with modulus `M`, which [`RsaKeyGen::Policy`] must permit.
+    ///
+    /// # Parameters
+    ///
+    /// - `destination`: Whether to return the private key or keep it in hardware.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the key pair.
+    fn generate_key_pair<M: RsaModulus>(
+        &mut self,
+        destination: KeyDestination<'_>,
+    ) -> Result<GeneratedKeyPairOf<Self>, Self::Error>
+    where
+        Self::Policy: Permits<M>;
+}
+
+/// Export of RSA public keys as (n, e).
+pub trait RsaPublicKeyExport: ErrorType + RsaKeys {
+    /// Writes the modulus and public exponent as big-endian integers without leading zeros.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the lengths written to `modulus` and `exponent`, or an error
+    /// of kind `ErrorKind::InvalidLength` if either buffer is too small.
+    fn export_public_key(
+        &self,
+        public_key: &Self::PublicKey,
+        modulus: &mut [u8],
+        exponent: &mut [u8],
+    ) -> Result<(usize, usize), Self::Error>;
+}
+
+/// Type-level RSA modulus size, so key sizes can be checked by an
+/// [`crate::common::AlgorithmPolicy`] at compile time.
+pub trait RsaModulus {
+    const BITS: u32;
+    const SIZE: RsaSize;
+}
+
+macro_rules! rsa_moduli {
+    ($($(#[$doc:meta])* $name:ident => $bits:literal, $size:ident;)*) => {
+        $(
+            $(#[$doc])*
+            #[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+            pub struct $name;
+
+            impl RsaModulus for $name {
+                const BITS: u32 = $bits;
+                const SIZE: RsaSize = RsaSize::$size;
+            }
+        )*
+    };
+}
+
+rsa_moduli! {
+    /// 2048-bit modulus.
+    Rsa2048 => 2048, Size2048;
+    /// 3072-bit modulus.
+    Rsa3072 => 3072, Size3072;
+    /// 4096-bit modulus.
+    Rsa4096 => 4096, Size4096;
+}
+
+/// Limits an RSA public key must meet.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct RsaKeyPolicy {
+    pub min_modulus_bits: u32,
+    pub max_modulus_bits: u32,
+    /// Smallest accepted public exponent. NIST SP 800-89 requires at least 65537.
+    pub min_exponent: u64,
+    /// The modulus is screened for prime factors below this bound; 0 disables the screen.
+    pub small_factor_bound: u16,
+}
+
+impl RsaKeyPolicy {
+    /// 2048- to 4096-bit moduli, exponent at least 65537, factors below 1000 screened.
+    pub const DEFAULT: Self = Self {
+        min_modulus_bits: 2048,
+        max_modulus_bits: 4096,
+        min_exponent: 65537,
+        small_factor_bound: 1000,
+    };
+}
+
+impl Default for RsaKeyPolicy {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Reason an RSA public key was rejected.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum RsaKeyDefect {
+    /// The modulus length is outside the policy limits.
+    ModulusSize { bits: u32 },
+    /// The modulus is even.
+    EvenModulus,
+    /// The modulus is divisible by a small prime.
+    SmallFactor { prime: u16 },
+    /// The exponent is below the policy minimum or not below the modulus.
+    ExponentOutOfRange,
+    /// The exponent is even.
+    EvenExponent,
+}
+
+/// Failure of [`RsaPublicKeyValidate::validate_public_key`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum KeyValidationError<E> {
+    /// The key violates the policy.
+    Rejected(RsaKeyDefect),
+    /// The check itself could not be run.
+    Other(E),
+}
+
+/// RSA public key sanity checks for externally supplied keys.
+pub trait RsaPublicKeyValidate: ErrorType + RsaKeys {
+    /// Checks `public_key` against `policy`.
+    ///
+    /// Returns the first defect found.
+    fn validate_public_key(
+        &self,
+        public_key: &Self::PublicKey,
+        policy: &RsaKeyPolicy,
+    ) -> Result<(), KeyValidationError<Self::Error>>;
+}
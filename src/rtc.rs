@@ -0,0 +1,245 @@
+//! Real-time clocks.
+//!
+//! Calendar time is exchanged as a [`DateTime`] in UTC; conversions to and
+//! from [`UnixTime`] are provided for code that only compares instants, such
+//! as certificate validity checks.
+
+pub use crate::error::{Error, ErrorKind};
+pub use crate::time::UnixTime;
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+const SECONDS_PER_DAY: u64 = 86_400;
+// Days from 0000-03-01 to 1970-01-01 in the proleptic Gregorian calendar.
+const EPOCH_DAYS: u64 = 719_468;
+
+/// Calendar date and time in UTC, from 1970 onwards.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct DateTime {
+    year: u16,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+}
+
+const fn is_leap_year(year: u16) -> bool {
+    (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
+const fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl DateTime {
+    /// Returns `None` unless every field is in range and `year` is at least 1970.
+    pub const fn new(year: u16, month: u8, day: u8, hour: u8, minute: u8, second: u8) -> Option<Self> {
+        if year < 1970
+            || month < 1
+            || month > 12
+            || day < 1
+            || day > days_in_month(year, month)
+            || hour > 23
+            || minute > 59
+            || second > 59
+        {
+            return None;
+        }
+        Some(Self {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+        })
+    }
+
+    pub const fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// Month, 1 to 12.
+    pub const fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// Day of the month, starting at 1.
+    pub const fn day(&self) -> u8 {
+        self.day
+    }
+
+    pub const fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    pub const fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    pub const fn second(&self) -> u8 {
+        self.second
+    }
+
+    pub const fn to_unix_time(&self) -> UnixTime {
+        // Count years from March so the leap day is the last day of the year.
+        let year = self.year as u64 - (self.month <= 2) as u64;
+        let month = self.month as u64;
+        let era = year / 400;
+        let year_of_era = year - era * 400;
+        let shifted_month = if month > 2 { month - 3 } else { month + 9 };
+        let day_of_year = (153 * shifted_month + 2) / 5 + self.day as u64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        let days = era * 146_097 + day_of_era - EPOCH_DAYS;
+        let seconds = self.hour as u64 * 3600 + self.minute as u64 * 60 + self.second as u64;
+        UnixTime(days * SECONDS_PER_DAY + seconds)
+    }
+
+    /// Returns `None` if the year does not fit a `u16`.
+    pub const fn from_unix_time(time: UnixTime) -> Option<Self> {
+        let days = time.0 / SECONDS_PER_DAY + EPOCH_DAYS;
+        let seconds = time.0 % SECONDS_PER_DAY;
+        let era = days / 146_097;
+        let day_of_era = days - era * 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = year_of_era + era * 400 + (month <= 2) as u64;
+        if year > u16::MAX as u64 {
+            return None;
+        }
+        Some(Self {
+            year: year as u16,
+            month: month as u8,
+            day: day as u8,
+            hour: (seconds / 3600) as u8,
+            minute: (seconds / 60 % 60) as u8,
+            second: (seconds % 60) as u8,
+        })
+    }
+}
+
+/// Whether the time kept by the RTC can be trusted.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum RtcStatus {
+    /// The time was set and the clock has run continuously since.
+    Valid,
+    /// The clock was never set.
+    NotSet,
+    /// The backup supply failed; the time restarted from an arbitrary value.
+    PowerLost,
+}
+
+/// Battery-backed calendar clock.
+pub trait Rtc: ErrorType {
+    /// Returns whether the current time can be trusted.
+    fn status(&mut self) -> Result<RtcStatus, Self::Error>;
+
+    /// Returns the current time.
+    ///
+    /// The value is meaningless unless [`Rtc::status`] reports [`RtcStatus::Valid`].
+    fn datetime(&mut self) -> Result<DateTime, Self::Error>;
+
+    /// Sets the current time, making [`Rtc::status`] report [`RtcStatus::Valid`].
+    fn set_datetime(&mut self, datetime: DateTime) -> Result<(), Self::Error>;
+
+    /// Returns the current time if it can be trusted.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the time, or `None` if the clock is not valid.
+    fn unix_time(&mut self) -> Result<Option<UnixTime>, Self::Error> {
+        if self.status()? != RtcStatus::Valid {
+            return Ok(None);
+        }
+        Ok(Some(self.datetime()?.to_unix_time()))
+    }
+}
+
+/// Alarm that fires at a calendar time, e.g. to wake the system.
+pub trait RtcAlarm: Rtc {
+    /// Arms the alarm for `datetime`, replacing any previous alarm.
+    fn set_alarm(&mut self, datetime: DateTime) -> Result<(), Self::Error>;
+
+    /// Disarms the alarm.
+    fn clear_alarm(&mut self) -> Result<(), Self::Error>;
+
+    /// Returns whether the alarm fired, clearing the flag.
+    fn take_alarm(&mut self) -> Result<bool, Self::Error>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Year, month, day, hour, minute and second.
+    type Fields = (u16, u8, u8, u8, u8, u8);
+
+    const KNOWN: [(Fields, u64); 5] = [
+        ((1970, 1, 1, 0, 0, 0), 0),
+        ((2000, 3, 1, 0, 0, 0), 951_868_800),
+        ((2024, 2, 29, 12, 34, 56), 1_709_210_096),
+        ((2038, 1, 19, 3, 14, 8), 2_147_483_648),
+        ((2100, 12, 31, 23, 59, 59), 4_133_980_799),
+    ];
+
+    fn datetime((year, month, day, hour, minute, second): Fields) -> DateTime {
+        DateTime::new(year, month, day, hour, minute, second).unwrap()
+    }
+
+    #[test]
+    fn to_unix_time_matches_known_values() {
+        for (fields, seconds) in KNOWN {
+            assert_eq!(datetime(fields).to_unix_time(), UnixTime(seconds), "{fields:?}");
+        }
+    }
+
+    #[test]
+    fn from_unix_time_matches_known_values() {
+        for (fields, seconds) in KNOWN {
+            assert_eq!(DateTime::from_unix_time(UnixTime(seconds)), Some(datetime(fields)), "{seconds}");
+        }
+    }
+
+    #[test]
+    fn round_trips_every_day_boundary() {
+        for day in (0..200_000u64).step_by(7) {
+            let time = UnixTime(day * SECONDS_PER_DAY + 86_399);
+            assert_eq!(DateTime::from_unix_time(time).unwrap().to_unix_time(), time);
+        }
+    }
+
+    #[test]
+    fn new_rejects_out_of_range_fields() {
+        assert!(DateTime::new(1969, 12, 31, 23, 59, 59).is_none());
+        assert!(DateTime::new(2023, 2, 29, 0, 0, 0).is_none());
+        assert!(DateTime::new(2100, 2, 29, 0, 0, 0).is_none());
+        assert!(DateTime::new(2000, 2, 29, 0, 0, 0).is_some());
+        assert!(DateTime::new(2024, 4, 31, 0, 0, 0).is_none());
+        assert!(DateTime::new(2024, 13, 1, 0, 0, 0).is_none());
+        assert!(DateTime::new(2024, 1, 1, 24, 0, 0).is_none());
+        assert!(DateTime::new(2024, 1, 1, 0, 60, 0).is_none());
+        assert!(DateTime::new(2024, 1, 1, 0, 0, 60).is_none());
+    }
+
+    #[test]
+    fn from_unix_time_rejects_years_past_u16() {
+        assert!(DateTime::from_unix_time(UnixTime(u64::MAX)).is_none());
+    }
+}
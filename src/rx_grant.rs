@@ -0,0 +1,17 @@
+//! Zero-copy receive buffers lent out by a HAL.
+
+use core::ops::Deref;
+
+/// Received data left in HAL-owned memory, such as the DMA buffer the
+/// controller wrote into.
+///
+/// The HAL cannot reuse the underlying buffer while the grant is alive. It is
+/// handed back when the grant is released or dropped.
+pub trait RxGrant: Deref<Target = [u8]> {
+    /// Returns the buffer to the HAL. Equivalent to dropping the grant.
+    fn release(self)
+    where
+        Self: Sized,
+    {
+    }
+}
@@ -0,0 +1,332 @@
+//! Command channel to an external secure element or HSM.
+//!
+//! Models the ISO/IEC 7816-4 APDU exchange used by smart-card-class parts:
+//! the host sends a command and receives response data followed by a
+//! two-byte status word. Adapter crates implement the crypto traits on top
+//! of [`SecureElement`] for a specific part, e.g. a TPM or an ATECC-class
+//! authenticator behind a vendor command set.
+
+/// Error kind.
+///
+/// This represents a common set of secure element errors. Implementations are
+/// free to define more specific or additional error types. However, by providing
+/// a mapping to these common errors, generic code can still react to them.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The bus or link to the element failed.
+    Transport,
+
+    /// The element did not answer in time.
+    Timeout,
+
+    /// The command or response does not fit the buffers or the element's limits.
+    BufferTooSmall,
+
+    /// The element answered with a status word other than success.
+    Status(StatusWord),
+
+    /// A different error occurred.
+    Other,
+}
+
+pub trait Error: core::fmt::Debug {
+    /// Convert error to a generic error kind
+    ///
+    /// By using this method, errors freely defined by HAL implementations
+    /// can be converted to a set of generic errors upon which generic
+    /// code can act.
+    fn kind(&self) -> ErrorKind;
+}
+
+impl Error for core::convert::Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// Status word `SW1 SW2` ending every response.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct StatusWord(pub u16);
+
+impl StatusWord {
+    pub const SUCCESS: Self = Self(0x9000);
+    pub const WRONG_LENGTH: Self = Self(0x6700);
+    pub const SECURITY_STATUS_NOT_SATISFIED: Self = Self(0x6982);
+    pub const AUTHENTICATION_BLOCKED: Self = Self(0x6983);
+    pub const CONDITIONS_NOT_SATISFIED: Self = Self(0x6985);
+    pub const WRONG_DATA: Self = Self(0x6A80);
+    /// Also returned by SELECT when no applet has the AID.
+    pub const NOT_FOUND: Self = Self(0x6A82);
+    pub const WRONG_PARAMETERS: Self = Self(0x6B00);
+    pub const INS_NOT_SUPPORTED: Self = Self(0x6D00);
+    pub const CLA_NOT_SUPPORTED: Self = Self(0x6E00);
+
+    pub const fn is_success(self) -> bool {
+        self.0 == Self::SUCCESS.0
+    }
+
+    /// For `61XX`, the number of further response bytes to fetch with GET RESPONSE.
+    pub const fn bytes_remaining(self) -> Option<u8> {
+        if self.0 >> 8 == 0x61 {
+            Some(self.0 as u8)
+        } else {
+            None
+        }
+    }
+
+    /// For `6CXX`, the response length the command must be repeated with.
+    pub const fn correct_length(self) -> Option<u8> {
+        if self.0 >> 8 == 0x6C {
+            Some(self.0 as u8)
+        } else {
+            None
+        }
+    }
+}
+
+/// A command APDU.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Command<'a> {
+    pub cla: u8,
+    pub ins: u8,
+    pub p1: u8,
+    pub p2: u8,
+    /// Command data, sent as `Lc || data` when not empty.
+    pub data: &'a [u8],
+    /// Expected response length `Le`; `Some(0)` means "as much as available".
+    pub expected_len: Option<u16>,
+}
+
+impl<'a> Command<'a> {
+    /// SELECT by application identifier (`00 A4 04 00`).
+    pub const fn select(aid: &'a [u8]) -> Self {
+        Self {
+            cla: 0x00,
+            ins: 0xA4,
+            p1: 0x04,
+            p2: 0x00,
+            data: aid,
+            expected_len: Some(0),
+        }
+    }
+
+    /// Encodes the command into `buf`, using extended length fields when
+    /// the data exceeds 255 bytes or `Le` exceeds 256.
+    ///
+    /// # Returns
+    ///
+    /// The encoded length, or `None` if `buf` is too short.
+    pub fn encode(&self, buf: &mut [u8]) -> Option<usize> {
+        let extended = self.data.len() > 255 || self.expected_len.is_some_and(|le| le > 256);
+        let data_len = u16::try_from(self.data.len()).ok()?;
+        let lc_len = match (self.data.is_empty(), extended) {
+            (true, _) => 0,
+            (false, false) => 1,
+            (false, true) => 3,
+        };
+        let le_len = match (self.expected_len, extended) {
+            (None, _) => 0,
+            (Some(_), false) => 1,
+            (Some(_), true) if self.data.is_empty() => 3,
+            (Some(_), true) => 2,
+        };
+        let total = 4 + lc_len + self.data.len() + le_len;
+        let buf = buf.get_mut(..total)?;
+        buf[..4].copy_from_slice(&[self.cla, self.ins, self.p1, self.p2]);
+        let mut pos = 4;
+        match lc_len {
+            1 => buf[pos] = data_len as u8,
+            3 => {
+                buf[pos] = 0;
+                buf[pos + 1..pos + 3].copy_from_slice(&data_len.to_be_bytes());
+            }
+            _ => {}
+        }
+        pos += lc_len;
+        buf[pos..pos + self.data.len()].copy_from_slice(self.data);
+        pos += self.data.len();
+        if let Some(le) = self.expected_len {
+            // A zero `Le` encodes the maximum, 256 or 65536 bytes.
+            match le_len {
+                1 => buf[pos] = le as u8,
+                2 => buf[pos..pos + 2].copy_from_slice(&le.to_be_bytes()),
+                _ => {
+                    buf[pos] = 0;
+                    buf[pos + 1..pos + 3].copy_from_slice(&le.to_be_bytes());
+                }
+            }
+        }
+        Some(total)
+    }
+}
+
+/// Response to a command: data written to the caller's buffer and the status word.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Response {
+    pub len: usize,
+    pub status: StatusWord,
+}
+
+/// Failure of [`SecureElement::command`].
+#[derive(Debug)]
+pub enum CommandError<E> {
+    /// The element rejected the command.
+    Status(StatusWord),
+    /// The exchange failed.
+    Transport(E),
+}
+
+impl<E: Error> Error for CommandError<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            CommandError::Status(status) => ErrorKind::Status(*status),
+            CommandError::Transport(e) => e.kind(),
+        }
+    }
+}
+
+/// APDU command/response channel.
+pub trait SecureElement: ErrorType {
+    /// Resets the element and writes its answer-to-reset into `atr`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the length of the answer-to-reset.
+    fn reset(&mut self, atr: &mut [u8]) -> Result<usize, Self::Error>;
+
+    /// Sends `command` and writes the response data, without the status word, into `response`.
+    ///
+    /// Transport-level chaining (`61XX` / GET RESPONSE) is handled by the implementation.
+    fn transmit(&mut self, command: &Command<'_>, response: &mut [u8]) -> Result<Response, Self::Error>;
+
+    /// Sends `command` and treats any status word other than `9000` as an error.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of response bytes.
+    fn command(&mut self, command: &Command<'_>, response: &mut [u8]) -> Result<usize, CommandError<Self::Error>> {
+        let result = self.transmit(command, response).map_err(CommandError::Transport)?;
+        if result.status.is_success() {
+            Ok(result.len)
+        } else {
+            Err(CommandError::Status(result.status))
+        }
+    }
+
+    /// Selects the applet with `aid`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the length of the select response (FCI) written to `response`.
+    fn select(&mut self, aid: &[u8], response: &mut [u8]) -> Result<usize, CommandError<Self::Error>> {
+        self.command(&Command::select(aid), response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode<'b>(command: &Command<'_>, buf: &'b mut [u8]) -> &'b [u8] {
+        let len = command.encode(buf).unwrap();
+        &buf[..len]
+    }
+
+    fn command(data: &[u8], expected_len: Option<u16>) -> Command<'_> {
+        Command {
+            cla: 0x80,
+            ins: 0xCA,
+            p1: 0x01,
+            p2: 0x02,
+            data,
+            expected_len,
+        }
+    }
+
+    #[test]
+    fn short_cases() {
+        let mut buf = [0u8; 16];
+        // Case 1: header only.
+        assert_eq!(encode(&command(&[], None), &mut buf), &[0x80, 0xCA, 0x01, 0x02]);
+        // Case 2: Le only; 256 is encoded as zero.
+        assert_eq!(encode(&command(&[], Some(0x10)), &mut buf), &[0x80, 0xCA, 0x01, 0x02, 0x10]);
+        assert_eq!(encode(&command(&[], Some(256)), &mut buf), &[0x80, 0xCA, 0x01, 0x02, 0x00]);
+        // Case 3: data only.
+        assert_eq!(
+            encode(&command(&[0xAA, 0xBB], None), &mut buf),
+            &[0x80, 0xCA, 0x01, 0x02, 0x02, 0xAA, 0xBB]
+        );
+        // Case 4: data and Le.
+        assert_eq!(
+            encode(&command(&[0xAA], Some(0)), &mut buf),
+            &[0x80, 0xCA, 0x01, 0x02, 0x01, 0xAA, 0x00]
+        );
+    }
+
+    #[test]
+    fn select_by_aid() {
+        let mut buf = [0u8; 16];
+        let aid = [0xA0, 0x00, 0x00, 0x03, 0x08];
+        assert_eq!(
+            encode(&Command::select(&aid), &mut buf),
+            &[0x00, 0xA4, 0x04, 0x00, 0x05, 0xA0, 0x00, 0x00, 0x03, 0x08, 0x00]
+        );
+    }
+
+    #[test]
+    fn extended_le_only() {
+        let mut buf = [0u8; 16];
+        assert_eq!(
+            encode(&command(&[], Some(0x0101)), &mut buf),
+            &[0x80, 0xCA, 0x01, 0x02, 0x00, 0x01, 0x01]
+        );
+    }
+
+    #[test]
+    fn extended_data() {
+        let data = [0x5Au8; 300];
+        let mut buf = [0u8; 320];
+
+        let encoded = encode(&command(&data, None), &mut buf);
+        assert_eq!(encoded.len(), 4 + 3 + 300);
+        assert_eq!(&encoded[4..7], &[0x00, 0x01, 0x2C]);
+        assert_eq!(&encoded[7..], &data[..]);
+
+        // With data present, the extended Le has no leading zero byte.
+        let encoded = encode(&command(&data, Some(0)), &mut buf);
+        assert_eq!(encoded.len(), 4 + 3 + 300 + 2);
+        assert_eq!(&encoded[307..], &[0x00, 0x00]);
+    }
+
+    #[test]
+    fn large_le_forces_extended_lc() {
+        let mut buf = [0u8; 16];
+        assert_eq!(
+            encode(&command(&[0xAA], Some(0x0200)), &mut buf),
+            &[0x80, 0xCA, 0x01, 0x02, 0x00, 0x00, 0x01, 0xAA, 0x02, 0x00]
+        );
+    }
+
+    #[test]
+    fn short_buffer() {
+        let mut buf = [0u8; 6];
+        assert_eq!(command(&[0xAA], Some(0)).encode(&mut buf), None);
+        assert_eq!(command(&[0xAA], None).encode(&mut buf), Some(6));
+    }
+
+    #[test]
+    fn status_words() {
+        assert!(StatusWord::SUCCESS.is_success());
+        assert!(!StatusWord::NOT_FOUND.is_success());
+        assert_eq!(StatusWord(0x6110).bytes_remaining(), Some(0x10));
+        assert_eq!(StatusWord(0x6C20).correct_length(), Some(0x20));
+        assert_eq!(StatusWord::SUCCESS.bytes_remaining(), None);
+        assert_eq!(StatusWord::WRONG_LENGTH.correct_length(), None);
+    }
+}
@@ -0,0 +1,145 @@
+use crate::dma::{CircularTransfer, ReadBuffer, StartError, Transfer, WriteBuffer};
+
+pub use crate::error::{Error, ErrorKind};
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Parity {
+    None,
+    Even,
+    Odd,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum StopBits {
+    One,
+    OnePointFive,
+    Two,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum DataBits {
+    Seven,
+    Eight,
+    Nine,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum FlowControl {
+    None,
+    /// Hardware RTS/CTS handshaking.
+    RtsCts,
+}
+
+/// Line configuration.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Config {
+    pub baud_rate: u32,
+    pub data_bits: DataBits,
+    pub parity: Parity,
+    pub stop_bits: StopBits,
+    pub flow_control: FlowControl,
+}
+
+impl Default for Config {
+    /// 115200 baud, 8N1, no flow control.
+    fn default() -> Self {
+        Self {
+            baud_rate: 115_200,
+            data_bits: DataBits::Eight,
+            parity: Parity::None,
+            stop_bits: StopBits::One,
+            flow_control: FlowControl::None,
+        }
+    }
+}
+
+/// Snapshot of the line status register.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct LineStatus {
+    /// Received data is waiting to be read.
+    pub data_ready: bool,
+    /// The transmitter has sent every queued byte.
+    pub tx_empty: bool,
+    /// A break condition was seen since the last query.
+    pub break_detected: bool,
+    pub overrun: bool,
+    pub parity_error: bool,
+    pub framing_error: bool,
+}
+
+/// Receive half of a serial port.
+pub trait SerialRead: ErrorType {
+    /// Reads at least one byte into `buf`, blocking until data is available.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes read.
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error>;
+}
+
+/// Transmit half of a serial port.
+pub trait SerialWrite: ErrorType {
+    /// Writes all of `data`, blocking while the transmit FIFO is full.
+    fn write(&mut self, data: &[u8]) -> Result<(), Self::Error>;
+
+    /// Blocks until every queued byte has left the transmitter.
+    fn flush(&mut self) -> Result<(), Self::Error>;
+
+    /// Holds the line in the break state for at least `bit_times` bit periods.
+    fn send_break(&mut self, bit_times: u32) -> Result<(), Self::Error>;
+}
+
+/// Line configuration and status of a serial port.
+pub trait SerialPort: ErrorType {
+    /// Applies `config`. Fails with `ErrorKind::Unsupported` if a setting can't be honoured.
+    fn configure(&mut self, config: &Config) -> Result<(), Self::Error>;
+
+    /// Enables or disables hardware flow control without touching the other settings.
+    fn set_flow_control(&mut self, flow_control: FlowControl) -> Result<(), Self::Error>;
+
+    /// Returns the line status and clears its sticky error and break flags.
+    fn line_status(&mut self) -> Result<LineStatus, Self::Error>;
+}
+
+/// A serial port that can be split into independently owned halves.
+pub trait Split: SerialPort {
+    type Rx: SerialRead<Error = Self::Error>;
+    type Tx: SerialWrite<Error = Self::Error>;
+
+    fn split(self) -> (Self::Rx, Self::Tx);
+}
+
+/// DMA transfers on a serial port.
+pub trait SerialDma: ErrorType {
+    type TxTransfer<'a, B>: Transfer<Buffer = B, Error = Self::Error>
+    where
+        Self: 'a,
+        B: ReadBuffer + 'a;
+
+    type RxTransfer<'a, B>: Transfer<Buffer = B, Error = Self::Error>
+    where
+        Self: 'a,
+        B: WriteBuffer + 'a;
+
+    type RxCircular<'a, B>: CircularTransfer<Buffer = B, Error = Self::Error>
+    where
+        Self: 'a,
+        B: WriteBuffer + 'a;
+
+    /// Starts transmitting `buf`.
+    fn write_dma<B: ReadBuffer>(&mut self, buf: B) -> Result<Self::TxTransfer<'_, B>, StartError<Self::Error, B>>;
+
+    /// Starts receiving until `buf` is full.
+    fn read_dma<B: WriteBuffer>(&mut self, buf: B) -> Result<Self::RxTransfer<'_, B>, StartError<Self::Error, B>>;
+
+    /// Starts receiving continuously into the two halves of `buf`.
+    fn read_circular_dma<B: WriteBuffer>(
+        &mut self,
+        buf: B,
+    ) -> Result<Self::RxCircular<'_, B>, StartError<Self::Error, B>>;
+}
@@ -0,0 +1,89 @@
+//! Sharing one bus controller between several drivers.
+//!
+//! Each driver holds a [`SharedDevice`] referring to the same [`BusMutex`].
+//! Every operation on the device locks the bus for its duration, so
+//! transactions from different drivers never interleave.
+
+use core::cell::{RefCell, RefMut};
+use core::ops::DerefMut;
+
+use crate::i3c_master::{ErrorType, I3cMaster};
+
+/// Exclusive access to a shared bus.
+///
+/// Bare-metal code can use the provided `RefCell` implementation; RTOS
+/// adapters implement it over their own mutex primitives.
+pub trait BusMutex {
+    type Bus;
+
+    /// Guard granting access to the bus; the lock is released when it is dropped.
+    type Guard<'a>: DerefMut<Target = Self::Bus>
+    where
+        Self: 'a;
+
+    /// Acquires the bus, waiting for the current owner to release it.
+    fn lock(&self) -> Self::Guard<'_>;
+
+    /// Acquires the bus only if it is free. Never blocks, so it is usable from interrupt context.
+    fn try_lock(&self) -> Option<Self::Guard<'_>>;
+}
+
+/// Single-context sharing. `lock` panics if the bus is already borrowed,
+/// as that can only happen through re-entrance.
+impl<T> BusMutex for RefCell<T> {
+    type Bus = T;
+    type Guard<'a>
+        = RefMut<'a, T>
+    where
+        T: 'a;
+
+    fn lock(&self) -> Self::Guard<'_> {
+        self.borrow_mut()
+    }
+
+    fn try_lock(&self) -> Option<Self::Guard<'_>> {
+        self.try_borrow_mut().ok()
+    }
+}
+
+/// One driver's handle on a shared bus.
+pub struct SharedDevice<'a, M> {
+    bus: &'a M,
+}
+
+impl<'a, M: BusMutex> SharedDevice<'a, M> {
+    pub fn new(bus: &'a M) -> Self {
+        Self { bus }
+    }
+
+    /// Locks the bus for a sequence of operations that must not be interleaved.
+    pub fn lock(&self) -> M::Guard<'a> {
+        self.bus.lock()
+    }
+}
+
+impl<M> ErrorType for SharedDevice<'_, M>
+where
+    M: BusMutex,
+    M::Bus: ErrorType,
+{
+    type Error = <M::Bus as ErrorType>::Error;
+}
+
+impl<M> I3cMaster for SharedDevice<'_, M>
+where
+    M: BusMutex,
+    M::Bus: I3cMaster,
+{
+    fn write(&mut self, addr: u8, data: &[u8]) -> Result<(), Self::Error> {
+        self.bus.lock().write(addr, data)
+    }
+
+    fn read(&mut self, addr: u8, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        self.bus.lock().read(addr, buf)
+    }
+
+    fn write_read(&mut self, addr: u8, data: &[u8], buf: &mut [u8]) -> Result<usize, Self::Error> {
+        self.bus.lock().write_read(addr, data, buf)
+    }
+}
@@ -0,0 +1,90 @@
+//! SMBus target semantics on top of [`I2cTarget`].
+//!
+//! Protocol crates such as PMBus or MCTP-over-SMBus decide from the command
+//! code whether a write is a plain or a block write, so the target reports
+//! raw commands and leaves their interpretation to the caller.
+
+use crate::i2c_target::I2cTarget;
+
+/// SMBus Host address, the destination of Host Notify.
+pub const HOST_ADDRESS: u8 = 0x08;
+
+/// SMBus Device Default Address used by ARP.
+pub const ARP_ADDRESS: u8 = 0x61;
+
+/// Computes the SMBus Packet Error Code (CRC-8, polynomial x^8 + x^2 + x + 1).
+///
+/// `crc` is the running value, 0 for a new packet. The address byte,
+/// including the R/W bit, is part of the covered data.
+pub fn pec(mut crc: u8, data: &[u8]) -> u8 {
+    for &byte in data {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+/// An SMBus command received by the target.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum Command {
+    /// Quick Command; the R/W bit is the only data.
+    Quick { read: bool },
+    /// Receive Byte: a read without a preceding command code.
+    ReceiveByte,
+    /// A command code followed by `len` data bytes, retrievable with
+    /// [`I2cTarget::read_received`]. `pec_valid` is `None` when PEC is disabled.
+    Write {
+        command: u8,
+        len: usize,
+        pec_valid: Option<bool>,
+    },
+    /// A command code followed by a repeated START and a read. The response
+    /// must be staged with [`SmbusTarget::respond`] or [`SmbusTarget::respond_block`].
+    Read { command: u8 },
+}
+
+/// SMBus target.
+pub trait SmbusTarget: I2cTarget {
+    /// Enables PEC generation on reads and PEC checking on writes.
+    fn set_pec_enabled(&mut self, enabled: bool) -> Result<(), Self::Error>;
+
+    /// Returns the next received command, if any, without blocking.
+    fn poll_command(&mut self) -> Result<Option<Command>, Self::Error>;
+
+    /// Stages a response, appending PEC when enabled.
+    fn respond(&mut self, data: &[u8]) -> Result<(), Self::Error>;
+
+    /// Stages a Block Read response, prefixing the byte count and appending PEC when enabled.
+    fn respond_block(&mut self, data: &[u8]) -> Result<(), Self::Error>;
+
+    /// Sends Host Notify to [`HOST_ADDRESS`] carrying this device's address and `status`.
+    ///
+    /// This briefly takes the controller role on the bus.
+    fn host_notify(&mut self, status: u16) -> Result<(), Self::Error>;
+}
+
+/// Unique Device Identifier used by SMBus ARP.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Udid(pub [u8; 16]);
+
+/// SMBus Address Resolution Protocol participation.
+///
+/// The implementation answers ARP commands on [`ARP_ADDRESS`] and moves the
+/// target to whichever address the ARP master assigns.
+pub trait SmbusArp: SmbusTarget {
+    /// Returns the UDID reported to the ARP master.
+    fn udid(&self) -> Udid;
+
+    /// Returns the address assigned by ARP, if any.
+    fn assigned_address(&self) -> Option<u8>;
+
+    /// Forgets the assigned address, as on an ARP Reset Device command.
+    fn reset_address(&mut self) -> Result<(), Self::Error>;
+}
@@ -0,0 +1,214 @@
+use core::fmt::Debug;
+
+use crate::dma::{ReadBuffer, StartError, Transfer, WriteBuffer};
+use crate::time::{Deadline, Monotonic};
+
+pub use crate::error::{Error, ErrorKind};
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// Clock polarity.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Polarity {
+    /// Clock signal low when idle.
+    IdleLow,
+    /// Clock signal high when idle.
+    IdleHigh,
+}
+
+/// Clock phase.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Phase {
+    /// Data is sampled on the first clock transition.
+    CaptureOnFirstTransition,
+    /// Data is sampled on the second clock transition.
+    CaptureOnSecondTransition,
+}
+
+/// SPI mode, as the combination of clock polarity and phase.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Mode {
+    pub polarity: Polarity,
+    pub phase: Phase,
+}
+
+pub const MODE_0: Mode = Mode {
+    polarity: Polarity::IdleLow,
+    phase: Phase::CaptureOnFirstTransition,
+};
+
+pub const MODE_1: Mode = Mode {
+    polarity: Polarity::IdleLow,
+    phase: Phase::CaptureOnSecondTransition,
+};
+
+pub const MODE_2: Mode = Mode {
+    polarity: Polarity::IdleHigh,
+    phase: Phase::CaptureOnFirstTransition,
+};
+
+pub const MODE_3: Mode = Mode {
+    polarity: Polarity::IdleHigh,
+    phase: Phase::CaptureOnSecondTransition,
+};
+
+/// Order in which the bits of a word are shifted out.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum BitOrder {
+    MsbFirst,
+    LsbFirst,
+}
+
+/// Bus configuration applied to a chip select.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Config {
+    pub mode: Mode,
+    pub bit_order: BitOrder,
+    /// Requested SCLK frequency in Hz. Implementations pick the closest
+    /// frequency that does not exceed this value.
+    pub frequency_hz: u32,
+}
+
+/// A single step of a SPI transaction.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Operation<'a> {
+    /// Read data into the buffer, clocking out an implementation-defined fill word.
+    Read(&'a mut [u8]),
+    /// Write data from the buffer, discarding what is read.
+    Write(&'a [u8]),
+    /// Full-duplex transfer: write the second buffer while reading into the first.
+    Transfer(&'a mut [u8], &'a [u8]),
+    /// Full-duplex transfer in place: the buffer is written out and overwritten with what is read.
+    TransferInPlace(&'a mut [u8]),
+    /// Delay for the given number of nanoseconds with chip select held asserted.
+    DelayNs(u32),
+}
+
+/// Fixed limits of a SPI controller.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Capabilities {
+    /// Highest SCK frequency, in Hz.
+    pub max_clock_hz: u32,
+    /// Longest single operation, in bytes.
+    pub max_transfer_len: usize,
+    /// Number of chip-select lines.
+    pub chip_selects: u8,
+}
+
+/// Capability discovery at compile time.
+pub trait SpiMasterCapabilities: SpiMaster {
+    const CAPABILITIES: Capabilities;
+}
+
+/// SPI controller.
+///
+/// Unlike a bare bus, the controller owns chip-select management: every
+/// [`SpiMaster::transaction`] asserts the selected line for its whole
+/// duration and releases it afterwards.
+///
+/// Drivers should also implement [`crate::system_control::power::PowerManaged`].
+pub trait SpiMaster: ErrorType {
+    /// Identifies a chip-select line driven by the controller.
+    type ChipSelect: Copy;
+
+    /// Applies `config` to all subsequent transactions on `cs`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or failure. Fails with [`ErrorKind::InvalidParameter`]
+    /// when the mode, bit order or frequency cannot be honoured.
+    fn configure(&mut self, cs: Self::ChipSelect, config: &Config) -> Result<(), Self::Error>;
+
+    /// Performs `operations` in order with `cs` asserted throughout.
+    ///
+    /// # Parameters
+    ///
+    /// - `cs`: The chip select to assert.
+    /// - `operations`: The operations to perform.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or failure. Chip select is released even when an operation fails.
+    fn transaction(
+        &mut self,
+        cs: Self::ChipSelect,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error>;
+
+    /// Full-duplex transfer in a transaction of its own.
+    fn transfer(
+        &mut self,
+        cs: Self::ChipSelect,
+        read: &mut [u8],
+        write: &[u8],
+    ) -> Result<(), Self::Error> {
+        self.transaction(cs, &mut [Operation::Transfer(read, write)])
+    }
+
+    /// Write in a transaction of its own.
+    fn write(&mut self, cs: Self::ChipSelect, data: &[u8]) -> Result<(), Self::Error> {
+        self.transaction(cs, &mut [Operation::Write(data)])
+    }
+
+    /// Read in a transaction of its own.
+    fn read(&mut self, cs: Self::ChipSelect, data: &mut [u8]) -> Result<(), Self::Error> {
+        self.transaction(cs, &mut [Operation::Read(data)])
+    }
+}
+
+/// Transactions bounded by a [`Deadline`].
+pub trait SpiMasterDeadline: SpiMaster {
+    /// Like [`SpiMaster::transaction`], but aborts and fails with
+    /// [`ErrorKind::Timeout`] once `deadline` passes. Chip select is released
+    /// either way.
+    fn transaction_with_deadline(
+        &mut self,
+        cs: Self::ChipSelect,
+        operations: &mut [Operation<'_>],
+        clock: &impl Monotonic,
+        deadline: Deadline,
+    ) -> Result<(), Self::Error>;
+}
+
+/// Buffers of a full-duplex DMA transfer.
+#[derive(Debug)]
+pub struct DuplexBuffers<R, W> {
+    /// Receives the data clocked in.
+    pub read: R,
+    /// Holds the data clocked out.
+    pub write: W,
+}
+
+/// Error of [`SpiMasterDma::transfer_dma`], handing both buffers back.
+pub type DuplexStartError<S, R, W> = StartError<<S as ErrorType>::Error, DuplexBuffers<R, W>>;
+
+/// DMA transfers on a SPI controller. Chip select is asserted for the whole transfer.
+pub trait SpiMasterDma: SpiMaster {
+    type WriteTransfer<'a, W>: Transfer<Buffer = W, Error = Self::Error>
+    where
+        Self: 'a,
+        W: ReadBuffer + 'a;
+
+    type TransferDma<'a, R, W>: Transfer<Buffer = DuplexBuffers<R, W>, Error = Self::Error>
+    where
+        Self: 'a,
+        R: WriteBuffer + 'a,
+        W: ReadBuffer + 'a;
+
+    /// Starts writing `write`, discarding what is read.
+    fn write_dma<W: ReadBuffer>(
+        &mut self,
+        cs: Self::ChipSelect,
+        write: W,
+    ) -> Result<Self::WriteTransfer<'_, W>, StartError<Self::Error, W>>;
+
+    /// Starts a full-duplex transfer, writing `buffers.write` while reading into `buffers.read`.
+    fn transfer_dma<R: WriteBuffer, W: ReadBuffer>(
+        &mut self,
+        cs: Self::ChipSelect,
+        buffers: DuplexBuffers<R, W>,
+    ) -> Result<Self::TransferDma<'_, R, W>, DuplexStartError<Self, R, W>>;
+}
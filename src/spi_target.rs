@@ -0,0 +1,59 @@
+use core::fmt::Debug;
+
+pub use crate::spi_master::{BitOrder, Mode, Phase, Polarity};
+
+pub use crate::error::{Error, ErrorKind};
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// Bus-level events observed by a SPI target.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Event {
+    /// The controller asserted chip select.
+    Selected,
+    /// The controller released chip select, ending the transaction.
+    Deselected {
+        /// Number of bytes clocked during the transaction.
+        len: usize,
+    },
+}
+
+/// SPI target (peripheral) role.
+///
+/// The controller decides when a transaction starts and how long it lasts,
+/// so the target has to stage its response before chip select is asserted.
+///
+/// Drivers should also implement [`crate::system_control::power::PowerManaged`].
+pub trait SpiTarget: ErrorType {
+    /// Sets the mode and bit order the target expects from the controller.
+    fn configure(&mut self, mode: Mode, bit_order: BitOrder) -> Result<(), Self::Error>;
+
+    /// Stages data to be clocked out during the next transaction.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes accepted. Bytes clocked beyond the staged
+    /// data are reported as [`ErrorKind::Underrun`] or filled with an implementation-defined word.
+    fn set_tx_data(&mut self, data: &[u8]) -> Result<usize, Self::Error>;
+
+    /// Blocks until the controller completes a transaction.
+    ///
+    /// # Parameters
+    ///
+    /// - `rx`: Buffer receiving the data written by the controller.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes clocked during the transaction, which may
+    /// exceed `rx.len()` when the controller transferred more than the buffer could hold.
+    fn transfer(&mut self, rx: &mut [u8]) -> Result<usize, Self::Error>;
+
+    /// Returns whether chip select is currently asserted.
+    fn is_selected(&mut self) -> Result<bool, Self::Error>;
+
+    /// Returns the next pending bus event, if any, without blocking.
+    fn poll_event(&mut self) -> Result<Option<Event>, Self::Error>;
+}
@@ -0,0 +1,52 @@
+//! Cipher suites for session protocols such as SPDM.
+//!
+//! A [`CipherSuite`] names one consistent selection of hash, key derivation,
+//! signature, key exchange and AEAD implementations, so protocol code can be
+//! generic over a single parameter. The associated type bounds tie the
+//! halves together: the verifier accepts the signer's signatures, and both
+//! use the same curve.
+
+use crate::aead::{AeadDecrypt, AeadEncrypt};
+use crate::digest::Digest;
+use crate::ecdh::EcdhEphemeral;
+use crate::ecdsa::{EcdsaCurve, EcdsaSign, EcdsaVerify, HashMarker};
+use crate::kdf::Hkdf;
+
+/// A consistent set of algorithms for a secure session.
+pub trait CipherSuite {
+    /// Output size of [`CipherSuite::Hash`] in bytes.
+    const HASH_SIZE: usize;
+
+    /// Hash for transcripts and signed digests.
+    type Hash: Digest;
+
+    /// Marker of [`CipherSuite::Hash`] passed to the signature traits.
+    type HashAlgorithm: HashMarker;
+
+    /// Key schedule.
+    type Kdf: Hkdf;
+
+    /// Curve shared by the signature algorithm.
+    type Curve: EcdsaCurve;
+
+    type Signer: EcdsaSign<Curve = Self::Curve>;
+
+    type Verifier: EcdsaVerify<
+        Curve = Self::Curve,
+        Signature = <Self::Signer as EcdsaSign>::Signature,
+    >;
+
+    /// Ephemeral key exchange.
+    type KeyExchange: EcdhEphemeral;
+
+    /// Record protection.
+    type Aead: AeadEncrypt + AeadDecrypt;
+
+    fn hash(&mut self) -> &mut Self::Hash;
+
+    fn kdf(&mut self) -> &mut Self::Kdf;
+
+    fn key_exchange(&mut self) -> &mut Self::KeyExchange;
+
+    fn aead(&mut self) -> &mut Self::Aead;
+}
@@ -0,0 +1,387 @@
+//! Symmetric cipher modes.
+//!
+//! Each mode has its own trait so that mode-specific state, such as the XTS
+//! data unit or the CTR counter block, is part of the signature rather than
+//! left to conventions on top of a raw block cipher.
+
+use core::fmt::Debug;
+
+use crate::common::{Operation, Zeroize};
+
+/// Error kind.
+///
+/// This represents a common set of cipher operation errors. Implementations are
+/// free to define more specific or additional error types. However, by providing
+/// a mapping to these common errors, generic code can still react to them.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The supplied key is not valid for the algorithm.
+    InvalidKey,
+
+    /// The input or output buffer length is not valid for the operation.
+    InvalidLength,
+
+    /// The CTR counter would wrap around and reuse keystream.
+    CounterOverflow,
+
+    /// The specified algorithm is not supported by the hardware or software implementation.
+    UnsupportedAlgorithm,
+
+    /// The hardware accelerator is busy and cannot process the operation.
+    Busy,
+
+    /// General hardware failure during the operation.
+    HardwareFailure,
+}
+
+pub trait Error: core::fmt::Debug {
+    /// Convert error to a generic error kind
+    ///
+    /// By using this method, errors freely defined by Algo implementations
+    /// can be converted to a set of generic errors upon which generic
+    /// code can act.
+    fn kind(&self) -> ErrorKind;
+}
+
+impl Error for core::convert::Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// Key types of a cipher.
+pub trait CipherTypes {
+    /// Raw key material. For XTS this holds both the data key and the tweak key.
+    type Key: Zeroize;
+
+    /// Handle to a key held in a key vault or in hardware, e.g.
+    /// [`crate::key_vault::KeyVault::KeyHandle`]. Implementations without
+    /// hardware keys use [`core::convert::Infallible`].
+    type KeyHandle: Copy;
+}
+
+/// A key passed to a cipher operation: raw material or a handle.
+///
+/// Mirrors [`crate::key_vault::GeneratedKey`], so a key generated into a
+/// vault can be used without exporting it.
+#[derive(Debug)]
+pub enum CipherKey<'a, K, H> {
+    Raw(&'a K),
+    Handle(H),
+}
+
+impl<K, H: Copy> Clone for CipherKey<'_, K, H> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, H: Copy> Copy for CipherKey<'_, K, H> {}
+
+/// [`CipherKey`] for the cipher `C`.
+pub type KeyOf<'a, C> = CipherKey<'a, <C as CipherTypes>::Key, <C as CipherTypes>::KeyHandle>;
+
+/// Raw block cipher, e.g. AES, for building modes and MACs such as CMAC.
+pub trait BlockCipher: ErrorType + CipherTypes {
+    /// Block size in bytes.
+    const BLOCK_SIZE: usize;
+
+    /// Encrypts one block in place.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The key to encrypt with.
+    /// - `block`: The block. Its length must equal [`BlockCipher::BLOCK_SIZE`].
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or failure.
+    fn encrypt_block(&mut self, key: KeyOf<'_, Self>, block: &mut [u8]) -> Result<(), Self::Error>;
+
+    /// Decrypts one block in place.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The key to decrypt with.
+    /// - `block`: The block. Its length must equal [`BlockCipher::BLOCK_SIZE`].
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or failure.
+    fn decrypt_block(&mut self, key: KeyOf<'_, Self>, block: &mut [u8]) -> Result<(), Self::Error>;
+}
+
+/// XTS mode (IEEE 1619), e.g. for inline flash encryption.
+pub trait XtsMode: ErrorType + CipherTypes {
+    /// Size of one data unit in bytes, e.g. a flash sector.
+    const DATA_UNIT_SIZE: usize;
+
+    /// Encrypts one data unit.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The key pair to encrypt with.
+    /// - `data_unit`: Sequence number of the data unit, used as the tweak.
+    /// - `plaintext`: The data to encrypt. Its length must equal [`XtsMode::DATA_UNIT_SIZE`].
+    /// - `ciphertext`: Output buffer. Its length must equal the length of `plaintext`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or failure.
+    fn encrypt_data_unit(
+        &mut self,
+        key: KeyOf<'_, Self>,
+        data_unit: u128,
+        plaintext: &[u8],
+        ciphertext: &mut [u8],
+    ) -> Result<(), Self::Error>;
+
+    /// Decrypts one data unit.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The key pair to decrypt with.
+    /// - `data_unit`: Sequence number of the data unit, used as the tweak.
+    /// - `ciphertext`: The data to decrypt. Its length must equal [`XtsMode::DATA_UNIT_SIZE`].
+    /// - `plaintext`: Output buffer. Its length must equal the length of `ciphertext`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or failure.
+    fn decrypt_data_unit(
+        &mut self,
+        key: KeyOf<'_, Self>,
+        data_unit: u128,
+        ciphertext: &[u8],
+        plaintext: &mut [u8],
+    ) -> Result<(), Self::Error>;
+
+    /// Encrypts one data unit in place.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The key pair to encrypt with.
+    /// - `data_unit`: Sequence number of the data unit, used as the tweak.
+    /// - `data`: Plaintext, replaced by the ciphertext. Its length must equal [`XtsMode::DATA_UNIT_SIZE`].
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or failure.
+    fn encrypt_data_unit_in_place(
+        &mut self,
+        key: KeyOf<'_, Self>,
+        data_unit: u128,
+        data: &mut [u8],
+    ) -> Result<(), Self::Error>;
+
+    /// Decrypts one data unit in place.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The key pair to decrypt with.
+    /// - `data_unit`: Sequence number of the data unit, used as the tweak.
+    /// - `data`: Ciphertext, replaced by the plaintext. Its length must equal [`XtsMode::DATA_UNIT_SIZE`].
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or failure.
+    fn decrypt_data_unit_in_place(
+        &mut self,
+        key: KeyOf<'_, Self>,
+        data_unit: u128,
+        data: &mut [u8],
+    ) -> Result<(), Self::Error>;
+}
+
+/// Error returned when a [`CtrCounter`] would wrap around.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct CounterOverflow;
+
+impl Error for CounterOverflow {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::CounterOverflow
+    }
+}
+
+/// CTR counter block.
+///
+/// The counter occupies the low `counter_bits` of the big-endian 128-bit block;
+/// the remaining high bits hold the nonce and never change. Advancing past the
+/// last counter value fails instead of wrapping, so keystream is never reused.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct CtrCounter {
+    block: [u8; 16],
+    mask: u128,
+    exhausted: bool,
+}
+
+impl CtrCounter {
+    /// Creates a counter starting at `initial`.
+    ///
+    /// Returns `None` if `counter_bits` is not in `1..=128`.
+    pub fn new(initial: [u8; 16], counter_bits: u32) -> Option<Self> {
+        if !(1..=128).contains(&counter_bits) {
+            return None;
+        }
+        Some(Self {
+            block: initial,
+            mask: u128::MAX >> (128 - counter_bits),
+            exhausted: false,
+        })
+    }
+
+    /// Returns the counter block of the next keystream block.
+    pub fn block(&self) -> &[u8; 16] {
+        &self.block
+    }
+
+    /// Returns whether every counter value has been used.
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    /// Consumes `blocks` counter values.
+    ///
+    /// Fails without changing the counter if fewer than `blocks` values are left.
+    pub fn advance(&mut self, blocks: u128) -> Result<(), CounterOverflow> {
+        if blocks == 0 {
+            return Ok(());
+        }
+        if self.exhausted {
+            return Err(CounterOverflow);
+        }
+        let value = u128::from_be_bytes(self.block);
+        let counter = value & self.mask;
+        let left_after_current = self.mask - counter;
+        if blocks - 1 > left_after_current {
+            return Err(CounterOverflow);
+        }
+        self.exhausted = blocks - 1 == left_after_current;
+        let counter = counter.wrapping_add(blocks) & self.mask;
+        self.block = ((value & !self.mask) | counter).to_be_bytes();
+        Ok(())
+    }
+}
+
+/// CTR mode.
+pub trait CtrMode: ErrorType + CipherTypes {
+    /// XORs keystream starting at `counter` into `input` and writes the result to `output`.
+    ///
+    /// The counter is advanced by one value per started block, so a following
+    /// call resumes on a block boundary. Implementations call
+    /// [`CtrCounter::advance`] before producing keystream and fail with
+    /// [`ErrorKind::CounterOverflow`] if the counter is exhausted.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The key to use.
+    /// - `counter`: The counter block, advanced on success.
+    /// - `input`: Plaintext or ciphertext.
+    /// - `output`: Output buffer. Its length must equal the length of `input`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or failure.
+    fn apply_keystream(
+        &mut self,
+        key: KeyOf<'_, Self>,
+        counter: &mut CtrCounter,
+        input: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), Self::Error>;
+
+    /// XORs keystream starting at `counter` into `data` in place.
+    ///
+    /// Advances `counter` like [`CtrMode::apply_keystream`].
+    fn apply_keystream_in_place(
+        &mut self,
+        key: KeyOf<'_, Self>,
+        counter: &mut CtrCounter,
+        data: &mut [u8],
+    ) -> Result<(), Self::Error>;
+}
+
+/// Direction of a [`CipherStream`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Direction {
+    Encrypt,
+    Decrypt,
+}
+
+/// Multi-part encryption or decryption with bounded memory.
+///
+/// Unlike [`CtrMode`], whose calls resume on block boundaries, chunks may
+/// have any length: the implementation buffers an incomplete block, so
+/// output may lag input by up to one block and the remainder is returned by
+/// `finish`. Suits decrypting a firmware image in small pieces on devices
+/// that cannot hold it.
+pub trait CipherStream: ErrorType + CipherTypes {
+    /// Initialization vector or initial counter block.
+    type Iv;
+
+    /// Block size in bytes, the most output can lag input.
+    const BLOCK_SIZE: usize;
+
+    /// Starts a stream, replacing any stream in progress.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The key to use. It is loaded during the call and need not outlive it.
+    /// - `iv`: The initialization vector.
+    /// - `direction`: Whether to encrypt or decrypt.
+    fn begin(&mut self, key: KeyOf<'_, Self>, iv: &Self::Iv, direction: Direction) -> Result<(), Self::Error>;
+
+    /// Processes the next chunk of `input`.
+    ///
+    /// # Parameters
+    ///
+    /// - `input`: The next chunk. May have any length.
+    /// - `output`: Output buffer, at least `input.len() + BLOCK_SIZE` bytes long.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes written to `output`.
+    fn update(&mut self, input: &[u8], output: &mut [u8]) -> Result<usize, Self::Error>;
+
+    /// Ends the stream, writing any buffered output.
+    ///
+    /// # Parameters
+    ///
+    /// - `output`: Output buffer, at least `BLOCK_SIZE` bytes long.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes written, or an error of kind
+    /// [`ErrorKind::InvalidLength`] if a mode without padding is left with a
+    /// partial block.
+    fn finish(&mut self, output: &mut [u8]) -> Result<usize, Self::Error>;
+}
+
+/// Cipher stream chunks running on an interrupt-driven engine.
+///
+/// Streams are started with [`CipherStream::begin`]; only the data-moving
+/// calls are split into start and completion.
+pub trait CipherStreamStart: CipherStream {
+    type UpdateOperation<'a>: Operation<Output = usize, Error = Self::Error>
+    where
+        Self: 'a;
+    type FinishOperation<'a>: Operation<Output = usize, Error = Self::Error>
+    where
+        Self: 'a;
+
+    /// Starts processing the next chunk. Completes like [`CipherStream::update`].
+    fn start_update<'a>(
+        &'a mut self,
+        input: &'a [u8],
+        output: &'a mut [u8],
+    ) -> Result<Self::UpdateOperation<'a>, Self::Error>;
+
+    /// Starts ending the stream. Completes like [`CipherStream::finish`].
+    fn start_finish<'a>(&'a mut self, output: &'a mut [u8]) -> Result<Self::FinishOperation<'a>, Self::Error>;
+}
@@ -0,0 +1,98 @@
+use super::ErrorType;
+
+/// How a requested frequency is mapped to one the hardware can produce.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Rounding {
+    /// Exactly the requested frequency, or fail with `ErrorKind::InvalidParameter`.
+    Exact,
+    /// The highest achievable frequency not above the request.
+    Down,
+    /// The lowest achievable frequency not below the request.
+    Up,
+    /// The achievable frequency closest to the request.
+    Nearest,
+}
+
+/// Gating control of peripheral clocks.
+///
+/// Clocks are identified by an implementation-defined [`ClockGate::ClockId`],
+/// typically an enum of the SoC's clock tree leaves.
+pub trait ClockGate: ErrorType {
+    type ClockId: Copy;
+
+    /// Ungates `clock`, enabling any parent clocks it depends on.
+    fn enable(&mut self, clock: Self::ClockId) -> Result<(), Self::Error>;
+
+    /// Gates `clock`. Parent clocks stay enabled while other children use them.
+    fn disable(&mut self, clock: Self::ClockId) -> Result<(), Self::Error>;
+
+    /// Returns whether `clock` is currently ungated.
+    fn is_enabled(&self, clock: Self::ClockId) -> Result<bool, Self::Error>;
+}
+
+/// Frequency control of clocks with a configurable divider or source.
+pub trait ClockRate: ClockGate {
+    /// Returns the current frequency of `clock` in Hz.
+    fn frequency_hz(&self, clock: Self::ClockId) -> Result<u32, Self::Error>;
+
+    /// Sets the frequency of `clock`.
+    ///
+    /// # Parameters
+    ///
+    /// - `clock`: The clock to configure.
+    /// - `hz`: Requested frequency in Hz.
+    /// - `rounding`: How to pick among achievable frequencies.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the frequency actually set, in Hz.
+    fn set_frequency_hz(
+        &mut self,
+        clock: Self::ClockId,
+        hz: u32,
+        rounding: Rounding,
+    ) -> Result<u32, Self::Error>;
+
+    /// Returns the frequency [`ClockRate::set_frequency_hz`] would set, without changing the clock.
+    fn round_frequency_hz(
+        &self,
+        clock: Self::ClockId,
+        hz: u32,
+        rounding: Rounding,
+    ) -> Result<u32, Self::Error>;
+}
+
+/// PLL parameters: `output = input / pre_divider * multiplier / post_divider`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct PllConfig {
+    pub pre_divider: u16,
+    pub multiplier: u16,
+    pub post_divider: u16,
+}
+
+impl PllConfig {
+    /// Output frequency for a reference clock of `input_hz`, or `None` on a
+    /// zero divider or overflow.
+    pub fn output_hz(&self, input_hz: u32) -> Option<u32> {
+        let hz = u64::from(input_hz) * u64::from(self.multiplier);
+        let div = u64::from(self.pre_divider) * u64::from(self.post_divider);
+        u32::try_from(hz.checked_div(div)?).ok()
+    }
+}
+
+/// Configuration of phase-locked loops.
+pub trait Pll: ErrorType {
+    type PllId: Copy;
+
+    /// Reprograms `pll` and waits for it to lock.
+    ///
+    /// Clocks derived from `pll` should be gated or switched to another
+    /// source first; implementations may refuse with `ErrorKind::Busy`.
+    fn configure(&mut self, pll: Self::PllId, config: PllConfig) -> Result<(), Self::Error>;
+
+    /// Returns the current configuration of `pll`.
+    fn config(&self, pll: Self::PllId) -> Result<PllConfig, Self::Error>;
+
+    /// Returns whether `pll` is locked.
+    fn is_locked(&self, pll: Self::PllId) -> Result<bool, Self::Error>;
+}
@@ -0,0 +1,112 @@
+use super::{Error, ErrorKind, ErrorType};
+
+/// Change on the host interface.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum HostEvent {
+    /// The host asserted its reset (PERST#, eSPI_RESET#, LPC reset, ...).
+    /// Host-visible state must be torn down and readiness withdrawn.
+    ResetAsserted,
+    /// The host released its reset and expects the device to come up.
+    ResetDeasserted,
+}
+
+/// Group of a [`BringUpStep`]. Steps run in this order on bring-up and in
+/// reverse order on tear-down.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum BringUpStage {
+    /// Clocks, power rails and pin muxing of the host interface.
+    Platform,
+    /// Measurement and verification that must finish before the host sees anything.
+    Security,
+    /// Host-visible functions, such as mailboxes, BARs and configuration space.
+    Endpoints,
+    /// Enabling the link itself.
+    Link,
+}
+
+/// One unit of host-interface bring-up.
+pub trait BringUpStep {
+    /// When the step runs.
+    fn stage(&self) -> BringUpStage;
+
+    /// Brings the step up. Returning an error aborts bring-up.
+    fn bring_up(&mut self) -> Result<(), ErrorKind>;
+
+    /// Undoes [`BringUpStep::bring_up`], e.g. because the host entered reset.
+    fn tear_down(&mut self);
+}
+
+/// Failure of [`HostInterface::bring_up`].
+#[derive(Debug)]
+pub enum BringUpError<E> {
+    /// A step failed; the steps already brought up were torn down.
+    Step(BringUpStage, ErrorKind),
+    /// Signalling readiness failed.
+    Interface(E),
+}
+
+impl<E: Error> Error for BringUpError<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            BringUpError::Step(_, kind) => *kind,
+            BringUpError::Interface(e) => e.kind(),
+        }
+    }
+}
+
+/// Device side of a host interface such as PCIe or eSPI.
+///
+/// Until readiness is signalled, the host is held off, e.g. by answering
+/// configuration requests with Configuration Request Retry Status.
+pub trait HostInterface: ErrorType {
+    /// Returns whether the host currently holds the device in reset.
+    fn is_host_in_reset(&mut self) -> Result<bool, Self::Error>;
+
+    /// Returns the oldest unhandled host event, without blocking.
+    fn poll_host_event(&mut self) -> Result<Option<HostEvent>, Self::Error>;
+
+    /// Signals to the host that the device is ready, or withdraws readiness.
+    fn set_ready(&mut self, ready: bool) -> Result<(), Self::Error>;
+
+    /// Runs `steps` in [`BringUpStage`] order and then signals readiness.
+    ///
+    /// `steps` is sorted in place; the order within a stage is unspecified.
+    /// If a step fails, the steps already brought up are torn down in
+    /// reverse order and readiness is not signalled.
+    fn bring_up(&mut self, steps: &mut [&mut dyn BringUpStep]) -> Result<(), BringUpError<Self::Error>> {
+        steps.sort_unstable_by_key(|step| step.stage());
+        for i in 0..steps.len() {
+            if let Err(kind) = steps[i].bring_up() {
+                let stage = steps[i].stage();
+                for step in steps[..i].iter_mut().rev() {
+                    step.tear_down();
+                }
+                return Err(BringUpError::Step(stage, kind));
+            }
+        }
+        self.set_ready(true).map_err(BringUpError::Interface)
+    }
+
+    /// Withdraws readiness and tears `steps` down in reverse [`BringUpStage`] order.
+    ///
+    /// Call on [`HostEvent::ResetAsserted`] with the steps passed to
+    /// [`HostInterface::bring_up`].
+    fn tear_down(&mut self, steps: &mut [&mut dyn BringUpStep]) -> Result<(), Self::Error> {
+        let result = self.set_ready(false);
+        steps.sort_unstable_by_key(|step| step.stage());
+        for step in steps.iter_mut().rev() {
+            step.tear_down();
+        }
+        result
+    }
+}
+
+/// Control of the host's reset line, for a root of trust that holds the
+/// host in reset until its firmware has been verified.
+pub trait HostResetControl: ErrorType {
+    /// Holds the host in reset.
+    fn assert_host_reset(&mut self) -> Result<(), Self::Error>;
+
+    /// Lets the host run.
+    fn release_host_reset(&mut self) -> Result<(), Self::Error>;
+}
@@ -0,0 +1,76 @@
+use super::ErrorType;
+
+/// Platform-level interrupt number.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct IrqId(pub u32);
+
+/// Condition that raises an interrupt.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Trigger {
+    LevelHigh,
+    LevelLow,
+    RisingEdge,
+    FallingEdge,
+    BothEdges,
+}
+
+/// Registration and masking of interrupt sources.
+pub trait InterruptController: ErrorType {
+    /// Sets how `irq` is triggered and its priority. Lower values are more urgent.
+    ///
+    /// Fails with `ErrorKind::InvalidParameter` for IDs or priorities the
+    /// controller does not have.
+    fn configure(&mut self, irq: IrqId, trigger: Trigger, priority: u8) -> Result<(), Self::Error>;
+
+    /// Unmasks `irq`.
+    fn enable(&mut self, irq: IrqId) -> Result<(), Self::Error>;
+
+    /// Masks `irq`. A pending interrupt stays pending.
+    fn disable(&mut self, irq: IrqId) -> Result<(), Self::Error>;
+
+    /// Returns whether `irq` is unmasked.
+    fn is_enabled(&self, irq: IrqId) -> Result<bool, Self::Error>;
+
+    /// Returns whether `irq` is pending, whether or not it is masked.
+    fn is_pending(&self, irq: IrqId) -> Result<bool, Self::Error>;
+}
+
+/// Receives interrupts from [`InterruptClaim::dispatch_interrupts`].
+pub trait InterruptHandler {
+    fn on_interrupt(&mut self, irq: IrqId);
+}
+
+/// Claim/complete handshake, called from interrupt context.
+///
+/// Methods take `&self` and must not block or allocate, so they can be
+/// called from an ISR through a shared reference. A claimed interrupt is
+/// not delivered again until it is completed.
+pub trait InterruptClaim {
+    /// Claims the most urgent pending, unmasked interrupt.
+    ///
+    /// # Returns
+    ///
+    /// The claimed interrupt, or `None` if nothing is pending.
+    fn claim(&self) -> Option<IrqId>;
+
+    /// Signals that `irq` was serviced, re-arming it.
+    fn complete(&self, irq: IrqId);
+
+    /// Claims, handles and completes every pending interrupt.
+    fn dispatch_interrupts(&self, handler: &mut impl InterruptHandler) {
+        while let Some(irq) = self.claim() {
+            handler.on_interrupt(irq);
+            self.complete(irq);
+        }
+    }
+}
+
+/// Interrupt wiring of a peripheral, so a dispatcher can route its sources
+/// without platform knowledge.
+pub trait InterruptSource {
+    /// Returns the interrupts the peripheral raises.
+    fn interrupts(&self) -> &[IrqId];
+
+    /// Services the peripheral after `irq` was claimed.
+    fn handle_interrupt(&mut self, irq: IrqId);
+}
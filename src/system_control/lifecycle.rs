@@ -0,0 +1,74 @@
+use crate::otp::{LifecycleState, OtpLifecycle};
+
+use super::ErrorType;
+
+/// Returns whether the lifecycle may move from `from` to `to`.
+///
+/// States only advance: Raw → Manufacturing → Production → Rma, and any
+/// state except Raw may be scrapped.
+pub const fn is_allowed_transition(from: LifecycleState, to: LifecycleState) -> bool {
+    matches!(
+        (from, to),
+        (LifecycleState::Raw, LifecycleState::Manufacturing)
+            | (LifecycleState::Manufacturing, LifecycleState::Production)
+            | (LifecycleState::Production, LifecycleState::Rma)
+            | (
+                LifecycleState::Manufacturing | LifecycleState::Production | LifecycleState::Rma,
+                LifecycleState::Scrapped
+            )
+    )
+}
+
+/// Permanent lifecycle transitions, persisted in OTP.
+pub trait LifecycleTransition: OtpLifecycle {
+    /// Advances the lifecycle to `to`.
+    ///
+    /// The transition is irreversible and usually takes effect after the next
+    /// reset. `token` authorizes it, e.g. an RMA unlock token whose hash was
+    /// provisioned in OTP; transitions that need no token ignore it.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success, or an error of kind
+    /// [`crate::error::ErrorKind::InvalidLifecycleState`] if
+    /// [`is_allowed_transition`] forbids the transition, or
+    /// [`crate::error::ErrorKind::NotPermitted`] if `token` is wrong.
+    fn transition(&mut self, to: LifecycleState, token: &[u8]) -> Result<(), Self::Error>;
+}
+
+/// Debug access granted to an external debugger, from none to full.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum DebugAccess {
+    Locked,
+    /// Trace and memory reads of non-secure state.
+    NonInvasive,
+    /// Halting debug, including secure state.
+    Full,
+}
+
+/// Challenge-response unlock of debug access.
+///
+/// The device hands out a fresh challenge; an authorized party signs it
+/// together with the requested access level, and the device checks the
+/// signed token against a key provisioned in OTP.
+pub trait AuthenticatedDebug: ErrorType {
+    /// Returns the debug access currently granted.
+    fn debug_access(&self) -> Result<DebugAccess, Self::Error>;
+
+    /// Writes a fresh challenge into `out`. Each challenge is valid for one unlock attempt.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes written.
+    fn debug_challenge(&mut self, out: &mut [u8]) -> Result<usize, Self::Error>;
+
+    /// Consumes `token`, a signed response to the last challenge, and grants `access`.
+    ///
+    /// Access stays granted until the next reset or [`AuthenticatedDebug::lock_debug`].
+    /// Fails with [`crate::error::ErrorKind::InvalidLifecycleState`] if the
+    /// lifecycle state does not allow `access` at all.
+    fn unlock_debug(&mut self, access: DebugAccess, token: &[u8]) -> Result<(), Self::Error>;
+
+    /// Revokes any granted debug access.
+    fn lock_debug(&mut self) -> Result<(), Self::Error>;
+}
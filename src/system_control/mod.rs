@@ -0,0 +1,20 @@
+//! System control traits.
+//!
+//! Chip-level services that are not tied to a single peripheral, such as
+//! clocks, interrupts, watchdogs, reset management, power states, host
+//! interface readiness and device lifecycle.
+
+pub mod clock;
+pub mod host;
+pub mod interrupt;
+pub mod lifecycle;
+pub mod power;
+pub mod reset;
+pub mod watchdog;
+
+pub use crate::error::{Error, ErrorKind};
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
@@ -0,0 +1,199 @@
+use super::{Error, ErrorKind, ErrorType};
+
+/// System power state, from fully running to off.
+///
+/// Deeper states save more power but retain less context and take longer to
+/// leave. Not every platform implements every state.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub enum PowerState {
+    /// Fully running.
+    Run,
+    /// CPU clock gated; peripherals keep running.
+    Idle,
+    /// CPU and most peripheral clocks gated; RAM retained.
+    Sleep,
+    /// Most power domains off; RAM and always-on domain retained.
+    DeepSleep,
+    /// Only the always-on domain is powered; waking goes through reset.
+    Hibernate,
+}
+
+/// A source that can wake the system from a low-power state.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum WakeSource {
+    /// GPIO pin with the given implementation-defined number.
+    Gpio(u32),
+    /// RTC alarm.
+    Rtc,
+    /// Low-power timer.
+    Timer,
+    /// Bus or peripheral activity, e.g. an I3C address match.
+    Peripheral(u32),
+    /// Implementation-defined source.
+    Other(u32),
+}
+
+/// Notification of power-state transitions, implemented by peripheral drivers
+/// that must save or quiesce state around a suspend.
+pub trait SuspendHook {
+    /// Called before `state` is entered.
+    ///
+    /// Returning an error vetoes the transition.
+    fn pre_suspend(&mut self, state: PowerState) -> Result<(), ErrorKind>;
+
+    /// Called after the system returns from `state`, or after the
+    /// transition was abandoned.
+    fn post_resume(&mut self, state: PowerState);
+}
+
+/// Position of a device in the suspend order.
+///
+/// Devices are suspended from [`PowerLayer::Device`] up to
+/// [`PowerLayer::Platform`] and resumed in the reverse order, so a bus
+/// controller is still running while the devices on it save their context,
+/// and is running again before they restore it.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum PowerLayer {
+    /// Device driver using a bus, e.g. a sensor or flash driver.
+    Device,
+    /// Bus or storage controller, e.g. SPI, I2C, I3C or a block device.
+    Bus,
+    /// Shared infrastructure such as DMA engines and GPIO banks.
+    Infrastructure,
+    /// Clocks, pin muxing and other chip-level services.
+    Platform,
+}
+
+/// A driver that can save its context and power down around a system suspend.
+///
+/// Bus and block-device drivers should implement this so a power manager can
+/// quiesce every driver with [`PowerControl::suspend_devices`] without knowing
+/// its type. The context is kept by the driver itself.
+pub trait PowerManaged {
+    /// Where the device sits in the suspend order.
+    fn power_layer(&self) -> PowerLayer;
+
+    /// Finishes or refuses outstanding work and saves the hardware context
+    /// that `state` does not retain.
+    ///
+    /// Returning an error vetoes the transition.
+    fn save_context(&mut self, state: PowerState) -> Result<(), ErrorKind>;
+
+    /// Puts the device into its lowest power mode compatible with `state`.
+    fn enter_low_power(&mut self, state: PowerState) -> Result<(), ErrorKind>;
+
+    /// Leaves low power and restores the context saved for `state`.
+    ///
+    /// Also called if the transition was abandoned after `save_context`.
+    fn restore_context(&mut self, state: PowerState) -> Result<(), ErrorKind>;
+}
+
+/// Restores `devices` in reverse order, returning the first failure.
+fn restore_devices(devices: &mut [&mut dyn PowerManaged], state: PowerState) -> Result<(), ErrorKind> {
+    let mut result = Ok(());
+    for device in devices.iter_mut().rev() {
+        if let Err(kind) = device.restore_context(state) {
+            result = result.and(Err(kind));
+        }
+    }
+    result
+}
+
+/// Failure of [`PowerControl::suspend`].
+#[derive(Debug)]
+pub enum SuspendError<E> {
+    /// A [`SuspendHook`] vetoed the transition.
+    Vetoed(ErrorKind),
+    /// Entering the power state failed.
+    Enter(E),
+    /// A device failed to restore its context after wake-up.
+    ///
+    /// The remaining devices were still restored.
+    Restore(ErrorKind),
+}
+
+impl<E: Error> Error for SuspendError<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            SuspendError::Vetoed(kind) | SuspendError::Restore(kind) => *kind,
+            SuspendError::Enter(e) => e.kind(),
+        }
+    }
+}
+
+/// Control of system power states.
+pub trait PowerControl: ErrorType {
+    /// Returns whether the platform implements `state`.
+    fn is_supported(&self, state: PowerState) -> bool;
+
+    /// Enters `state` and returns once the system has woken up.
+    ///
+    /// For [`PowerState::Hibernate`] this does not return on success; the
+    /// system restarts through reset instead.
+    fn enter(&mut self, state: PowerState) -> Result<(), Self::Error>;
+
+    /// Notifies `hooks`, enters `state` and notifies them again on wake-up.
+    ///
+    /// Hooks are resumed in reverse order. If a hook vetoes, the hooks that
+    /// were already suspended are resumed and `state` is not entered.
+    fn suspend(
+        &mut self,
+        state: PowerState,
+        hooks: &mut [&mut dyn SuspendHook],
+    ) -> Result<(), SuspendError<Self::Error>> {
+        for i in 0..hooks.len() {
+            if let Err(kind) = hooks[i].pre_suspend(state) {
+                for hook in hooks[..i].iter_mut().rev() {
+                    hook.post_resume(state);
+                }
+                return Err(SuspendError::Vetoed(kind));
+            }
+        }
+        let result = self.enter(state);
+        for hook in hooks.iter_mut().rev() {
+            hook.post_resume(state);
+        }
+        result.map_err(SuspendError::Enter)
+    }
+
+    /// Suspends `devices` in [`PowerLayer`] order, enters `state` and restores
+    /// them in reverse order on wake-up.
+    ///
+    /// `devices` is sorted in place; the order within a layer is unspecified. If a device fails to save its context or
+    /// to enter low power, the devices already handled are restored and
+    /// `state` is not entered.
+    fn suspend_devices(
+        &mut self,
+        state: PowerState,
+        devices: &mut [&mut dyn PowerManaged],
+    ) -> Result<(), SuspendError<Self::Error>> {
+        devices.sort_unstable_by_key(|device| device.power_layer());
+        for i in 0..devices.len() {
+            let device = &mut devices[i];
+            if let Err(kind) = device.save_context(state).and_then(|()| device.enter_low_power(state)) {
+                // The failing device may have saved its context already.
+                let _ = restore_devices(&mut devices[..=i], state);
+                return Err(SuspendError::Vetoed(kind));
+            }
+        }
+        let result = self.enter(state);
+        let restored = restore_devices(devices, state);
+        result.map_err(SuspendError::Enter)?;
+        restored.map_err(SuspendError::Restore)
+    }
+}
+
+/// Configuration of wake sources and query of the last wake reason.
+pub trait WakeControl: ErrorType {
+    /// Allows `source` to wake the system.
+    fn enable_wake_source(&mut self, source: WakeSource) -> Result<(), Self::Error>;
+
+    /// Stops `source` from waking the system.
+    fn disable_wake_source(&mut self, source: WakeSource) -> Result<(), Self::Error>;
+
+    /// Returns the source of the most recent wake-up, or `None` if the system
+    /// has not been suspended since reset.
+    fn last_wake_reason(&self) -> Result<Option<WakeSource>, Self::Error>;
+}
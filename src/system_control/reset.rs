@@ -0,0 +1,28 @@
+use super::ErrorType;
+
+/// Cause of the most recent reset.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum ResetReason {
+    /// Power-on reset.
+    PowerOn,
+    /// The watchdog expired.
+    Watchdog,
+    /// Reset requested by software.
+    Software,
+    /// Supply voltage dropped below the brown-out threshold.
+    BrownOut,
+    /// The external reset pin was asserted.
+    External,
+    /// Implementation-defined cause.
+    Other(u32),
+}
+
+/// Query of the reset cause, for boot-time policy decisions.
+pub trait ResetCause: ErrorType {
+    /// Returns the cause of the most recent reset.
+    fn reset_reason(&self) -> Result<ResetReason, Self::Error>;
+
+    /// Clears the sticky reset-cause state so the next boot reports a fresh cause.
+    fn clear_reset_reason(&mut self) -> Result<(), Self::Error>;
+}
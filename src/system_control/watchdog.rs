@@ -0,0 +1,24 @@
+use super::ErrorType;
+
+/// Watchdog timer.
+pub trait Watchdog: ErrorType {
+    /// Timeout representation, e.g. milliseconds or a hardware tick count.
+    type Timeout: Copy;
+
+    /// Starts the watchdog with the given timeout.
+    ///
+    /// Fails with `ErrorKind::InvalidParameter` if the timeout cannot be
+    /// represented by the hardware.
+    fn start(&mut self, timeout: Self::Timeout) -> Result<(), Self::Error>;
+
+    /// Restarts the countdown. Must be called before the timeout elapses.
+    fn feed(&mut self) -> Result<(), Self::Error>;
+}
+
+/// Watchdog that can be stopped after it has been started.
+///
+/// Not every watchdog supports this; some lock once started.
+pub trait WatchdogDisable: Watchdog {
+    /// Stops the watchdog.
+    fn disable(&mut self) -> Result<(), Self::Error>;
+}
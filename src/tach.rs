@@ -0,0 +1,24 @@
+pub use crate::error::{Error, ErrorKind};
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// Tachometer input, e.g. a fan speed sensor.
+pub trait Tach: ErrorType {
+    /// Sets the number of tach pulses the fan emits per revolution.
+    fn set_pulses_per_revolution(&mut self, pulses: u8) -> Result<(), Self::Error>;
+
+    /// Returns the measured speed in revolutions per minute.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the speed, or an error of kind [`ErrorKind::Stalled`]
+    /// if no pulse was seen within the stall timeout.
+    fn rpm(&mut self) -> Result<u32, Self::Error>;
+
+    /// Sets how long the input may stay without pulses before [`Tach::rpm`]
+    /// reports [`ErrorKind::Stalled`].
+    fn set_stall_timeout_ms(&mut self, timeout_ms: u32) -> Result<(), Self::Error>;
+}
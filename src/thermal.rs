@@ -0,0 +1,66 @@
+pub use crate::error::{Error, ErrorKind};
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// Temperature in millidegrees Celsius.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct MilliCelsius(pub i32);
+
+impl MilliCelsius {
+    pub const fn from_celsius(degrees: i16) -> Self {
+        Self(degrees as i32 * 1000)
+    }
+
+    /// Returns the whole degrees, rounded towards negative infinity.
+    pub const fn celsius(self) -> i32 {
+        self.0.div_euclid(1000)
+    }
+}
+
+/// A temperature threshold crossed by an alert.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Threshold {
+    /// The temperature fell below the low threshold.
+    Low,
+    /// The temperature rose above the high threshold.
+    High,
+    /// The temperature rose above the critical threshold; hardware may already
+    /// have started a shutdown.
+    Critical,
+}
+
+/// Temperature sensor, e.g. an on-die diode or a remote thermistor.
+pub trait Thermal: ErrorType {
+    /// Returns the current temperature.
+    fn temperature(&mut self) -> Result<MilliCelsius, Self::Error>;
+}
+
+/// Threshold alerts, typically wired to an interrupt or an alert pin.
+pub trait ThermalAlert: Thermal {
+    /// Sets a threshold. Alerts are raised when the temperature crosses it.
+    ///
+    /// # Parameters
+    ///
+    /// - `threshold`: The threshold to set.
+    /// - `limit`: Temperature of the threshold.
+    /// - `hysteresis`: Distance the temperature must move back before the alert clears.
+    fn set_threshold(
+        &mut self,
+        threshold: Threshold,
+        limit: MilliCelsius,
+        hysteresis: MilliCelsius,
+    ) -> Result<(), Self::Error>;
+
+    /// Stops raising alerts for `threshold`.
+    fn disable_threshold(&mut self, threshold: Threshold) -> Result<(), Self::Error>;
+
+    /// Returns the most severe active alert without blocking.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the alert, or `None` if the temperature is within all thresholds.
+    fn poll_alert(&mut self) -> Result<Option<Threshold>, Self::Error>;
+}
@@ -0,0 +1,99 @@
+//! Time keeping and delays.
+
+use core::time::Duration;
+
+/// Point in time, in ticks of a [`Monotonic`] counter.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Instant(pub u64);
+
+/// Wall-clock time in seconds since 1970-01-01T00:00:00Z, ignoring leap seconds.
+///
+/// Shared by the [`crate::rtc`] traits and certificate validity checks.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct UnixTime(pub u64);
+
+impl UnixTime {
+    pub const EPOCH: Self = Self(0);
+
+    pub const fn seconds(self) -> u64 {
+        self.0
+    }
+
+    /// Returns the time `secs` seconds later, saturating at the maximum.
+    pub const fn add_seconds(self, secs: u64) -> Self {
+        Self(self.0.saturating_add(secs))
+    }
+}
+
+/// Free-running counter that never goes backwards.
+pub trait Monotonic {
+    /// Tick frequency in Hz.
+    fn frequency_hz(&self) -> u32;
+
+    /// Returns the current tick count.
+    fn now(&self) -> Instant;
+
+    /// Converts `duration` to ticks, rounding up.
+    fn ticks_for(&self, duration: Duration) -> u64 {
+        let ticks = duration.as_nanos() * u128::from(self.frequency_hz());
+        u64::try_from(ticks.div_ceil(1_000_000_000)).unwrap_or(u64::MAX)
+    }
+}
+
+/// Absolute point in time after which an operation should give up.
+///
+/// Bus traits that wait on the hardware can take a `Deadline` and fail with
+/// `ErrorKind::Timeout` once it has passed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Deadline(Instant);
+
+impl Deadline {
+    /// Deadline `timeout` from now.
+    pub fn after(clock: &impl Monotonic, timeout: Duration) -> Self {
+        Self(Instant(clock.now().0.saturating_add(clock.ticks_for(timeout))))
+    }
+
+    /// Deadline at `instant`.
+    pub fn at(instant: Instant) -> Self {
+        Self(instant)
+    }
+
+    /// Deadline that never expires.
+    pub fn never() -> Self {
+        Self(Instant(u64::MAX))
+    }
+
+    pub fn instant(&self) -> Instant {
+        self.0
+    }
+
+    /// Returns whether the deadline has passed.
+    pub fn has_expired(&self, clock: &impl Monotonic) -> bool {
+        clock.now() >= self.0
+    }
+
+    /// Returns the ticks left until the deadline, or 0 once it has passed.
+    pub fn remaining_ticks(&self, clock: &impl Monotonic) -> u64 {
+        self.0 .0.saturating_sub(clock.now().0)
+    }
+}
+
+/// Blocking delay.
+pub trait DelayNs {
+    /// Pauses execution for at least `ns` nanoseconds.
+    fn delay_ns(&mut self, ns: u32);
+
+    /// Pauses execution for at least `us` microseconds.
+    fn delay_us(&mut self, us: u32) {
+        for _ in 0..us {
+            self.delay_ns(1_000);
+        }
+    }
+
+    /// Pauses execution for at least `ms` milliseconds.
+    fn delay_ms(&mut self, ms: u32) {
+        for _ in 0..ms {
+            self.delay_ns(1_000_000);
+        }
+    }
+}
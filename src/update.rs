@@ -0,0 +1,471 @@
+//! Firmware update agents, modelled on PLDM for Firmware Update (DSP0267).
+//!
+//! An update server (e.g. a BMC) drives an [`UpdateAgent`] on the device
+//! through a fixed sequence: inventory, per-component transfer, verification,
+//! apply and finally activation. Images are staged on any [`BlockDevice`],
+//! typically the inactive slot of a [`crate::flash_partition::AbImage`],
+//! through [`BlockStaging`], and checked by an [`ImageVerifier`] built from
+//! the crypto traits, e.g. [`crate::integrity::DigestRegion`] plus a
+//! signature verifier.
+
+use core::fmt::Debug;
+
+use crate::block_device::{self, BlockDevice, ReadBlockDevice};
+use crate::messaging::common::{CodecError, Decode, Encode, MaxSize};
+
+/// Error kind.
+///
+/// This represents a common set of firmware update errors. Implementations are
+/// free to define more specific or additional error types. However, by providing
+/// a mapping to these common errors, generic code can still react to them.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The device has no component with the given ID.
+    UnknownComponent,
+
+    /// The request is not valid in the current [`UpdateState`].
+    InvalidState,
+
+    /// The image does not fit in the staging area.
+    ImageTooLarge,
+
+    /// A chunk lies outside the announced image or is misaligned.
+    InvalidOffset,
+
+    /// Staging storage failed.
+    Storage,
+
+    /// The image failed verification.
+    VerificationFailed,
+
+    /// The requested activation method is not supported by the component.
+    UnsupportedActivation,
+
+    /// A different error occurred.
+    Other,
+}
+
+pub trait Error: core::fmt::Debug {
+    /// Convert error to a generic error kind
+    ///
+    /// By using this method, errors freely defined by HAL implementations
+    /// can be converted to a set of generic errors upon which generic
+    /// code can act.
+    fn kind(&self) -> ErrorKind;
+}
+
+impl Error for core::convert::Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+impl Error for ErrorKind {
+    fn kind(&self) -> ErrorKind {
+        *self
+    }
+}
+
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+/// Component identifier, unique within its classification.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct ComponentId(pub u16);
+
+/// Ways a component can start running a new image.
+///
+/// Values combine with `|`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ActivationMethods(pub u16);
+
+impl ActivationMethods {
+    /// The component switches to the new image on activation, without reset.
+    pub const AUTOMATIC: Self = Self(1 << 0);
+    /// The component resets itself on activation.
+    pub const SELF_CONTAINED: Self = Self(1 << 1);
+    /// The new image runs after a reset of the component's medium, e.g. a host reset.
+    pub const MEDIUM_RESET: Self = Self(1 << 2);
+    /// The new image runs after a system reboot.
+    pub const SYSTEM_REBOOT: Self = Self(1 << 3);
+    /// The new image runs after a DC power cycle.
+    pub const DC_POWER_CYCLE: Self = Self(1 << 4);
+    /// The new image runs after an AC power cycle.
+    pub const AC_POWER_CYCLE: Self = Self(1 << 5);
+
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl core::ops::BitOr for ActivationMethods {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// One entry of the firmware inventory.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ComponentInfo {
+    pub id: ComponentId,
+    /// Kind of component, e.g. firmware or a configuration blob (DSP0267 table 27).
+    pub classification: u16,
+    /// Comparison stamp of the running image; higher is newer.
+    pub active_stamp: u32,
+    /// Comparison stamp of an applied image waiting for activation.
+    pub pending_stamp: Option<u32>,
+    /// Activation methods the component supports.
+    pub activation: ActivationMethods,
+    /// Largest image the component accepts, in bytes.
+    pub max_image_size: usize,
+}
+
+/// Reporting of the firmware components on the device.
+pub trait ComponentInventory: ErrorType {
+    /// Number of components.
+    fn component_count(&self) -> usize;
+
+    /// Returns component number `index`, in `0..component_count()`.
+    fn component(&self, index: usize) -> Result<ComponentInfo, Self::Error>;
+
+    /// Writes the version string of the running image, or with `pending` set
+    /// of the pending image, into `out`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of bytes written.
+    fn version_string(&self, id: ComponentId, pending: bool, out: &mut [u8]) -> Result<usize, Self::Error>;
+
+    /// Returns the component with `id`, if any.
+    fn find_component(&self, id: ComponentId) -> Result<Option<ComponentInfo>, Self::Error> {
+        for index in 0..self.component_count() {
+            let info = self.component(index)?;
+            if info.id == id {
+                return Ok(Some(info));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// State of an update agent (DSP0267 figure 6).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum UpdateState {
+    /// No update in progress.
+    Idle,
+    /// An update was requested; waiting for the next component.
+    ReadyToTransfer,
+    /// Receiving a component image.
+    Download,
+    /// Verifying a received image.
+    Verify,
+    /// Writing a verified image to its final location.
+    Apply,
+    /// All components applied; waiting for activation.
+    ReadyToActivate,
+}
+
+/// Outcome of [`ImageVerifier::verify_image`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Verification {
+    /// The image is authentic; its comparison stamp is recorded for the inventory.
+    Valid { stamp: u32 },
+    /// The image digest does not match its manifest.
+    DigestMismatch,
+    /// The manifest signature does not verify.
+    InvalidSignature,
+    /// The image is older than the anti-rollback security version.
+    Rollback,
+}
+
+/// Authenticity check of a staged image.
+pub trait ImageVerifier: ErrorType {
+    /// Checks the first `len` bytes of `staged` as an image for `component`.
+    fn verify_image<B: ReadBlockDevice + ?Sized>(
+        &mut self,
+        component: ComponentId,
+        staged: &mut B,
+        len: usize,
+    ) -> Result<Verification, Self::Error>;
+}
+
+/// When an activated image takes effect.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Activation {
+    /// Activate now with the given method.
+    Now(ActivationMethods),
+    /// Activate automatically on the next occurrence of the given method,
+    /// e.g. the next system reboot.
+    Deferred(ActivationMethods),
+}
+
+/// Receives state changes and transfer progress from an [`UpdateAgent`].
+pub trait UpdateObserver {
+    fn on_state(&mut self, component: Option<ComponentId>, state: UpdateState);
+
+    /// Called after each stored chunk with the bytes received and the image size.
+    fn on_progress(&mut self, component: ComponentId, received: usize, total: usize);
+}
+
+impl UpdateObserver for () {
+    fn on_state(&mut self, _component: Option<ComponentId>, _state: UpdateState) {}
+
+    fn on_progress(&mut self, _component: ComponentId, _received: usize, _total: usize) {}
+}
+
+/// Device side of a firmware update.
+///
+/// Calls outside the order given by [`UpdateState`] fail with
+/// [`ErrorKind::InvalidState`]; `cancel_update` is accepted in every state.
+pub trait UpdateAgent: ComponentInventory {
+    fn state(&self) -> UpdateState;
+
+    /// Starts an update session. Moves from `Idle` to `ReadyToTransfer`.
+    fn request_update(&mut self) -> Result<(), Self::Error>;
+
+    /// Announces an image of `size` bytes for `component`. Moves to `Download`.
+    fn begin_component(&mut self, component: ComponentId, size: usize) -> Result<(), Self::Error>;
+
+    /// Stores `data` at byte `offset` of the image being downloaded.
+    fn write_chunk(&mut self, offset: usize, data: &[u8]) -> Result<(), Self::Error>;
+
+    /// Ends the download and verifies the image. Moves through `Verify` to
+    /// `Apply` if it is valid, or back to `ReadyToTransfer` if not.
+    fn verify_component(&mut self) -> Result<Verification, Self::Error>;
+
+    /// Writes the verified image to its final location. Moves to
+    /// `ReadyToTransfer` for the next component.
+    fn apply_component(&mut self) -> Result<(), Self::Error>;
+
+    /// Ends the transfer phase. Moves to `ReadyToActivate`.
+    fn finish_transfers(&mut self) -> Result<(), Self::Error>;
+
+    /// Activates every applied component. Moves to `Idle`.
+    ///
+    /// Fails with [`ErrorKind::UnsupportedActivation`] if a component does
+    /// not support the requested method. With [`Activation::Now`] and a
+    /// reset-based method, this may not return.
+    fn activate(&mut self, activation: Activation) -> Result<(), Self::Error>;
+
+    /// Abandons the session, discarding staged images. Moves to `Idle`.
+    fn cancel_update(&mut self) -> Result<(), Self::Error>;
+}
+
+/// Transfer progress as published to update servers, e.g. over a
+/// [`crate::messaging::pubsub::Publisher`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ProgressReport {
+    pub component: ComponentId,
+    pub state: UpdateState,
+    /// Download progress in percent.
+    pub percent: u8,
+}
+
+impl MaxSize for ProgressReport {
+    const MAX_SIZE: usize = 4;
+}
+
+impl Encode for ProgressReport {
+    fn encode(&self, buf: &mut [u8]) -> Result<usize, CodecError> {
+        let buf = buf.get_mut(..Self::MAX_SIZE).ok_or(CodecError::BufferTooSmall)?;
+        buf[..2].copy_from_slice(&self.component.0.to_le_bytes());
+        buf[2] = self.state as u8;
+        buf[3] = self.percent;
+        Ok(Self::MAX_SIZE)
+    }
+}
+
+impl<'a> Decode<'a> for ProgressReport {
+    fn decode(buf: &'a [u8]) -> Result<Self, CodecError> {
+        let &[c0, c1, state, percent] = buf else {
+            return Err(CodecError::Malformed);
+        };
+        let state = match state {
+            0 => UpdateState::Idle,
+            1 => UpdateState::ReadyToTransfer,
+            2 => UpdateState::Download,
+            3 => UpdateState::Verify,
+            4 => UpdateState::Apply,
+            5 => UpdateState::ReadyToActivate,
+            _ => return Err(CodecError::Malformed),
+        };
+        if percent > 100 {
+            return Err(CodecError::Malformed);
+        }
+        Ok(Self {
+            component: ComponentId(u16::from_le_bytes([c0, c1])),
+            state,
+            percent,
+        })
+    }
+}
+
+/// Largest program size [`BlockStaging`] can pad a final chunk to, in bytes.
+pub const MAX_PROGRAM_SIZE: usize = 256;
+
+/// Failure of [`BlockStaging`].
+#[derive(Debug)]
+pub enum StagingError<E> {
+    /// The image is larger than the device.
+    ImageTooLarge,
+    /// A chunk is outside the image, or not aligned to the program size.
+    InvalidOffset,
+    /// The device failed.
+    Device(E),
+}
+
+impl<E: block_device::Error> Error for StagingError<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            StagingError::ImageTooLarge => ErrorKind::ImageTooLarge,
+            StagingError::InvalidOffset => ErrorKind::InvalidOffset,
+            StagingError::Device(_) => ErrorKind::Storage,
+        }
+    }
+}
+
+/// Image staging on a block device, such as the inactive A/B slot.
+///
+/// Agents implement [`UpdateAgent::begin_component`] and
+/// [`UpdateAgent::write_chunk`] on top of it.
+pub struct BlockStaging<D> {
+    device: D,
+    size: usize,
+}
+
+impl<D: BlockDevice> BlockStaging<D> {
+    pub fn new(device: D) -> Self {
+        Self { device, size: 0 }
+    }
+
+    /// Erases enough of the device for an image of `size` bytes.
+    pub fn begin(&mut self, size: usize) -> Result<(), StagingError<D::Error>> {
+        let erase_size = self.device.erase_size().max(1);
+        let erase_len = size.div_ceil(erase_size) * erase_size;
+        if erase_len > self.device.capacity() {
+            return Err(StagingError::ImageTooLarge);
+        }
+        self.device.erase(0, erase_len).map_err(StagingError::Device)?;
+        self.size = size;
+        Ok(())
+    }
+
+    /// Programs `data` at image `offset`.
+    ///
+    /// `offset` must be aligned to the device program size; so must the
+    /// length of every chunk except the one ending the image, which is
+    /// padded with `0xFF`.
+    pub fn write(&mut self, offset: usize, data: &[u8]) -> Result<(), StagingError<D::Error>> {
+        let program_size = self.device.program_size().max(1);
+        let end = offset.checked_add(data.len()).ok_or(StagingError::InvalidOffset)?;
+        if end > self.size || !offset.is_multiple_of(program_size) {
+            return Err(StagingError::InvalidOffset);
+        }
+        let aligned = data.len() - data.len() % program_size;
+        if aligned != data.len() && (end != self.size || program_size > MAX_PROGRAM_SIZE) {
+            return Err(StagingError::InvalidOffset);
+        }
+        if aligned > 0 {
+            self.device
+                .program(offset, &data[..aligned])
+                .map_err(StagingError::Device)?;
+        }
+        if aligned != data.len() {
+            let mut last = [0xFF; MAX_PROGRAM_SIZE];
+            last[..data.len() - aligned].copy_from_slice(&data[aligned..]);
+            self.device
+                .program(offset + aligned, &last[..program_size])
+                .map_err(StagingError::Device)?;
+        }
+        Ok(())
+    }
+
+    /// Size of the image announced to [`BlockStaging::begin`].
+    pub fn image_size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the device, e.g. to hand it to an [`ImageVerifier`].
+    pub fn device(&mut self) -> &mut D {
+        &mut self.device
+    }
+
+    pub fn into_inner(self) -> D {
+        self.device
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::block_device::RamBlockDevice;
+
+    #[test]
+    fn progress_report_round_trips() {
+        let report = ProgressReport {
+            component: ComponentId(0x0102),
+            state: UpdateState::Download,
+            percent: 42,
+        };
+        let mut buf = [0u8; ProgressReport::MAX_SIZE];
+        assert_eq!(report.encode(&mut buf), Ok(4));
+        assert_eq!(buf, [0x02, 0x01, 2, 42]);
+        assert_eq!(ProgressReport::decode(&buf), Ok(report));
+        assert_eq!(report.encode(&mut [0u8; 3]), Err(CodecError::BufferTooSmall));
+    }
+
+    #[test]
+    fn progress_report_rejects_invalid_fields() {
+        assert_eq!(ProgressReport::decode(&[0, 0, 6, 0]), Err(CodecError::Malformed));
+        assert_eq!(ProgressReport::decode(&[0, 0, 5, 100]).map(|r| r.percent), Ok(100));
+        assert_eq!(ProgressReport::decode(&[0, 0, 5, 101]), Err(CodecError::Malformed));
+        assert_eq!(ProgressReport::decode(&[0, 0, 5]), Err(CodecError::Malformed));
+    }
+
+    fn staging(size: usize) -> BlockStaging<RamBlockDevice<64>> {
+        let mut staging = BlockStaging::new(RamBlockDevice::new(16, 4).unwrap());
+        staging.begin(size).unwrap();
+        staging
+    }
+
+    #[test]
+    fn final_chunk_is_padded() {
+        let mut staging = staging(10);
+        staging.write(0, &[1, 2, 3, 4]).unwrap();
+        staging.write(4, &[5, 6, 7, 8, 9, 10]).unwrap();
+        let bytes = staging.into_inner();
+        assert_eq!(bytes.as_bytes()[..12], [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 0xFF, 0xFF]);
+    }
+
+    #[test]
+    fn misaligned_chunks_are_rejected() {
+        let mut staging = staging(10);
+        let result = staging.write(2, &[0; 4]);
+        assert!(matches!(result, Err(StagingError::InvalidOffset)));
+        // Only the chunk ending the image may have a partial length.
+        assert!(matches!(staging.write(0, &[0; 6]), Err(StagingError::InvalidOffset)));
+        assert!(matches!(staging.write(8, &[0; 4]), Err(StagingError::InvalidOffset)));
+        assert_eq!(staging.into_inner().as_bytes(), &[0xFF; 64]);
+    }
+
+    #[test]
+    fn image_larger_than_the_device() {
+        let mut staging = BlockStaging::new(RamBlockDevice::<64>::new(16, 4).unwrap());
+        assert!(matches!(staging.begin(65), Err(StagingError::ImageTooLarge)));
+        assert!(staging.begin(64).is_ok());
+    }
+
+    #[test]
+    fn unpaddable_final_chunk_programs_nothing() {
+        let mut staging = BlockStaging::new(RamBlockDevice::<1024>::new(512, 512).unwrap());
+        staging.begin(600).unwrap();
+        let result = staging.write(0, &[0; 600]);
+        assert_eq!(result.map_err(|e| e.kind()), Err(ErrorKind::InvalidOffset));
+        assert_eq!(staging.into_inner().as_bytes(), &[0xFF; 1024]);
+    }
+}